@@ -0,0 +1,98 @@
+use core_api::{
+    models::{ApiResponse, Category, Expense, LoginBody, LoginResponse},
+    paging::{ExpensePage, ExpenseQuery, MAX_PAGE_SIZE},
+};
+use reqwest::{
+    blocking::{Client as HttpClient, RequestBuilder},
+    Method,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use uuid::Uuid;
+
+use crate::error::CliError;
+
+pub struct Client {
+    base: String,
+    token: String,
+    http: HttpClient,
+}
+
+impl Client {
+    pub fn new(base: String, token: String) -> Self {
+        Client {
+            base: base.trim_end_matches('/').to_owned(),
+            token,
+            http: HttpClient::new(),
+        }
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http
+            .request(method, format!("{}/{path}", self.base))
+            .bearer_auth(&self.token)
+    }
+
+    fn send<T: DeserializeOwned + Serialize + Clone>(
+        request: RequestBuilder,
+    ) -> Result<T, CliError> {
+        let resp = request.send()?;
+        if !resp.status().is_success() {
+            return Err(CliError::Api(resp.text()?));
+        }
+        Ok(resp.json::<ApiResponse<T>>()?.data())
+    }
+
+    pub fn login(&self, body: &LoginBody) -> Result<LoginResponse, CliError> {
+        Self::send(self.request(Method::POST, "login").json(body))
+    }
+
+    pub fn categories(&self) -> Result<Vec<Category>, CliError> {
+        Self::send(self.request(Method::GET, "categories?all_listed=true"))
+    }
+
+    /// Every expense matching the query, a page at a time. Servers that do not page
+    /// answer with every expense, which is then filtered and sorted here.
+    pub fn expenses(&self, mut query: ExpenseQuery) -> Result<Vec<Expense>, CliError> {
+        query.limit = MAX_PAGE_SIZE;
+        let mut expenses = vec![];
+        loop {
+            let path = format!("expenses?{}", query.to_query_string());
+            let resp = self.request(Method::GET, &path).send()?;
+            if !resp.status().is_success() {
+                return Err(CliError::Api(resp.text()?));
+            }
+            let body = resp.text()?;
+            let page = match serde_json::from_str::<ApiResponse<ExpensePage>>(&body) {
+                Ok(page) => page.data(),
+                Err(e) => {
+                    let all = serde_json::from_str::<ApiResponse<Vec<Expense>>>(&body)
+                        .map_err(|_| e)?
+                        .data();
+                    let mut matching = query.filter.apply(&all, None);
+                    query.options.sort(&mut matching);
+                    return Ok(matching);
+                }
+            };
+            expenses.extend(page.items);
+            match page.next_cursor {
+                Some(cursor) => query = query.cursor(Some(cursor)),
+                None => return Ok(expenses),
+            }
+        }
+    }
+
+    pub fn add_expense(&self, expense: &Expense) -> Result<Expense, CliError> {
+        let body = serde_json::json!({
+            "category_id": expense.category_id,
+            "date": expense.date.clone().unwrap_or_default(),
+            "amount": expense.amount,
+            "description": expense.description.clone().unwrap_or_default(),
+        });
+        Self::send(self.request(Method::POST, "expenses").json(&body))
+    }
+
+    pub fn delete_expense(&self, id: Uuid) -> Result<(), CliError> {
+        Self::send::<bool>(self.request(Method::DELETE, &format!("expenses/{id}")))?;
+        Ok(())
+    }
+}
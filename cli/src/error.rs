@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid data: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("server error: {0}")]
+    Api(String),
+    #[error("not logged in, run `finance-home login` first")]
+    NotLoggedIn,
+    #[error("{0}")]
+    Usage(String),
+}
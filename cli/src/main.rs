@@ -0,0 +1,221 @@
+mod api;
+mod error;
+mod session;
+
+use std::{
+    io::{self, BufRead, Write},
+    process::ExitCode,
+};
+
+use clap::{Parser, Subcommand};
+use core_api::{
+    filters::ExpenseFilter,
+    models::{parse_date, Category, Expense, LoginBody},
+    paging::ExpenseQuery,
+    session::Session,
+    sorting::ListOptions,
+};
+use time::{util::days_in_month, Date, Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::{api::Client, error::CliError};
+
+/// Command-line companion to the Finance Home desktop app.
+#[derive(Parser)]
+#[command(name = "finance-home", version)]
+struct Cli {
+    /// Print JSON instead of text, for scripting.
+    #[arg(long, global = true)]
+    json: bool,
+    /// API base URL. Defaults to the server configured in the desktop app.
+    #[arg(long, global = true)]
+    server: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Log in and store the session shared with the desktop app.
+    Login {
+        #[arg(long)]
+        email: String,
+        /// Asked for on standard input when missing.
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Add an expense.
+    Add {
+        amount: i32,
+        /// Category name, case insensitive.
+        #[arg(long)]
+        category: String,
+        /// `today`, `yesterday` or `YYYY-MM-DD`.
+        #[arg(long, default_value = "today")]
+        date: String,
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// List expenses.
+    List {
+        /// Only expenses in this `YYYY-MM` month.
+        #[arg(long)]
+        month: Option<String>,
+        /// Only expenses in this category, case insensitive.
+        #[arg(long)]
+        category: Option<String>,
+    },
+    /// Delete an expense by id.
+    Delete { id: Uuid },
+    /// List categories.
+    Categories,
+}
+
+fn today() -> Date {
+    OffsetDateTime::now_local()
+        .unwrap_or_else(|_| OffsetDateTime::now_utc())
+        .date()
+}
+
+fn parse_date_arg(date: &str) -> Result<Date, CliError> {
+    match date {
+        "today" => Ok(today()),
+        "yesterday" => Ok(today() - Duration::days(1)),
+        date => parse_date(date)
+            .ok_or_else(|| CliError::Usage(format!("invalid date `{date}`, use YYYY-MM-DD"))),
+    }
+}
+
+fn parse_month_arg(month: &str) -> Result<Date, CliError> {
+    parse_date(&format!("{month}-01"))
+        .ok_or_else(|| CliError::Usage(format!("invalid month `{month}`, use YYYY-MM")))
+}
+
+fn find_category(categories: Vec<Category>, name: &str) -> Result<Category, CliError> {
+    categories
+        .into_iter()
+        .find(|category| category.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| CliError::Usage(format!("no category named `{name}`")))
+}
+
+fn read_password() -> Result<String, CliError> {
+    eprint!("Password: ");
+    io::stderr().flush()?;
+    let mut password = String::new();
+    io::stdin().lock().read_line(&mut password)?;
+    Ok(password.trim_end_matches(['\r', '\n']).to_owned())
+}
+
+fn print_json<T: serde::Serialize>(value: &T) -> Result<(), CliError> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn print_expense(expense: &Expense) {
+    println!(
+        "{}  {:>10}  {}  {}  {}",
+        expense.date.as_deref().unwrap_or("----------"),
+        format!("${}", expense.amount),
+        expense.category_name,
+        expense.description.as_deref().unwrap_or_default(),
+        expense.id
+    );
+}
+
+fn run(cli: Cli) -> Result<(), CliError> {
+    let server = cli.server.unwrap_or_else(session::server_url);
+    if let Command::Login { email, password } = cli.command {
+        let password = match password {
+            Some(password) => password,
+            None => read_password()?,
+        };
+        let mut body = LoginBody::new();
+        body.email(email.clone());
+        body.password(password);
+        let response = Client::new(server, String::new()).login(&body)?;
+        session::save_session(&Session {
+            token: response.token,
+            email: Some(email.clone()),
+        })?;
+        if cli.json {
+            return print_json(&serde_json::json!({ "email": email }));
+        }
+        println!("Logged in as {email}.");
+        return Ok(());
+    }
+
+    let client = Client::new(server, session::load_session()?.token);
+    match cli.command {
+        Command::Login { .. } => unreachable!(),
+        Command::Add {
+            amount,
+            category,
+            date,
+            description,
+        } => {
+            let category = find_category(client.categories()?, &category)?;
+            let mut expense = Expense::new();
+            expense.amount(amount);
+            expense.category(category.id, category.name);
+            expense.date(parse_date_arg(&date)?.to_string());
+            expense.description(description);
+            let created = client.add_expense(&expense)?;
+            if cli.json {
+                return print_json(&created);
+            }
+            print_expense(&created);
+        }
+        Command::List { month, category } => {
+            let mut filter = ExpenseFilter::default();
+            if let Some(month) = month.as_deref().map(parse_month_arg).transpose()? {
+                let last = days_in_month(month.month(), month.year());
+                filter.from = Some(month.to_string());
+                filter.to = Some(month.replace_day(last).unwrap_or(month).to_string());
+            }
+            if let Some(name) = category {
+                filter.category_ids = vec![find_category(client.categories()?, &name)?.id];
+            }
+            let options = ListOptions {
+                descending: false,
+                ..ListOptions::default()
+            };
+            let expenses = client.expenses(ExpenseQuery::new(filter, options))?;
+            if cli.json {
+                return print_json(&expenses);
+            }
+            expenses.iter().for_each(print_expense);
+            let total: i64 = expenses
+                .iter()
+                .map(|expense| i64::from(expense.amount))
+                .sum();
+            println!("{} expenses, ${total} in total.", expenses.len());
+        }
+        Command::Delete { id } => {
+            client.delete_expense(id)?;
+            if cli.json {
+                return print_json(&serde_json::json!({ "deleted": id }));
+            }
+            println!("Deleted {id}.");
+        }
+        Command::Categories => {
+            let categories = client.categories()?;
+            if cli.json {
+                return print_json(&categories);
+            }
+            for category in categories {
+                println!("{}  {}", category.id, category.name);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::{fs, io, path::PathBuf};
+
+use core_api::{
+    backup::LocalData,
+    session::{Session, API_URL, APP_IDENTIFIER, DATA_FILE, SESSION_FILE},
+};
+
+use crate::error::CliError;
+
+/// The folder the desktop app keeps its data in, so both share one login.
+pub fn data_dir() -> Result<PathBuf, CliError> {
+    dirs::data_dir()
+        .map(|dir| dir.join(APP_IDENTIFIER))
+        .ok_or_else(|| CliError::Usage("could not find the data directory".to_owned()))
+}
+
+pub fn load_session() -> Result<Session, CliError> {
+    let session: Session = match fs::read(data_dir()?.join(SESSION_FILE)) {
+        Ok(bytes) => serde_json::from_slice(&bytes)?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Session::default(),
+        Err(e) => return Err(e.into()),
+    };
+    if session.token.is_empty() {
+        return Err(CliError::NotLoggedIn);
+    }
+    Ok(session)
+}
+
+pub fn save_session(session: &Session) -> Result<(), CliError> {
+    let dir = data_dir()?;
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(SESSION_FILE);
+    let temporary = path.with_extension("json.tmp");
+    fs::write(&temporary, serde_json::to_vec_pretty(session)?)?;
+    Ok(fs::rename(temporary, path)?)
+}
+
+/// The server configured in the desktop app's settings, or the default one.
+pub fn server_url() -> String {
+    data_dir()
+        .ok()
+        .and_then(|dir| fs::read(dir.join(DATA_FILE)).ok())
+        .and_then(|bytes| serde_json::from_slice::<LocalData>(&bytes).ok())
+        .and_then(|data| data.settings.server_url)
+        .unwrap_or(API_URL.to_owned())
+}
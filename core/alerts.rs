@@ -0,0 +1,277 @@
+use crate::models::{Expense, Settings};
+use serde::{Deserialize, Serialize};
+use time::{util::days_in_month, Date, Month};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Budget {
+    pub category_id: Uuid,
+    pub category_name: String,
+    pub monthly_limit: i32,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct RecurringBill {
+    pub id: Uuid,
+    pub name: String,
+    pub amount: i32,
+    pub day_of_month: u8,
+    pub category_id: Uuid,
+    pub category_name: String,
+}
+impl RecurringBill {
+    /// The next time the bill is due, on or after `today`. Days past the end of a short
+    /// month fall on its last day.
+    pub fn next_due(&self, today: Date) -> Date {
+        let due_in = |year: i32, month: Month| {
+            let day = self.day_of_month.clamp(1, days_in_month(month, year));
+            Date::from_calendar_date(year, month, day).unwrap()
+        };
+        let this_month = due_in(today.year(), today.month());
+        if this_month >= today {
+            return this_month;
+        }
+        let year = if today.month() == Month::December {
+            today.year() + 1
+        } else {
+            today.year()
+        };
+        due_in(year, today.month().next())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Trigger {
+    BudgetThreshold { category_id: Uuid, percent: u32 },
+    BillDue { bill_id: Uuid, days_before: u32 },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct NotificationRule {
+    pub id: Uuid,
+    pub enabled: bool,
+    pub trigger: Trigger,
+}
+impl NotificationRule {
+    pub fn new(trigger: Trigger) -> Self {
+        NotificationRule {
+            id: Uuid::new_v4(),
+            enabled: true,
+            trigger,
+        }
+    }
+}
+
+/// A notification that should be shown. `key` names this particular occurrence, so the
+/// same budget month or bill due date is only announced once.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Alert {
+    pub key: String,
+    pub title: String,
+    pub body: String,
+}
+
+pub fn month_key(date: Date) -> String {
+    format!("{:04}-{:02}", date.year(), u8::from(date.month()))
+}
+
+pub fn spent_in_month(expenses: &[Expense], category_id: Uuid, today: Date) -> i64 {
+    expenses
+        .iter()
+        .filter(|expense| expense.category_id == category_id)
+        .filter(|expense| {
+            expense
+                .parsed_date()
+                .is_some_and(|date| date.year() == today.year() && date.month() == today.month())
+        })
+        .map(|expense| i64::from(expense.amount))
+        .sum()
+}
+
+pub fn evaluate(settings: &Settings, expenses: &[Expense], today: Date) -> Vec<Alert> {
+    settings
+        .notification_rules
+        .iter()
+        .filter(|rule| rule.enabled)
+        .filter_map(|rule| match &rule.trigger {
+            Trigger::BudgetThreshold {
+                category_id,
+                percent,
+            } => {
+                let budget = settings
+                    .budgets
+                    .iter()
+                    .find(|budget| budget.category_id == *category_id)?;
+                let spent = spent_in_month(expenses, *category_id, today);
+                let limit = i64::from(budget.monthly_limit);
+                (limit > 0 && spent * 100 >= limit * i64::from(*percent)).then(|| Alert {
+                    key: format!("budget:{}:{}:{}", rule.id, percent, month_key(today)),
+                    title: format!(
+                        "{} budget at {}%",
+                        budget.category_name,
+                        spent * 100 / limit
+                    ),
+                    body: format!("Spent ${spent} of ${limit} this month."),
+                })
+            }
+            Trigger::BillDue {
+                bill_id,
+                days_before,
+            } => {
+                let bill = settings.bills.iter().find(|bill| bill.id == *bill_id)?;
+                let due = bill.next_due(today);
+                let days_left = (due - today).whole_days();
+                (days_left <= i64::from(*days_before)).then(|| Alert {
+                    key: format!("bill:{}:{}", rule.id, due),
+                    title: format!("{} is due", bill.name),
+                    body: match days_left {
+                        0 => format!("${} due today.", bill.amount),
+                        1 => format!("${} due tomorrow.", bill.amount),
+                        days => format!("${} due in {days} days, on {due}.", bill.amount),
+                    },
+                })
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    fn expense(category_id: Uuid, amount: i32, date: &str) -> Expense {
+        let mut expense = Expense::new();
+        expense.category(category_id, "Food".to_owned());
+        expense.amount(amount);
+        expense.date(date.to_owned());
+        expense
+    }
+
+    fn bill(day_of_month: u8) -> RecurringBill {
+        RecurringBill {
+            id: Uuid::from_u128(7),
+            name: "Rent".to_owned(),
+            amount: 900,
+            day_of_month,
+            ..RecurringBill::default()
+        }
+    }
+
+    fn budget_settings(percent: u32) -> (Settings, Uuid) {
+        let food = Uuid::from_u128(1);
+        let settings = Settings {
+            budgets: vec![Budget {
+                category_id: food,
+                category_name: "Food".to_owned(),
+                monthly_limit: 100,
+            }],
+            notification_rules: vec![NotificationRule::new(Trigger::BudgetThreshold {
+                category_id: food,
+                percent,
+            })],
+            ..Settings::default()
+        };
+        (settings, food)
+    }
+
+    fn bill_settings(day_of_month: u8, days_before: u32) -> Settings {
+        Settings {
+            bills: vec![bill(day_of_month)],
+            notification_rules: vec![NotificationRule::new(Trigger::BillDue {
+                bill_id: Uuid::from_u128(7),
+                days_before,
+            })],
+            ..Settings::default()
+        }
+    }
+
+    #[test]
+    fn budget_counts_only_the_current_month() {
+        let (settings, food) = budget_settings(80);
+        let expenses = vec![
+            expense(food, 70, "2026-10-31"),
+            expense(food, 50, "2026-11-01"),
+        ];
+        let october = evaluate(&settings, &expenses, date!(2026 - 10 - 31));
+        assert!(october.is_empty());
+
+        let more = [expenses.clone(), vec![expense(food, 10, "2026-10-15")]].concat();
+        let october = evaluate(&settings, &more, date!(2026 - 10 - 31));
+        assert_eq!(october.len(), 1);
+        assert_eq!(october[0].title, "Food budget at 80%");
+        assert_eq!(october[0].body, "Spent $80 of $100 this month.");
+
+        assert!(evaluate(&settings, &more, date!(2026 - 11 - 01)).is_empty());
+    }
+
+    #[test]
+    fn budget_alerts_are_keyed_by_month() {
+        let (settings, food) = budget_settings(50);
+        let expenses = vec![
+            expense(food, 60, "2026-12-05"),
+            expense(food, 60, "2027-01-05"),
+        ];
+        let december = evaluate(&settings, &expenses, date!(2026 - 12 - 31));
+        let january = evaluate(&settings, &expenses, date!(2027 - 01 - 01));
+        assert_eq!(december.len(), 1);
+        assert_eq!(january.len(), 1);
+        assert!(december[0].key.ends_with(":2026-12"));
+        assert!(january[0].key.ends_with(":2027-01"));
+    }
+
+    #[test]
+    fn disabled_rules_and_missing_budgets_stay_quiet() {
+        let (mut settings, food) = budget_settings(10);
+        let expenses = vec![expense(food, 90, "2026-10-10")];
+        settings.notification_rules[0].enabled = false;
+        assert!(evaluate(&settings, &expenses, date!(2026 - 10 - 20)).is_empty());
+        settings.notification_rules[0].enabled = true;
+        settings.budgets.clear();
+        assert!(evaluate(&settings, &expenses, date!(2026 - 10 - 20)).is_empty());
+    }
+
+    #[test]
+    fn bills_roll_into_the_next_month() {
+        assert_eq!(
+            bill(5).next_due(date!(2026 - 10 - 05)),
+            date!(2026 - 10 - 05)
+        );
+        assert_eq!(
+            bill(5).next_due(date!(2026 - 10 - 06)),
+            date!(2026 - 11 - 05)
+        );
+        assert_eq!(
+            bill(5).next_due(date!(2026 - 12 - 20)),
+            date!(2027 - 01 - 05)
+        );
+        assert_eq!(
+            bill(31).next_due(date!(2027 - 02 - 10)),
+            date!(2027 - 02 - 28)
+        );
+        assert_eq!(
+            bill(31).next_due(date!(2028 - 02 - 10)),
+            date!(2028 - 02 - 29)
+        );
+        assert_eq!(
+            bill(30).next_due(date!(2026 - 01 - 31)),
+            date!(2026 - 02 - 28)
+        );
+    }
+
+    #[test]
+    fn bill_reminders_cross_month_ends() {
+        let settings = bill_settings(2, 3);
+        let alerts = evaluate(&settings, &[], date!(2026 - 12 - 30));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].body, "$900 due in 3 days, on 2027-01-02.");
+        assert!(alerts[0].key.ends_with(":2027-01-02"));
+
+        assert!(evaluate(&settings, &[], date!(2026 - 12 - 29)).is_empty());
+        let tomorrow = evaluate(&settings, &[], date!(2027 - 01 - 01));
+        assert_eq!(tomorrow[0].body, "$900 due tomorrow.");
+        let today = evaluate(&settings, &[], date!(2027 - 01 - 02));
+        assert_eq!(today[0].body, "$900 due today.");
+    }
+}
@@ -0,0 +1,298 @@
+use crate::models::{Category, Expense, Settings};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+pub const BACKUP_VERSION: u32 = 1;
+
+/// Everything the app keeps on this device, minus the session token.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct LocalData {
+    pub expenses: Vec<Expense>,
+    pub categories: Vec<Category>,
+    pub settings: Settings,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Backup {
+    pub version: u32,
+    pub created_at: String,
+    pub data: LocalData,
+}
+impl Backup {
+    /// The server URL is left out: like the session, it belongs to the device.
+    pub fn new(created_at: String, mut data: LocalData) -> Self {
+        data.settings.server_url = None;
+        Backup {
+            version: BACKUP_VERSION,
+            created_at,
+            data,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreMode {
+    #[default]
+    Merge,
+    Replace,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct RestorePreview {
+    pub added: Vec<Expense>,
+    pub updated: Vec<Expense>,
+    pub removed: Vec<Expense>,
+    pub unchanged: usize,
+    pub categories_added: Vec<Category>,
+    pub categories_removed: Vec<Category>,
+    /// Whether restoring changes any setting; see `merge_settings` for what merging takes.
+    pub settings_changed: bool,
+}
+
+fn by_id<T, F: Fn(&T) -> Uuid>(items: &[T], id: F) -> HashMap<Uuid, &T> {
+    items.iter().map(|item| (id(item), item)).collect()
+}
+
+fn add_missing<T: Clone, K: PartialEq, F: Fn(&T) -> K>(
+    current: &mut Vec<T>,
+    incoming: &[T],
+    key: F,
+) {
+    for item in incoming {
+        if !current.iter().any(|existing| key(existing) == key(item)) {
+            current.push(item.clone());
+        }
+    }
+}
+
+/// Replacing takes every setting from the backup. Merging keeps the current choices
+/// (language, theme, limits) and only adds the rules, budgets, bills, notifications and
+/// category colors this device does not have yet. Either way the server URL stays.
+pub fn merge_settings(current: &Settings, incoming: &Settings, mode: RestoreMode) -> Settings {
+    let mut settings = match mode {
+        RestoreMode::Replace => incoming.clone(),
+        RestoreMode::Merge => {
+            let mut merged = current.clone();
+            add_missing(&mut merged.rules, &incoming.rules, |rule| rule.id);
+            add_missing(&mut merged.budgets, &incoming.budgets, |budget| {
+                budget.category_id
+            });
+            add_missing(&mut merged.bills, &incoming.bills, |bill| bill.id);
+            add_missing(
+                &mut merged.notification_rules,
+                &incoming.notification_rules,
+                |rule| rule.id,
+            );
+            for (category_id, color) in &incoming.category_colors {
+                merged
+                    .category_colors
+                    .entry(*category_id)
+                    .or_insert_with(|| color.clone());
+            }
+            merged
+        }
+    };
+    settings.server_url = current.server_url.clone();
+    settings
+}
+
+/// Describes what restoring `incoming` over `current` would change. Merging keeps every
+/// current expense and category that the backup does not mention; replacing makes the
+/// backup the only source.
+pub fn preview(current: &LocalData, incoming: &LocalData, mode: RestoreMode) -> RestorePreview {
+    let current_expenses = by_id(&current.expenses, |expense| expense.id);
+    let incoming_ids: HashSet<Uuid> = incoming.expenses.iter().map(|e| e.id).collect();
+    let mut preview = RestorePreview::default();
+    for expense in &incoming.expenses {
+        match current_expenses.get(&expense.id) {
+            None => preview.added.push(expense.clone()),
+            Some(existing) if *existing != expense => preview.updated.push(expense.clone()),
+            Some(_) => preview.unchanged += 1,
+        }
+    }
+
+    let current_categories = by_id(&current.categories, |category| category.id);
+    let incoming_categories = by_id(&incoming.categories, |category| category.id);
+    preview.categories_added = incoming
+        .categories
+        .iter()
+        .filter(|category| !current_categories.contains_key(&category.id))
+        .cloned()
+        .collect();
+
+    if mode == RestoreMode::Replace {
+        preview.removed = current
+            .expenses
+            .iter()
+            .filter(|expense| !incoming_ids.contains(&expense.id))
+            .cloned()
+            .collect();
+        preview.categories_removed = current
+            .categories
+            .iter()
+            .filter(|category| !incoming_categories.contains_key(&category.id))
+            .cloned()
+            .collect();
+    }
+    preview.settings_changed =
+        merge_settings(&current.settings, &incoming.settings, mode) != current.settings;
+    preview
+}
+
+pub fn apply(current: &LocalData, incoming: LocalData, mode: RestoreMode) -> LocalData {
+    let settings = merge_settings(&current.settings, &incoming.settings, mode);
+    let data = match mode {
+        RestoreMode::Replace => incoming,
+        RestoreMode::Merge => {
+            let mut merged = current.clone();
+            for expense in incoming.expenses {
+                match merged.expenses.iter_mut().find(|e| e.id == expense.id) {
+                    Some(existing) => *existing = expense,
+                    None => merged.expenses.push(expense),
+                }
+            }
+            for category in incoming.categories {
+                match merged.categories.iter_mut().find(|c| c.id == category.id) {
+                    Some(existing) => *existing = category,
+                    None => merged.categories.push(category),
+                }
+            }
+            merged
+        }
+    };
+    LocalData { settings, ..data }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{alerts::Budget, models::Theme, rules::Rule};
+
+    fn expense(id: u128, amount: i32) -> Expense {
+        let mut expense = Expense::new();
+        expense.id = Uuid::from_u128(id);
+        expense.amount(amount);
+        expense
+    }
+
+    fn category(id: u128, name: &str) -> Category {
+        let mut category = Category::new().name(name.to_owned());
+        category.id = Uuid::from_u128(id);
+        category
+    }
+
+    fn rule(id: u128) -> Rule {
+        Rule {
+            id: Uuid::from_u128(id),
+            ..Rule::default()
+        }
+    }
+
+    fn data() -> (LocalData, LocalData) {
+        let current = LocalData {
+            expenses: vec![expense(1, 10), expense(2, 20)],
+            categories: vec![category(10, "Food")],
+            settings: Settings {
+                theme: Theme::Dark,
+                rules: vec![rule(1)],
+                ..Settings::default()
+            },
+        };
+        let incoming = LocalData {
+            expenses: vec![expense(2, 25), expense(3, 30)],
+            categories: vec![category(10, "Food"), category(11, "Rent")],
+            settings: Settings {
+                theme: Theme::Light,
+                rules: vec![rule(1), rule(2)],
+                budgets: vec![Budget {
+                    category_id: Uuid::from_u128(11),
+                    category_name: "Rent".to_owned(),
+                    monthly_limit: 500,
+                }],
+                ..Settings::default()
+            },
+        };
+        (current, incoming)
+    }
+
+    #[test]
+    fn merge_preview_compares_by_id() {
+        let (current, incoming) = data();
+        let changes = preview(&current, &incoming, RestoreMode::Merge);
+        assert_eq!(changes.added, vec![expense(3, 30)]);
+        assert_eq!(changes.updated, vec![expense(2, 25)]);
+        assert!(changes.removed.is_empty());
+        assert_eq!(changes.unchanged, 0);
+        assert_eq!(changes.categories_added, vec![category(11, "Rent")]);
+        assert!(changes.categories_removed.is_empty());
+        assert!(changes.settings_changed);
+    }
+
+    #[test]
+    fn replace_preview_lists_removals() {
+        let (current, incoming) = data();
+        let changes = preview(&current, &incoming, RestoreMode::Replace);
+        assert_eq!(changes.removed, vec![expense(1, 10)]);
+        assert!(changes.settings_changed);
+    }
+
+    #[test]
+    fn restoring_twice_changes_nothing() {
+        let (current, incoming) = data();
+        for mode in [RestoreMode::Merge, RestoreMode::Replace] {
+            let restored = apply(&current, incoming.clone(), mode);
+            let again = preview(&restored, &incoming, mode);
+            assert!(again.added.is_empty() && again.updated.is_empty());
+            assert!(again.removed.is_empty() && again.categories_added.is_empty());
+            assert!(!again.settings_changed);
+        }
+    }
+
+    #[test]
+    fn merge_keeps_choices_and_adds_missing_items() {
+        let (current, incoming) = data();
+        let merged = merge_settings(&current.settings, &incoming.settings, RestoreMode::Merge);
+        assert_eq!(merged.theme, Theme::Dark);
+        assert_eq!(merged.rules, vec![rule(1), rule(2)]);
+        assert_eq!(merged.budgets, incoming.settings.budgets);
+
+        let replaced = merge_settings(&current.settings, &incoming.settings, RestoreMode::Replace);
+        assert_eq!(replaced, incoming.settings);
+    }
+
+    #[test]
+    fn server_url_stays_with_the_device() {
+        let (mut current, mut incoming) = data();
+        current.settings.server_url = Some("http://here".to_owned());
+        incoming.settings.server_url = Some("http://there".to_owned());
+        assert_eq!(
+            Backup::new(String::new(), incoming.clone())
+                .data
+                .settings
+                .server_url,
+            None
+        );
+        for mode in [RestoreMode::Merge, RestoreMode::Replace] {
+            let restored = merge_settings(&current.settings, &incoming.settings, mode);
+            assert_eq!(restored.server_url.as_deref(), Some("http://here"));
+        }
+    }
+
+    #[test]
+    fn merge_settings_alone_is_previewed() {
+        let (current, _) = data();
+        let incoming = LocalData {
+            settings: Settings {
+                rules: vec![rule(3)],
+                ..current.settings.clone()
+            },
+            ..current.clone()
+        };
+        let changes = preview(&current, &incoming, RestoreMode::Merge);
+        assert_eq!(changes.unchanged, 2);
+        assert!(changes.settings_changed);
+    }
+}
@@ -0,0 +1,214 @@
+use crate::reports::{CategoryTotal, MonthSummary};
+use leptos::*;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const PALETTE: [&str; 8] = [
+    "#6461a2", "#24c8db", "#d89b39", "#a82e20", "#3c9d5d", "#c15fa8", "#7a8b99", "#e0c341",
+];
+
+/// A stable color per category, so it matches across charts.
+pub fn category_color(category_id: Uuid) -> &'static str {
+    let sum = category_id
+        .as_bytes()
+        .iter()
+        .map(|b| usize::from(*b))
+        .sum::<usize>();
+    PALETTE[sum % PALETTE.len()]
+}
+
+/// The accent picked for a category, falling back to [`category_color`].
+fn accent(colors: &HashMap<Uuid, String>, category_id: Uuid) -> String {
+    colors
+        .get(&category_id)
+        .cloned()
+        .unwrap_or_else(|| category_color(category_id).to_owned())
+}
+
+/// The dash length and offset of each category with a share, in order. The first starts
+/// at the top of the ring, and each follows the one before it clockwise.
+fn donut_dashes(categories: &[CategoryTotal]) -> Vec<(f32, f32)> {
+    let mut offset = 25.0;
+    categories
+        .iter()
+        .filter(|category| category.share > 0.0)
+        .map(|category| {
+            let length = category.share * 100.0;
+            let dash = (length, offset);
+            offset -= length;
+            dash
+        })
+        .collect()
+}
+
+/// Each category's share of the month as a ring. A circle of radius 100/2π has a
+/// circumference of 100, so dash lengths are percentages.
+#[component]
+pub fn category_donut(
+    categories: Vec<CategoryTotal>,
+    #[prop(optional)] colors: HashMap<Uuid, String>,
+) -> impl IntoView {
+    let dashes = donut_dashes(&categories);
+    let segments = categories
+        .into_iter()
+        .filter(|category| category.share > 0.0)
+        .zip(dashes)
+        .map(|(category, (length, dash_offset))| {
+            view! {
+                <circle
+                    cx="21" cy="21" r="15.915"
+                    fill="none"
+                    stroke={accent(&colors, category.category_id)}
+                    stroke-width="6"
+                    stroke-dasharray={format!("{length} {}", 100.0 - length)}
+                    stroke-dashoffset={dash_offset.to_string()}
+                >
+                    <title>{format!("{}: {:.1}%", category.category_name, length)}</title>
+                </circle>
+            }
+        })
+        .collect_view();
+    view! {
+        <svg class="chart" viewBox="0 0 42 42" role="img">
+            <circle cx="21" cy="21" r="15.915" fill="none" stroke="currentColor" stroke-opacity="0.1" stroke-width="6"></circle>
+            {segments}
+        </svg>
+    }
+}
+
+/// One bar per month, stacked by category.
+#[component]
+pub fn monthly_bars(
+    months: Vec<MonthSummary>,
+    #[prop(optional)] colors: HashMap<Uuid, String>,
+) -> impl IntoView {
+    let max = months
+        .iter()
+        .map(|month| month.total)
+        .max()
+        .unwrap_or(0)
+        .max(1) as f32;
+    let width = 100.0 / months.len().max(1) as f32;
+    let bars = months
+        .into_iter()
+        .enumerate()
+        .map(|(i, month)| {
+            let x = i as f32 * width + width * 0.15;
+            let mut top = 52.0;
+            let segments = month
+                .categories
+                .into_iter()
+                .map(|category| {
+                    let height = category.total.max(0) as f32 / max * 48.0;
+                    top -= height;
+                    view! {
+                        <rect
+                            x={x.to_string()} y={top.to_string()}
+                            width={(width * 0.7).to_string()} height={height.to_string()}
+                            fill={accent(&colors, category.category_id)}
+                        >
+                            <title>{format!("{} {}: ${}", month.month, category.category_name, category.total)}</title>
+                        </rect>
+                    }
+                })
+                .collect_view();
+            view! {
+                {segments}
+                <text
+                    x={(x + width * 0.35).to_string()} y="58"
+                    font-size="3" text-anchor="middle" fill="currentColor"
+                >
+                    {month.month.get(2..).unwrap_or_default().to_owned()}
+                </text>
+            }
+        })
+        .collect_view();
+    view! {
+        <svg class="chart" viewBox="0 0 100 60" role="img">
+            {bars}
+        </svg>
+    }
+}
+
+/// Where each day's total goes in the line's 100 by 50 view box, and the height of the
+/// limit. The largest value reaches 4 from the top and zero sits on the baseline at 48.
+fn line_points(days: &[i64], limit: Option<i64>) -> (Vec<(f32, f32)>, Option<f32>) {
+    let top = days.iter().copied().chain(limit).max().unwrap_or(0).max(1) as f32;
+    let step = 100.0 / days.len().saturating_sub(1).max(1) as f32;
+    let y = move |value: i64| 48.0 - value.max(0) as f32 / top * 44.0;
+    let points = days
+        .iter()
+        .enumerate()
+        .map(|(day, total)| (day as f32 * step, y(*total)))
+        .collect();
+    (points, limit.map(y))
+}
+
+/// Running total through the month, from `reports::daily_cumulative`. An optional
+/// `limit` is drawn as a dashed line.
+#[component]
+pub fn cumulative_line(
+    days: Vec<i64>,
+    #[prop(default = None)] limit: Option<i64>,
+) -> impl IntoView {
+    let (points, limit_y) = line_points(&days, limit);
+    let points = points
+        .iter()
+        .map(|(x, y)| format!("{x},{y}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let limit_line = limit_y.map(|y| {
+        let y = y.to_string();
+        view! {
+            <line x1="0" x2="100" y1={y.clone()} y2={y} stroke="#a82e20" stroke-width="0.4" stroke-dasharray="2 1"></line>
+        }
+    });
+    view! {
+        <svg class="chart" viewBox="0 0 100 50" role="img">
+            <line x1="0" x2="100" y1="48" y2="48" stroke="currentColor" stroke-opacity="0.3" stroke-width="0.3"></line>
+            {limit_line}
+            <polyline points={points} fill="none" stroke="#6461a2" stroke-width="0.8" stroke-linejoin="round"></polyline>
+        </svg>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn share(share: f32) -> CategoryTotal {
+        CategoryTotal {
+            share,
+            ..CategoryTotal::default()
+        }
+    }
+
+    #[test]
+    fn colors_are_stable_unless_picked() {
+        let id = Uuid::new_v4();
+        assert_eq!(category_color(id), category_color(id));
+        assert!(PALETTE.contains(&category_color(id)));
+        let mut colors = HashMap::new();
+        assert_eq!(accent(&colors, id), category_color(id));
+        colors.insert(id, "#000000".to_owned());
+        assert_eq!(accent(&colors, id), "#000000");
+    }
+
+    #[test]
+    fn donut_slices_follow_each_other() {
+        let dashes = donut_dashes(&[share(0.5), share(0.0), share(0.25), share(0.25)]);
+        assert_eq!(dashes, [(50.0, 25.0), (25.0, -25.0), (25.0, -50.0)]);
+    }
+
+    #[test]
+    fn the_line_spans_the_chart() {
+        let (points, limit) = line_points(&[0, 50, 100], Some(200));
+        assert_eq!(points, [(0.0, 48.0), (50.0, 37.0), (100.0, 26.0)]);
+        assert_eq!(limit, Some(4.0));
+
+        let (points, limit) = line_points(&[-10, 40], None);
+        assert_eq!(points, [(0.0, 48.0), (100.0, 4.0)]);
+        assert_eq!(limit, None);
+        assert_eq!(line_points(&[], None), (vec![], None));
+    }
+}
@@ -17,8 +17,9 @@ pub mod error {
 
 pub mod models {
     use serde::{Deserialize, Serialize};
+    use time::{Date, Month};
     use uuid::Uuid;
-    #[derive(Clone, Serialize, Deserialize, Debug)]
+    #[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
     pub struct Expense {
         pub id: Uuid,
         pub category_id: Uuid,
@@ -26,18 +27,13 @@ pub mod models {
         pub description: Option<String>,
         pub amount: i32,
         pub date: Option<String>,
-    }
-    impl Default for Expense {
-        fn default() -> Expense {
-            Expense {
-                id: Uuid::default(),
-                category_id: Uuid::default(),
-                description: None,
-                amount: i32::default(),
-                category_name: String::default(),
-                date: None,
-            }
-        }
+        #[serde(default)]
+        pub external_id: Option<String>,
+        #[serde(default)]
+        pub account: Option<String>,
+        /// The household member who paid.
+        #[serde(default)]
+        pub paid_by: Option<Uuid>,
     }
     impl Expense {
         pub fn new() -> Self {
@@ -57,8 +53,29 @@ pub mod models {
         pub fn date(&mut self, date: String) {
             self.date = Some(date)
         }
+        pub fn external_id(&mut self, external_id: String) {
+            self.external_id = Some(external_id);
+        }
+        pub fn account(&mut self, account: Option<String>) {
+            self.account = account;
+        }
+        pub fn paid_by(&mut self, paid_by: Option<Uuid>) {
+            self.paid_by = paid_by;
+        }
+        /// The `YYYY-MM-DD` date as a calendar date, if it is set and valid.
+        pub fn parsed_date(&self) -> Option<Date> {
+            parse_date(self.date.as_deref()?)
+        }
     }
-    #[derive(Debug, Serialize, Deserialize, Clone, Default)]
+    /// Parses the `YYYY-MM-DD` dates the API uses; anything after the day is ignored.
+    pub fn parse_date(date: &str) -> Option<Date> {
+        let mut parts = date.splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = Month::try_from(parts.next()?.parse::<u8>().ok()?).ok()?;
+        let day = parts.next()?.get(..2)?.parse().ok()?;
+        Date::from_calendar_date(year, month, day).ok()
+    }
+    #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
     pub struct Category {
         pub id: Uuid,
         pub name: String,
@@ -66,13 +83,20 @@ pub mod models {
     }
     impl Category {
         pub fn new() -> Self {
-            let mut category = Category::default();
-            category.id = Uuid::new_v4();
-            category
+            Category {
+                id: Uuid::new_v4(),
+                ..Category::default()
+            }
         }
         pub fn name(self, name: String) -> Self {
             Category { name, ..self }
         }
+        pub fn description(self, description: Option<String>) -> Self {
+            Category {
+                description,
+                ..self
+            }
+        }
     }
     #[derive(Debug, Serialize, Deserialize, Clone, Default)]
     pub struct LoginBody {
@@ -90,14 +114,117 @@ pub mod models {
             self.password = password;
         }
     }
+    /// An expense deleted on this device, kept so it can be recovered.
+    #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+    pub struct TrashedExpense {
+        pub expense: Expense,
+        /// RFC 3339 timestamp.
+        pub deleted_at: String,
+    }
+    /// Body of `POST /expenses`; the server fills in the category name, and the id unless
+    /// one is given.
+    #[derive(Debug, Serialize, Deserialize, Clone, Default)]
+    pub struct NewExpense {
+        /// Kept when given, so a restored expense stays the same expense.
+        #[serde(default)]
+        pub id: Option<Uuid>,
+        pub category_id: Uuid,
+        pub date: String,
+        pub amount: i32,
+        #[serde(default)]
+        pub description: String,
+        #[serde(default)]
+        pub external_id: Option<String>,
+        #[serde(default)]
+        pub account: Option<String>,
+        /// Defaults to whoever adds the expense.
+        #[serde(default)]
+        pub paid_by: Option<Uuid>,
+    }
     #[derive(Debug, Serialize, Deserialize, Clone, Default)]
     pub struct SetTokenArgs {
         pub new_token: String,
+        pub email: Option<String>,
+    }
+    #[derive(Debug, Serialize, Deserialize, Clone, Default)]
+    pub struct PasswordResetRequest {
+        pub email: String,
+    }
+    /// Sets a new password with the code from a `PasswordResetRequest`.
+    #[derive(Debug, Serialize, Deserialize, Clone, Default)]
+    pub struct PasswordResetBody {
+        pub email: String,
+        pub code: String,
+        pub password: String,
     }
     #[derive(Debug, Serialize, Deserialize, Clone, Default)]
     pub struct LoginResponse {
         pub token: String,
     }
+    #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+    pub enum Language {
+        #[serde(rename = "en")]
+        English,
+        #[serde(rename = "es")]
+        Spanish,
+    }
+    #[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+    #[serde(rename_all = "snake_case")]
+    pub enum Theme {
+        #[default]
+        System,
+        Light,
+        Dark,
+    }
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+    #[serde(default)]
+    pub struct Settings {
+        pub server_url: Option<String>,
+        /// `None` follows the system language.
+        pub language: Option<Language>,
+        pub theme: Theme,
+        /// Accent colors picked for categories, as `#rrggbb`.
+        pub category_colors: std::collections::HashMap<Uuid, String>,
+        pub rules: Vec<crate::rules::Rule>,
+        pub duplicate_window_days: u32,
+        pub budgets: Vec<crate::alerts::Budget>,
+        pub bills: Vec<crate::alerts::RecurringBill>,
+        pub notification_rules: Vec<crate::alerts::NotificationRule>,
+        /// Minutes without input before the PIN lock comes back; `None` locks on launch only.
+        pub idle_lock_minutes: Option<u32>,
+    }
+    impl Default for Settings {
+        fn default() -> Self {
+            Settings {
+                server_url: None,
+                language: None,
+                theme: Theme::System,
+                category_colors: Default::default(),
+                rules: vec![],
+                duplicate_window_days: 3,
+                budgets: vec![],
+                bills: vec![],
+                notification_rules: vec![],
+                idle_lock_minutes: None,
+            }
+        }
+    }
+    impl Settings {
+        pub fn server_url(&mut self, server_url: Option<String>) {
+            self.server_url = server_url.filter(|url| !url.trim().is_empty());
+        }
+        /// The picked accent color, or the category's default one.
+        pub fn category_color(&self, category_id: Uuid) -> String {
+            self.category_colors
+                .get(&category_id)
+                .cloned()
+                .unwrap_or_else(|| crate::charts::category_color(category_id).to_owned())
+        }
+        pub fn duplicate_criteria(&self) -> crate::duplicates::DuplicateCriteria {
+            crate::duplicates::DuplicateCriteria::new()
+                .window_days(self.duplicate_window_days.into())
+        }
+    }
     #[derive(Serialize, Deserialize, Debug, Clone)]
     pub struct ApiResponse<T: Serialize + Clone> {
         data: T,
@@ -105,9 +232,74 @@ pub mod models {
         success: bool,
     }
     impl<T: Serialize + Clone> ApiResponse<T> {
+        pub fn new(data: T) -> Self {
+            ApiResponse {
+                data,
+                errors: None,
+                success: true,
+            }
+        }
+        pub fn failed(data: T, errors: Vec<String>) -> Self {
+            ApiResponse {
+                data,
+                errors: Some(errors),
+                success: false,
+            }
+        }
         pub fn data(self) -> T {
             self.data
         }
+        pub fn errors(&self) -> Vec<String> {
+            self.errors.clone().unwrap_or_default()
+        }
+    }
+}
+
+pub mod alerts;
+
+pub mod backup;
+
+pub mod charts;
+
+pub mod duplicates;
+
+pub mod filters;
+
+pub mod forecast;
+
+pub mod household;
+
+pub mod import;
+
+pub mod journal;
+
+pub mod lock;
+
+pub mod paging;
+
+pub mod passwords;
+
+pub mod reports;
+
+pub mod rules;
+
+pub mod sorting;
+
+pub mod session {
+    use serde::{Deserialize, Serialize};
+
+    /// Bundle identifier from `tauri.conf.json`; the desktop app keeps its data in a folder
+    /// with this name inside the platform data directory.
+    pub const APP_IDENTIFIER: &str = "arrebolit.finance-home";
+    pub const API_URL: &str = "https://arrebolit.com/apiFinance/api";
+    pub const DATA_FILE: &str = "local_data.json";
+    pub const SESSION_FILE: &str = "session.json";
+
+    #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+    #[serde(default)]
+    pub struct Session {
+        pub token: String,
+        pub email: Option<String>,
     }
 }
 
@@ -116,30 +308,40 @@ pub mod icons {
     #[component]
     pub fn trash_icon(class: Option<String>) -> impl IntoView {
         view! {
-           <svg  class={class} width="1em" height="1em" viewBox="0 0 24 24"><path fill="none" stroke="currentColor" strokeLinecap="round" strokeLinejoin="round" strokeWidth="2" d="M4 6h16l-1.58 14.22A2 2 0 0 1 16.432 22H7.568a2 2 0 0 1-1.988-1.78L4 6Zm3.345-2.853A2 2 0 0 1 9.154 2h5.692a2 2 0 0 1 1.81 1.147L18 6H6l1.345-2.853ZM2 6h20m-12 5v5m4-5v5"></path></svg>
+           <svg  class={class} width="1em" height="1em" viewBox="0 0 24 24"><path fill="none" stroke="currentColor" stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M4 6h16l-1.58 14.22A2 2 0 0 1 16.432 22H7.568a2 2 0 0 1-1.988-1.78L4 6Zm3.345-2.853A2 2 0 0 1 9.154 2h5.692a2 2 0 0 1 1.81 1.147L18 6H6l1.345-2.853ZM2 6h20m-12 5v5m4-5v5"></path></svg>
         }
     }
     #[component]
     pub fn refresh_icon(class: Option<String>) -> impl IntoView {
         view! {
-            <svg  class={class} width="1em" height="1em" viewBox="0 0 24 24"><g fill="none" stroke="currentColor" strokeLinecap="round" strokeLinejoin="round" strokeWidth="2"><path d="M4.266 16.06a8.923 8.923 0 0 0 3.915 3.978a8.706 8.706 0 0 0 5.471.832a8.796 8.796 0 0 0 4.887-2.64a9.067 9.067 0 0 0 2.388-5.079a9.137 9.137 0 0 0-1.044-5.53a8.904 8.904 0 0 0-4.068-3.815a8.7 8.7 0 0 0-5.5-.608c-1.85.401-3.367 1.313-4.62 2.755a7.62 7.62 0 0 0-1.22 1.781"></path><path d="m8.931 7.813l-5.04.907L3 3.59"></path></g></svg>
+            <svg  class={class} width="1em" height="1em" viewBox="0 0 24 24"><g fill="none" stroke="currentColor" stroke-linecap="round" stroke-linejoin="round" stroke-width="2"><path d="M4.266 16.06a8.923 8.923 0 0 0 3.915 3.978a8.706 8.706 0 0 0 5.471.832a8.796 8.796 0 0 0 4.887-2.64a9.067 9.067 0 0 0 2.388-5.079a9.137 9.137 0 0 0-1.044-5.53a8.904 8.904 0 0 0-4.068-3.815a8.7 8.7 0 0 0-5.5-.608c-1.85.401-3.367 1.313-4.62 2.755a7.62 7.62 0 0 0-1.22 1.781"></path><path d="m8.931 7.813l-5.04.907L3 3.59"></path></g></svg>
         }
     }
     #[component]
     pub fn bill_icon(class: Option<String>) -> impl IntoView {
         view! {
-           <svg class={class} width="1em" height="1em" viewBox="0 0 24 24"><g fill="none"><g fill="currentColor" clipPath="url(#akarIconsBitcoinFill0)"><path d="M11.385 15.275c1.111-.004 3.54-.013 3.512-1.558c-.027-1.58-2.36-1.485-3.497-1.438c-.127.005-.24.01-.332.011l.052 2.987c.075-.002.165-.002.265-.002Zm-.118-4.353c.927-.001 2.95-.003 2.926-1.408c-.026-1.437-1.969-1.352-2.918-1.31c-.107.005-.2.009-.278.01l.047 2.709l.223-.001Z"></path><path fillRule="evenodd" d="M9.096 23.641c6.43 1.603 12.942-2.31 14.545-8.738C25.244 8.474 21.33 1.962 14.9.36C8.474-1.244 1.962 2.67.36 9.1c-1.603 6.428 2.31 12.94 8.737 14.542Zm4.282-17.02c1.754.124 3.15.638 3.333 2.242c.136 1.174-.344 1.889-1.123 2.303c1.3.288 2.125 1.043 1.995 2.771c-.161 2.145-1.748 2.748-4.026 2.918l.038 2.25l-1.356.025l-.039-2.22c-.351.006-.711.01-1.084.008l.04 2.23l-1.356.024l-.04-2.254l-.383.003c-.194.001-.39.002-.586.006l-1.766.03l.241-1.624s1.004-.002.986-.017c.384-.008.481-.285.502-.459L8.693 11.3l.097-.002h.046a1.101 1.101 0 0 0-.144-.007l-.044-2.54c-.057-.274-.241-.59-.79-.58c.015-.02-.986.017-.986.017L6.846 6.74l1.872-.032v.007c.281-.005.57-.015.863-.026L9.543 4.46l1.356-.023l.038 2.184c.362-.013.726-.027 1.083-.033l-.038-2.17l1.357-.024l.039 2.229Z" clipRule="evenodd"></path></g><defs><clipPath id="akarIconsBitcoinFill0"><path fill="#fff" d="M0 0h24v24H0z"></path></clipPath></defs></g></svg>
+           <svg class={class} width="1em" height="1em" viewBox="0 0 24 24"><g fill="none"><g fill="currentColor" clip-path="url(#akarIconsBitcoinFill0)"><path d="M11.385 15.275c1.111-.004 3.54-.013 3.512-1.558c-.027-1.58-2.36-1.485-3.497-1.438c-.127.005-.24.01-.332.011l.052 2.987c.075-.002.165-.002.265-.002Zm-.118-4.353c.927-.001 2.95-.003 2.926-1.408c-.026-1.437-1.969-1.352-2.918-1.31c-.107.005-.2.009-.278.01l.047 2.709l.223-.001Z"></path><path fill-rule="evenodd" d="M9.096 23.641c6.43 1.603 12.942-2.31 14.545-8.738C25.244 8.474 21.33 1.962 14.9.36C8.474-1.244 1.962 2.67.36 9.1c-1.603 6.428 2.31 12.94 8.737 14.542Zm4.282-17.02c1.754.124 3.15.638 3.333 2.242c.136 1.174-.344 1.889-1.123 2.303c1.3.288 2.125 1.043 1.995 2.771c-.161 2.145-1.748 2.748-4.026 2.918l.038 2.25l-1.356.025l-.039-2.22c-.351.006-.711.01-1.084.008l.04 2.23l-1.356.024l-.04-2.254l-.383.003c-.194.001-.39.002-.586.006l-1.766.03l.241-1.624s1.004-.002.986-.017c.384-.008.481-.285.502-.459L8.693 11.3l.097-.002h.046a1.101 1.101 0 0 0-.144-.007l-.044-2.54c-.057-.274-.241-.59-.79-.58c.015-.02-.986.017-.986.017L6.846 6.74l1.872-.032v.007c.281-.005.57-.015.863-.026L9.543 4.46l1.356-.023l.038 2.184c.362-.013.726-.027 1.083-.033l-.038-2.17l1.357-.024l.039 2.229Z" clip-rule="evenodd"></path></g><defs><clipPath id="akarIconsBitcoinFill0"><path fill="#fff" d="M0 0h24v24H0z"></path></clipPath></defs></g></svg>
         }
     }
     #[component]
     pub fn list_icon(class: Option<String>) -> impl IntoView {
         view! {
-           <svg class={class} width="1em" height="1em" viewBox="0 0 24 24"><path fill="none" stroke="currentColor" strokeLinecap="round" strokeWidth="2" d="M3 6h18M3 12h18M3 18h18"></path></svg>
+           <svg class={class} width="1em" height="1em" viewBox="0 0 24 24"><path fill="none" stroke="currentColor" stroke-linecap="round" stroke-width="2" d="M3 6h18M3 12h18M3 18h18"></path></svg>
         }
     }
     #[component]
     pub fn user_icon(class: Option<String>) -> impl IntoView {
         view! {
-        <svg class={class} width="1em" height="1em" viewBox="0 0 24 24"><g fill="none" stroke="currentColor" strokeWidth="2"><circle cx="12" cy="7" r="5"></circle><path strokeLinecap="round" strokeLinejoin="round" d="M17 14h.352a3 3 0 0 1 2.976 2.628l.391 3.124A2 2 0 0 1 18.734 22H5.266a2 2 0 0 1-1.985-2.248l.39-3.124A3 3 0 0 1 6.649 14H7"></path></g></svg>        }
+        <svg class={class} width="1em" height="1em" viewBox="0 0 24 24"><g fill="none" stroke="currentColor" stroke-width="2"><circle cx="12" cy="7" r="5"></circle><path stroke-linecap="round" stroke-linejoin="round" d="M17 14h.352a3 3 0 0 1 2.976 2.628l.391 3.124A2 2 0 0 1 18.734 22H5.266a2 2 0 0 1-1.985-2.248l.39-3.124A3 3 0 0 1 6.649 14H7"></path></g></svg>        }
+    }
+    #[component]
+    pub fn settings_icon(class: Option<String>) -> impl IntoView {
+        view! {
+        <svg class={class} width="1em" height="1em" viewBox="0 0 24 24"><g fill="none" stroke="currentColor" stroke-width="2"><circle cx="12" cy="12" r="3"></circle><path d="M19.4 15a1.65 1.65 0 0 0 .33 1.82l.06.06a2 2 0 1 1-2.83 2.83l-.06-.06a1.65 1.65 0 0 0-1.82-.33a1.65 1.65 0 0 0-1 1.51V21a2 2 0 1 1-4 0v-.09A1.65 1.65 0 0 0 9 19.4a1.65 1.65 0 0 0-1.82.33l-.06.06a2 2 0 1 1-2.83-2.83l.06-.06A1.65 1.65 0 0 0 4.68 15a1.65 1.65 0 0 0-1.51-1H3a2 2 0 1 1 0-4h.09A1.65 1.65 0 0 0 4.6 9a1.65 1.65 0 0 0-.33-1.82l-.06-.06a2 2 0 1 1 2.83-2.83l.06.06A1.65 1.65 0 0 0 9 4.68a1.65 1.65 0 0 0 1-1.51V3a2 2 0 1 1 4 0v.09a1.65 1.65 0 0 0 1 1.51a1.65 1.65 0 0 0 1.82-.33l.06-.06a2 2 0 1 1 2.83 2.83l-.06.06A1.65 1.65 0 0 0 19.4 9a1.65 1.65 0 0 0 1.51 1H21a2 2 0 1 1 0 4h-.09a1.65 1.65 0 0 0-1.51 1Z"></path></g></svg>        }
+    }
+    #[component]
+    pub fn chart_icon(class: Option<String>) -> impl IntoView {
+        view! {
+        <svg class={class} width="1em" height="1em" viewBox="0 0 24 24"><path fill="none" stroke="currentColor" stroke-linecap="round" stroke-width="2" d="M3 21h18M6 17v-6m5 6V5m5 12v-9m4 9v-4"></path></svg>        }
     }
 }
@@ -0,0 +1,254 @@
+use crate::{filters::ExpenseFilter, models::Expense};
+use std::collections::HashSet;
+use time::{Date, Duration};
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DuplicateCriteria {
+    pub window_days: i64,
+    pub min_similarity: f32,
+}
+impl Default for DuplicateCriteria {
+    fn default() -> Self {
+        DuplicateCriteria {
+            window_days: 3,
+            min_similarity: 0.5,
+        }
+    }
+}
+impl DuplicateCriteria {
+    pub fn new() -> Self {
+        DuplicateCriteria::default()
+    }
+    pub fn window_days(self, window_days: i64) -> Self {
+        DuplicateCriteria {
+            window_days,
+            ..self
+        }
+    }
+}
+
+pub(crate) fn fold(c: char) -> char {
+    match c {
+        'á' | 'à' | 'ä' | 'â' | 'ã' => 'a',
+        'é' | 'è' | 'ë' | 'ê' => 'e',
+        'í' | 'ì' | 'ï' | 'î' => 'i',
+        'ó' | 'ò' | 'ö' | 'ô' | 'õ' => 'o',
+        'ú' | 'ù' | 'ü' | 'û' => 'u',
+        'ñ' => 'n',
+        'ç' => 'c',
+        c => c,
+    }
+}
+
+fn tokens(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_lowercase().chars().map(fold).collect())
+        .collect()
+}
+
+/// Share of words the two descriptions have in common, from 0 to 1. Two empty
+/// descriptions count as identical.
+pub fn similarity(a: &str, b: &str) -> f32 {
+    let (a, b) = (tokens(a), tokens(b));
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    a.intersection(&b).count() as f32 / a.union(&b).count() as f32
+}
+
+fn within_window(a: &Expense, b: &Expense, window_days: i64) -> bool {
+    match (a.parsed_date(), b.parsed_date()) {
+        (Some(a), Some(b)) => (a - b).whole_days().abs() <= window_days,
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+pub fn is_duplicate(a: &Expense, b: &Expense, criteria: DuplicateCriteria) -> bool {
+    a.amount == b.amount
+        && within_window(a, b, criteria.window_days)
+        && similarity(
+            a.description.as_deref().unwrap_or_default(),
+            b.description.as_deref().unwrap_or_default(),
+        ) >= criteria.min_similarity
+}
+
+/// Existing expenses that look like the same purchase as `candidate`.
+pub fn find_duplicates<'a>(
+    candidate: &Expense,
+    existing: &'a [Expense],
+    criteria: DuplicateCriteria,
+) -> Vec<&'a Expense> {
+    existing
+        .iter()
+        .filter(|expense| candidate.id == Uuid::default() || expense.id != candidate.id)
+        .filter(|expense| is_duplicate(candidate, expense, criteria))
+        .collect()
+}
+
+/// Narrows a search to the expenses that could be duplicates of `candidate`: the same
+/// amount, within the window. [`find_duplicates`] still has the final say.
+pub fn candidates_filter(candidate: &Expense, criteria: DuplicateCriteria) -> ExpenseFilter {
+    let day = |date: Date| {
+        format!(
+            "{:04}-{:02}-{:02}",
+            date.year(),
+            u8::from(date.month()),
+            date.day()
+        )
+    };
+    let window = Duration::days(criteria.window_days);
+    let date = candidate.parsed_date();
+    ExpenseFilter {
+        from: date.and_then(|date| date.checked_sub(window)).map(day),
+        to: date.and_then(|date| date.checked_add(window)).map(day),
+        min_amount: Some(candidate.amount),
+        max_amount: Some(candidate.amount),
+        ..ExpenseFilter::default()
+    }
+}
+
+/// Groups expenses around the earliest one not yet grouped: every other member is a
+/// duplicate of that first expense, so a purchase repeated every day does not chain into
+/// one long group. Only groups with more than one expense are returned, each sorted by
+/// date.
+pub fn duplicate_groups(expenses: &[Expense], criteria: DuplicateCriteria) -> Vec<Vec<Expense>> {
+    let mut by_date: Vec<&Expense> = expenses.iter().collect();
+    by_date.sort_by(|a, b| a.date.cmp(&b.date));
+    let mut grouped = vec![false; by_date.len()];
+
+    let mut groups: Vec<Vec<Expense>> = vec![];
+    for (i, anchor) in by_date.iter().enumerate() {
+        if grouped[i] {
+            continue;
+        }
+        let mut group = vec![(*anchor).clone()];
+        for (j, other) in by_date.iter().enumerate().skip(i + 1) {
+            if !grouped[j] && is_duplicate(anchor, other, criteria) {
+                grouped[j] = true;
+                group.push((*other).clone());
+            }
+        }
+        if group.len() > 1 {
+            groups.push(group);
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expense(amount: i32, date: &str, description: &str) -> Expense {
+        let mut expense = Expense::new();
+        expense.amount(amount);
+        expense.date(date.to_owned());
+        expense.description(Some(description.to_owned()));
+        expense
+    }
+
+    #[test]
+    fn the_filter_keeps_every_duplicate() {
+        let candidate = expense(1250, "2026-03-01", "Coffee shop");
+        let existing = vec![
+            expense(1250, "2026-02-27", "coffee"),
+            expense(1250, "2026-03-04", "Coffee Shop downtown"),
+            expense(1250, "2026-03-05", "Coffee shop"),
+            expense(1200, "2026-03-01", "Coffee shop"),
+        ];
+        let criteria = DuplicateCriteria::default();
+        let filter = candidates_filter(&candidate, criteria);
+        assert_eq!(filter.from.as_deref(), Some("2026-02-26"));
+        assert_eq!(filter.to.as_deref(), Some("2026-03-04"));
+
+        let narrowed = filter.apply(&existing, None);
+        assert_eq!(narrowed.len(), 2);
+        assert_eq!(
+            find_duplicates(&candidate, &narrowed, criteria),
+            find_duplicates(&candidate, &existing, criteria)
+        );
+    }
+
+    #[test]
+    fn descriptions_are_compared_by_words() {
+        assert_eq!(similarity("Café Central", "cafe central"), 1.0);
+        assert_eq!(similarity("", ""), 1.0);
+        assert_eq!(similarity("coffee", ""), 0.0);
+        assert_eq!(similarity("Coffee shop", "coffee, bakery"), 1.0 / 3.0);
+    }
+
+    #[test]
+    fn the_window_includes_its_last_day() {
+        let criteria = DuplicateCriteria::default();
+        let first = expense(1250, "2026-03-01", "Coffee");
+        assert!(is_duplicate(
+            &first,
+            &expense(1250, "2026-03-04", "Coffee"),
+            criteria
+        ));
+        assert!(is_duplicate(
+            &expense(1250, "2026-02-26", "Coffee"),
+            &first,
+            criteria
+        ));
+        assert!(!is_duplicate(
+            &first,
+            &expense(1250, "2026-03-05", "Coffee"),
+            criteria
+        ));
+        assert!(!is_duplicate(
+            &first,
+            &expense(1251, "2026-03-01", "Coffee"),
+            criteria
+        ));
+        assert!(!is_duplicate(
+            &first,
+            &expense(1250, "2026-03-01", "Rent"),
+            criteria
+        ));
+    }
+
+    #[test]
+    fn undated_expenses_only_match_each_other() {
+        let criteria = DuplicateCriteria::default();
+        let mut undated = expense(900, "", "Taxi");
+        undated.date = None;
+        let other_undated = undated.clone();
+        assert!(is_duplicate(&undated, &other_undated, criteria));
+        assert!(!is_duplicate(
+            &undated,
+            &expense(900, "2026-03-01", "Taxi"),
+            criteria
+        ));
+    }
+
+    #[test]
+    fn a_daily_purchase_does_not_chain_into_one_group() {
+        let coffees: Vec<Expense> = (1..=10)
+            .map(|day| expense(350, &format!("2026-03-{day:02}"), "Morning coffee"))
+            .collect();
+        let groups = duplicate_groups(&coffees, DuplicateCriteria::default());
+        let days = |group: &Vec<Expense>| {
+            group
+                .iter()
+                .map(|expense| expense.date.clone().unwrap_or_default())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(groups.len(), 3);
+        assert_eq!(
+            days(&groups[0]),
+            ["2026-03-01", "2026-03-02", "2026-03-03", "2026-03-04"]
+        );
+        assert_eq!(days(&groups[2]), ["2026-03-09", "2026-03-10"]);
+        for group in &groups {
+            assert!(group[1..].iter().all(|expense| is_duplicate(
+                &group[0],
+                expense,
+                DuplicateCriteria::default()
+            )));
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{duplicates::fold, household::Household, models::Expense};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+fn normalize(text: &str) -> String {
+    text.to_lowercase().chars().map(fold).collect()
+}
+
+/// What the All Expenses list is narrowed to. Empty fields do not filter.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ExpenseFilter {
+    /// `YYYY-MM-DD`, inclusive.
+    pub from: Option<String>,
+    /// `YYYY-MM-DD`, inclusive.
+    pub to: Option<String>,
+    pub category_ids: Vec<Uuid>,
+    pub min_amount: Option<i32>,
+    pub max_amount: Option<i32>,
+    /// Matched against the description, ignoring case and accents.
+    pub text: String,
+    /// The household member who paid.
+    pub paid_by: Option<Uuid>,
+}
+/// One active part of an [`ExpenseFilter`], for display.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ActiveFilter {
+    /// At least one bound is set.
+    Dates {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    Categories(Vec<Uuid>),
+    /// At least one bound is set.
+    Amounts {
+        min: Option<i32>,
+        max: Option<i32>,
+    },
+    Text(String),
+    PaidBy(Uuid),
+}
+
+impl ExpenseFilter {
+    pub fn is_active(&self) -> bool {
+        *self != ExpenseFilter::default()
+    }
+
+    pub fn toggle_category(&mut self, category_id: Uuid) {
+        match self.category_ids.iter().position(|id| *id == category_id) {
+            Some(at) => {
+                self.category_ids.remove(at);
+            }
+            None => self.category_ids.push(category_id),
+        }
+    }
+
+    /// Expenses without a payer count as paid by the `household`'s owner, as in reports.
+    pub fn matches(&self, expense: &Expense, household: Option<&Household>) -> bool {
+        // Dates are `YYYY-MM-DD`, so they compare correctly as text.
+        let date = expense.date.as_deref().unwrap_or_default();
+        self.from.as_deref().is_none_or(|from| date >= from)
+            && self
+                .to
+                .as_deref()
+                .is_none_or(|to| !date.is_empty() && date <= to)
+            && (self.category_ids.is_empty() || self.category_ids.contains(&expense.category_id))
+            && self.min_amount.is_none_or(|min| expense.amount >= min)
+            && self.max_amount.is_none_or(|max| expense.amount <= max)
+            && (self.text.trim().is_empty()
+                || normalize(expense.description.as_deref().unwrap_or_default())
+                    .contains(&normalize(self.text.trim())))
+            && self.paid_by.is_none_or(|member| {
+                let payer = match household {
+                    Some(household) => Some(household.payer(expense.paid_by)),
+                    None => expense.paid_by,
+                };
+                payer == Some(member)
+            })
+    }
+
+    pub fn apply(&self, expenses: &[Expense], household: Option<&Household>) -> Vec<Expense> {
+        expenses
+            .iter()
+            .filter(|expense| self.matches(expense, household))
+            .cloned()
+            .collect()
+    }
+
+    /// The active filters, in the order they are shown. The caller words them, so they
+    /// can be translated.
+    pub fn active(&self) -> Vec<ActiveFilter> {
+        let mut active = vec![];
+        if self.from.is_some() || self.to.is_some() {
+            active.push(ActiveFilter::Dates {
+                from: self.from.clone(),
+                to: self.to.clone(),
+            });
+        }
+        if !self.category_ids.is_empty() {
+            active.push(ActiveFilter::Categories(self.category_ids.clone()));
+        }
+        if self.min_amount.is_some() || self.max_amount.is_some() {
+            active.push(ActiveFilter::Amounts {
+                min: self.min_amount,
+                max: self.max_amount,
+            });
+        }
+        if !self.text.trim().is_empty() {
+            active.push(ActiveFilter::Text(self.text.trim().to_owned()));
+        }
+        if let Some(member) = self.paid_by {
+            active.push(ActiveFilter::PaidBy(member));
+        }
+        active
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::household::Member;
+
+    fn expense(amount: i32, date: Option<&str>, paid_by: Option<Uuid>) -> Expense {
+        let mut expense = Expense::new();
+        expense.amount(amount);
+        expense.date = date.map(str::to_owned);
+        expense.paid_by = paid_by;
+        expense
+    }
+
+    #[test]
+    fn date_bounds_are_inclusive() {
+        let filter = ExpenseFilter {
+            from: Some("2026-03-01".to_owned()),
+            to: Some("2026-03-31".to_owned()),
+            ..ExpenseFilter::default()
+        };
+        assert!(filter.matches(&expense(1, Some("2026-03-01"), None), None));
+        assert!(filter.matches(&expense(1, Some("2026-03-31"), None), None));
+        assert!(!filter.matches(&expense(1, Some("2026-02-28"), None), None));
+        assert!(!filter.matches(&expense(1, Some("2026-04-01"), None), None));
+        // Undated expenses are never inside a date range.
+        assert!(!filter.matches(&expense(1, None, None), None));
+        let until = ExpenseFilter {
+            to: Some("2026-03-31".to_owned()),
+            ..ExpenseFilter::default()
+        };
+        assert!(!until.matches(&expense(1, None, None), None));
+    }
+
+    #[test]
+    fn amount_bounds_are_inclusive() {
+        let filter = ExpenseFilter {
+            min_amount: Some(100),
+            max_amount: Some(200),
+            ..ExpenseFilter::default()
+        };
+        let amounts: Vec<i32> = filter
+            .apply(
+                &[99, 100, 150, 200, 201].map(|amount| expense(amount, None, None)),
+                None,
+            )
+            .iter()
+            .map(|expense| expense.amount)
+            .collect();
+        assert_eq!(amounts, [100, 150, 200]);
+    }
+
+    #[test]
+    fn text_ignores_case_and_accents() {
+        let mut cafe = expense(1, None, None);
+        cafe.description = Some("Café CENTRAL".to_owned());
+        let filter = |text: &str| ExpenseFilter {
+            text: text.to_owned(),
+            ..ExpenseFilter::default()
+        };
+        assert!(filter("cafe central").matches(&cafe, None));
+        assert!(filter("  central ").matches(&cafe, None));
+        assert!(!filter("bakery").matches(&cafe, None));
+        assert!(!filter("cafe").matches(&expense(1, None, None), None));
+    }
+
+    #[test]
+    fn any_selected_category_matches() {
+        let (food, rent) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut filter = ExpenseFilter::default();
+        filter.toggle_category(food);
+        filter.toggle_category(rent);
+        let mut lunch = expense(1, None, None);
+        lunch.category_id = food;
+        assert!(filter.matches(&lunch, None));
+        filter.toggle_category(food);
+        assert!(!filter.matches(&lunch, None));
+        filter.toggle_category(rent);
+        assert!(!filter.is_active());
+        assert!(filter.matches(&lunch, None));
+    }
+
+    #[test]
+    fn unattributed_expenses_are_the_owners() {
+        let (owner, member) = (Uuid::new_v4(), Uuid::new_v4());
+        let household = Household {
+            owner_id: owner,
+            members: vec![
+                Member {
+                    id: owner,
+                    ..Member::default()
+                },
+                Member {
+                    id: member,
+                    ..Member::default()
+                },
+            ],
+            ..Household::default()
+        };
+        let expenses = [
+            expense(1, None, None),
+            expense(2, None, Some(owner)),
+            expense(3, None, Some(member)),
+        ];
+        let paid_by = |id| ExpenseFilter {
+            paid_by: Some(id),
+            ..ExpenseFilter::default()
+        };
+        let amounts = |filter: ExpenseFilter, household| -> Vec<i32> {
+            filter
+                .apply(&expenses, household)
+                .iter()
+                .map(|expense| expense.amount)
+                .collect()
+        };
+        assert_eq!(amounts(paid_by(owner), Some(&household)), [1, 2]);
+        assert_eq!(amounts(paid_by(member), Some(&household)), [3]);
+        assert_eq!(amounts(paid_by(owner), None), [2]);
+    }
+
+    #[test]
+    fn active_filters_are_listed_in_order() {
+        let member = Uuid::new_v4();
+        let filter = ExpenseFilter {
+            to: Some("2026-03-31".to_owned()),
+            min_amount: Some(100),
+            text: "  taxi ".to_owned(),
+            paid_by: Some(member),
+            ..ExpenseFilter::default()
+        };
+        assert_eq!(
+            filter.active(),
+            [
+                ActiveFilter::Dates {
+                    from: None,
+                    to: Some("2026-03-31".to_owned())
+                },
+                ActiveFilter::Amounts {
+                    min: Some(100),
+                    max: None
+                },
+                ActiveFilter::Text("taxi".to_owned()),
+                ActiveFilter::PaidBy(member),
+            ]
+        );
+        assert!(ExpenseFilter::default().active().is_empty());
+    }
+}
@@ -0,0 +1,363 @@
+use crate::{
+    alerts::month_key,
+    models::{Expense, Settings},
+};
+use serde::{Deserialize, Serialize};
+use time::{util::days_in_month, Date, Month};
+use uuid::Uuid;
+
+/// Past months used to learn how much is usually spent in the rest of a month.
+const HISTORY_MONTHS: usize = 6;
+/// The band covers roughly 80% of outcomes if the history is normally distributed.
+const BAND_WIDTH: f64 = 1.28;
+
+/// Ordered from best to worst.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetStatus {
+    NoBudget,
+    OnTrack,
+    /// Projected under the budget, but the top of the band is over it.
+    AtRisk,
+    Over,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct CategoryForecast {
+    pub category_id: Uuid,
+    pub category_name: String,
+    pub spent: i64,
+    /// Recurring bills still due this month.
+    pub upcoming_bills: i64,
+    pub projected: i64,
+    pub low: i64,
+    pub high: i64,
+    pub budget: Option<i64>,
+}
+impl CategoryForecast {
+    pub fn status(&self) -> BudgetStatus {
+        budget_status(self.budget, self.projected, self.high)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Forecast {
+    /// `YYYY-MM`.
+    pub month: String,
+    pub day: u8,
+    pub days_in_month: u8,
+    pub spent: i64,
+    pub projected: i64,
+    pub low: i64,
+    pub high: i64,
+    /// Sum of the budgets, if any are set.
+    pub budget: Option<i64>,
+    /// Largest projection first.
+    pub categories: Vec<CategoryForecast>,
+}
+impl Forecast {
+    /// The worst status among the budgeted categories.
+    pub fn status(&self) -> BudgetStatus {
+        self.categories
+            .iter()
+            .map(CategoryForecast::status)
+            .max()
+            .unwrap_or(BudgetStatus::NoBudget)
+    }
+}
+
+fn budget_status(budget: Option<i64>, projected: i64, high: i64) -> BudgetStatus {
+    match budget {
+        None => BudgetStatus::NoBudget,
+        Some(budget) if projected > budget => BudgetStatus::Over,
+        Some(budget) if high > budget => BudgetStatus::AtRisk,
+        Some(_) => BudgetStatus::OnTrack,
+    }
+}
+
+fn previous_month(year: i32, month: Month) -> (i32, Month) {
+    match month {
+        Month::January => (year - 1, Month::December),
+        month => (year, month.previous()),
+    }
+}
+
+/// Spending in a category after `day` of the given month.
+fn rest_of_month(expenses: &[Expense], category_id: Uuid, year: i32, month: Month, day: u8) -> i64 {
+    expenses
+        .iter()
+        .filter(|expense| expense.category_id == category_id)
+        .filter(|expense| {
+            expense.parsed_date().is_some_and(|date| {
+                date.year() == year && date.month() == month && date.day() > day
+            })
+        })
+        .map(|expense| i64::from(expense.amount))
+        .sum()
+}
+
+fn mean_and_deviation(samples: &[f64]) -> (f64, f64) {
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    let variance = samples
+        .iter()
+        .map(|sample| (sample - mean).powi(2))
+        .sum::<f64>()
+        / samples.len() as f64;
+    (mean, variance.sqrt())
+}
+
+fn entry(categories: &mut Vec<CategoryForecast>, category_id: Uuid, name: &str) -> usize {
+    match categories
+        .iter()
+        .position(|category| category.category_id == category_id)
+    {
+        Some(at) => at,
+        None => {
+            categories.push(CategoryForecast {
+                category_id,
+                category_name: name.to_owned(),
+                ..CategoryForecast::default()
+            });
+            categories.len() - 1
+        }
+    }
+}
+
+/// Projects where the month of `today` will end, per category and overall.
+///
+/// Each category's projection is what was spent so far, plus the recurring bills still
+/// due, plus what was usually spent in the same remaining days of previous months, not
+/// counting the bills paid then. With no history the current daily pace is extended.
+pub fn forecast(settings: &Settings, expenses: &[Expense], today: Date) -> Forecast {
+    let (year, month, day) = (today.year(), today.month(), today.day());
+    let month_days = days_in_month(month, year);
+    let remaining = month_days - day;
+
+    let mut history = vec![];
+    let (mut past_year, mut past_month) = (year, month);
+    for _ in 0..HISTORY_MONTHS {
+        (past_year, past_month) = previous_month(past_year, past_month);
+        let key = month_key(Date::from_calendar_date(past_year, past_month, 1).unwrap());
+        if expenses
+            .iter()
+            .any(|expense| expense.parsed_date().map(month_key).as_ref() == Some(&key))
+        {
+            history.push((past_year, past_month));
+        }
+    }
+
+    let mut categories: Vec<CategoryForecast> = vec![];
+    let this_month = month_key(today);
+    let mut spent_this_month = vec![];
+    for expense in expenses {
+        let Some(date) = expense.parsed_date() else {
+            continue;
+        };
+        let recent = history
+            .iter()
+            .any(|(year, month)| date.year() == *year && date.month() == *month);
+        if month_key(date) == this_month && date <= today {
+            spent_this_month.push((
+                entry(&mut categories, expense.category_id, &expense.category_name),
+                expense,
+            ));
+        } else if recent {
+            entry(&mut categories, expense.category_id, &expense.category_name);
+        }
+    }
+    for budget in &settings.budgets {
+        let at = entry(&mut categories, budget.category_id, &budget.category_name);
+        categories[at].budget = Some(i64::from(budget.monthly_limit));
+    }
+    let bills_after = |category_id: Uuid, year: i32, month: Month| -> i64 {
+        let month_days = days_in_month(month, year);
+        settings
+            .bills
+            .iter()
+            .filter(|bill| bill.category_id == category_id)
+            .filter(|bill| bill.day_of_month.clamp(1, month_days) > day)
+            .map(|bill| i64::from(bill.amount))
+            .sum()
+    };
+    for bill in &settings.bills {
+        if bill.category_id != Uuid::default() {
+            entry(&mut categories, bill.category_id, &bill.category_name);
+        }
+    }
+    for (at, expense) in spent_this_month {
+        categories[at].spent += i64::from(expense.amount);
+    }
+
+    let mut variance_total = 0.0;
+    for category in &mut categories {
+        category.upcoming_bills = bills_after(category.category_id, year, month);
+        let samples: Vec<f64> = history
+            .iter()
+            .map(|(past_year, past_month)| {
+                let past_days = days_in_month(*past_month, *past_year);
+                if day >= past_days {
+                    return 0.0;
+                }
+                let variable =
+                    rest_of_month(expenses, category.category_id, *past_year, *past_month, day)
+                        - bills_after(category.category_id, *past_year, *past_month);
+                // Scaled, since the past month may have had more or fewer days left.
+                variable.max(0) as f64 * f64::from(remaining) / f64::from(past_days - day)
+            })
+            .collect();
+        let (mean, deviation) = if samples.is_empty() {
+            let pace = category.spent as f64 / f64::from(day) * f64::from(remaining);
+            (pace, pace / 2.0)
+        } else {
+            mean_and_deviation(&samples)
+        };
+        variance_total += deviation.powi(2);
+        let base = category.spent + category.upcoming_bills;
+        category.projected = base + mean.round() as i64;
+        category.low = base + (mean - BAND_WIDTH * deviation).max(0.0).round() as i64;
+        category.high = base + (mean + BAND_WIDTH * deviation).round() as i64;
+    }
+    categories.sort_by(|a, b| {
+        b.projected
+            .cmp(&a.projected)
+            .then_with(|| a.category_name.cmp(&b.category_name))
+    });
+
+    // Categories are treated as independent, so their deviations add in quadrature.
+    let projected: i64 = categories.iter().map(|category| category.projected).sum();
+    let floor: i64 = categories
+        .iter()
+        .map(|category| category.spent + category.upcoming_bills)
+        .sum();
+    let spread = (BAND_WIDTH * variance_total.sqrt()).round() as i64;
+    let budgets: Vec<i64> = categories
+        .iter()
+        .filter_map(|category| category.budget)
+        .collect();
+    Forecast {
+        month: this_month,
+        day,
+        days_in_month: month_days,
+        spent: categories.iter().map(|category| category.spent).sum(),
+        projected,
+        low: (projected - spread).max(floor),
+        high: projected + spread,
+        budget: (!budgets.is_empty()).then(|| budgets.iter().sum()),
+        categories,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::{Budget, RecurringBill};
+    use time::macros::date;
+
+    const FOOD: Uuid = Uuid::from_u128(1);
+    const HOME: Uuid = Uuid::from_u128(2);
+
+    fn expense(category_id: Uuid, amount: i32, date: &str) -> Expense {
+        let mut expense = Expense::new();
+        let name = if category_id == FOOD { "Food" } else { "Home" };
+        expense.category(category_id, name.to_owned());
+        expense.amount(amount);
+        expense.date(date.to_owned());
+        expense
+    }
+
+    fn bill(name: &str, amount: i32, day_of_month: u8) -> RecurringBill {
+        RecurringBill {
+            name: name.to_owned(),
+            amount,
+            day_of_month,
+            category_id: HOME,
+            category_name: "Home".to_owned(),
+            ..RecurringBill::default()
+        }
+    }
+
+    #[test]
+    fn without_history_the_pace_is_extended() {
+        let settings = Settings {
+            budgets: vec![Budget {
+                category_id: FOOD,
+                category_name: "Food".to_owned(),
+                monthly_limit: 350,
+            }],
+            ..Settings::default()
+        };
+        let expenses = vec![
+            expense(FOOD, 60, "2026-04-02"),
+            expense(FOOD, 40, "2026-04-10"),
+            // Entered ahead of time, not spent yet.
+            expense(FOOD, 500, "2026-04-12"),
+        ];
+        let forecast = forecast(&settings, &expenses, date!(2026 - 04 - 10));
+        assert_eq!(forecast.month, "2026-04");
+        assert_eq!((forecast.day, forecast.days_in_month), (10, 30));
+        assert_eq!(forecast.spent, 100);
+
+        // 10 a day for the 20 days left, give or take half of that.
+        let food = &forecast.categories[0];
+        assert_eq!((food.projected, food.low, food.high), (300, 172, 428));
+        assert_eq!(food.status(), BudgetStatus::AtRisk);
+        assert_eq!(
+            (forecast.projected, forecast.low, forecast.high),
+            (300, 172, 428)
+        );
+        assert_eq!(forecast.budget, Some(350));
+        assert_eq!(forecast.status(), BudgetStatus::AtRisk);
+    }
+
+    #[test]
+    fn history_is_scaled_to_the_days_left() {
+        let expenses = vec![
+            // November has 15 days left after the 15th, December and January 16.
+            expense(FOOD, 150, "2026-11-25"),
+            expense(FOOD, 999, "2026-11-10"),
+            expense(FOOD, 160, "2026-12-20"),
+            expense(FOOD, 50, "2027-01-03"),
+        ];
+        let forecast = forecast(&Settings::default(), &expenses, date!(2027 - 01 - 15));
+        let food = &forecast.categories[0];
+        assert_eq!(food.spent, 50);
+        assert_eq!((food.projected, food.low, food.high), (210, 210, 210));
+        assert_eq!(food.status(), BudgetStatus::NoBudget);
+        assert_eq!(forecast.budget, None);
+        assert_eq!(forecast.status(), BudgetStatus::NoBudget);
+    }
+
+    #[test]
+    fn bills_are_counted_once() {
+        let settings = Settings {
+            bills: vec![bill("Rent", 900, 31), bill("Internet", 50, 10)],
+            budgets: vec![Budget {
+                category_id: HOME,
+                category_name: "Home".to_owned(),
+                monthly_limit: 900,
+            }],
+            ..Settings::default()
+        };
+        let expenses = vec![
+            // Rent falls on the last day of shorter months.
+            expense(HOME, 900, "2026-01-31"),
+            expense(HOME, 900, "2026-02-28"),
+            expense(FOOD, 30, "2026-02-20"),
+            expense(HOME, 50, "2026-03-10"),
+        ];
+        let forecast = forecast(&settings, &expenses, date!(2026 - 03 - 15));
+        let home = &forecast.categories[0];
+        assert_eq!(home.category_name, "Home");
+        assert_eq!((home.spent, home.upcoming_bills), (50, 900));
+        assert_eq!((home.projected, home.low, home.high), (950, 950, 950));
+        assert_eq!(home.status(), BudgetStatus::Over);
+        assert_eq!(forecast.status(), BudgetStatus::Over);
+
+        // 30 in the 13 days left of February is about 37 in March's 16, and nothing in January.
+        let food = &forecast.categories[1];
+        assert_eq!(food.spent, 0);
+        assert_eq!(food.projected, 18);
+        assert!(food.low < food.projected && food.projected < food.high);
+        assert_eq!(forecast.projected, 968);
+    }
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Member {
+    pub id: Uuid,
+    pub email: String,
+}
+
+/// People sharing one ledger. It is the owner's: members see and add to the owner's
+/// expenses and categories while they belong to the household.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Household {
+    pub id: Uuid,
+    pub name: String,
+    pub owner_id: Uuid,
+    pub members: Vec<Member>,
+    /// The member asking, so the app knows who "me" is.
+    pub current_member_id: Uuid,
+}
+impl Household {
+    pub fn member(&self, id: Uuid) -> Option<&Member> {
+        self.members.iter().find(|member| member.id == id)
+    }
+
+    /// Who paid an expense. Expenses from before the household count as the owner's.
+    pub fn payer(&self, paid_by: Option<Uuid>) -> Uuid {
+        paid_by.unwrap_or(self.owner_id)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NewHousehold {
+    pub name: String,
+}
+
+/// A code another person enters to join.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Invite {
+    pub code: String,
+    /// RFC 3339.
+    pub expires_at: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct JoinHousehold {
+    pub code: String,
+}
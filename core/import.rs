@@ -0,0 +1,511 @@
+use crate::error::AppError;
+use crate::models::Expense;
+use std::collections::HashSet;
+use time::{Date, Month};
+
+/// A single bank transaction as read from an OFX or QIF download.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportedTransaction {
+    pub fitid: String,
+    pub date: String,
+    pub amount: i32,
+    pub payee: Option<String>,
+    pub memo: Option<String>,
+    pub account: Option<String>,
+    pub credit: bool,
+}
+
+impl ImportedTransaction {
+    pub fn description(&self) -> Option<String> {
+        match (&self.payee, &self.memo) {
+            (Some(payee), Some(memo)) if payee != memo => Some(format!("{payee} - {memo}")),
+            (Some(payee), _) => Some(payee.clone()),
+            (None, Some(memo)) => Some(memo.clone()),
+            (None, None) => None,
+        }
+    }
+    pub fn to_expense(&self) -> Expense {
+        let mut expense = Expense::new();
+        expense.amount(self.amount);
+        expense.date(self.date.clone());
+        expense.description(self.description());
+        expense.external_id(self.fitid.clone());
+        expense.account(self.account.clone());
+        expense
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ImportOutcome {
+    pub expenses: Vec<Expense>,
+    pub credits: Vec<ImportedTransaction>,
+    pub already_imported: Vec<ImportedTransaction>,
+}
+
+/// Splits parsed transactions into new expenses, credits and transactions whose
+/// FITID is in `known_ids` (or repeated inside the same file).
+pub fn into_expenses(
+    transactions: Vec<ImportedTransaction>,
+    known_ids: &HashSet<String>,
+) -> ImportOutcome {
+    let mut seen = known_ids.clone();
+    let mut outcome = ImportOutcome::default();
+    for transaction in transactions {
+        if !seen.insert(transaction.fitid.clone()) {
+            outcome.already_imported.push(transaction);
+        } else if transaction.credit {
+            outcome.credits.push(transaction);
+        } else {
+            outcome.expenses.push(transaction.to_expense());
+        }
+    }
+    outcome
+}
+
+pub fn known_ids(expenses: &[Expense]) -> HashSet<String> {
+    expenses
+        .iter()
+        .filter_map(|expense| expense.external_id.clone())
+        .collect()
+}
+
+/// Parses a decimal amount such as `-1.234,56` or `1500.5` and rounds it to whole units.
+/// Amounts too large for an expense, and those that round to nothing, are refused.
+fn parse_amount(raw: &str) -> Option<i32> {
+    let cleaned: String = raw
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | ','))
+        .collect();
+    let negative = cleaned.starts_with('-');
+    let digits = cleaned.trim_start_matches(['-', '+']);
+    let decimal_at = digits
+        .rfind(['.', ','])
+        .filter(|at| (1..=2).contains(&(digits.len() - at - 1)));
+    let (whole, fraction) = match decimal_at {
+        Some(at) => (&digits[..at], &digits[at + 1..]),
+        None => (digits, ""),
+    };
+    let whole: String = whole.chars().filter(char::is_ascii_digit).collect();
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    let whole: i64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    let cents: i64 = match fraction.len() {
+        0 => 0,
+        1 => fraction.parse::<i64>().ok()? * 10,
+        _ => fraction.parse().ok()?,
+    };
+    let units = whole.checked_mul(100)?.checked_add(cents + 50)? / 100;
+    if units == 0 {
+        return None;
+    }
+    i32::try_from(if negative { -units } else { units }).ok()
+}
+
+fn format_date(year: i32, month: u8, day: u8) -> Option<String> {
+    let month = Month::try_from(month).ok()?;
+    let date = Date::from_calendar_date(year, month, day).ok()?;
+    Some(format!(
+        "{:04}-{:02}-{:02}",
+        date.year(),
+        u8::from(date.month()),
+        date.day()
+    ))
+}
+
+pub mod ofx {
+    use super::{format_date, parse_amount, ImportedTransaction};
+    use crate::error::AppError;
+
+    #[derive(Default)]
+    struct Pending {
+        fitid: Option<String>,
+        date: Option<String>,
+        amount: Option<i32>,
+        payee: Option<String>,
+        memo: Option<String>,
+    }
+
+    fn unescape(value: &str) -> String {
+        value
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&")
+    }
+
+    /// OFX dates look like `20261019120000.000[-3:ART]`; only the calendar date is kept.
+    fn parse_date(raw: &str) -> Option<String> {
+        let digits = raw.get(..8)?;
+        format_date(
+            digits.get(..4)?.parse().ok()?,
+            digits.get(4..6)?.parse().ok()?,
+            digits.get(6..8)?.parse().ok()?,
+        )
+    }
+
+    fn finish(
+        pending: Pending,
+        account: &Option<String>,
+        index: usize,
+    ) -> Result<ImportedTransaction, AppError<'static>> {
+        let missing =
+            |tag: &str| AppError::HardError(format!("OFX transaction #{index} is missing <{tag}>"));
+        let amount = pending.amount.ok_or_else(|| missing("TRNAMT"))?;
+        Ok(ImportedTransaction {
+            fitid: pending.fitid.ok_or_else(|| missing("FITID"))?,
+            date: pending.date.ok_or_else(|| missing("DTPOSTED"))?,
+            amount: amount.abs(),
+            payee: pending.payee,
+            memo: pending.memo,
+            account: account.clone(),
+            credit: amount > 0,
+        })
+    }
+
+    /// Parses both the SGML (OFX 1.x) and XML (OFX 2.x) flavours.
+    pub fn parse(input: &str) -> Result<Vec<ImportedTransaction>, AppError<'static>> {
+        let start = input
+            .find("<OFX>")
+            .ok_or(AppError::Invalid("missing <OFX> root element"))?;
+        let mut transactions = vec![];
+        let mut account = None;
+        let mut pending: Option<Pending> = None;
+
+        for segment in input[start..].split('<').skip(1) {
+            let Some((tag, value)) = segment.split_once('>') else {
+                continue;
+            };
+            let tag = tag.trim().to_ascii_uppercase();
+            let value = value.trim();
+            let text = (!value.is_empty()).then(|| unescape(value));
+            match tag.as_str() {
+                "STMTTRN" => pending = Some(Pending::default()),
+                "/STMTTRN" => {
+                    if let Some(done) = pending.take() {
+                        transactions.push(finish(done, &account, transactions.len() + 1)?);
+                    }
+                }
+                "ACCTID" => account = text,
+                _ => {
+                    let Some(current) = pending.as_mut() else {
+                        continue;
+                    };
+                    match tag.as_str() {
+                        "FITID" => current.fitid = text,
+                        "DTPOSTED" => {
+                            current.date = Some(parse_date(value).ok_or_else(|| {
+                                AppError::HardError(format!("invalid OFX date: {value}"))
+                            })?)
+                        }
+                        "TRNAMT" => {
+                            current.amount = Some(parse_amount(value).ok_or_else(|| {
+                                AppError::HardError(format!("invalid OFX amount: {value}"))
+                            })?)
+                        }
+                        "NAME" | "PAYEE" => current.payee = text,
+                        "MEMO" => current.memo = text,
+                        _ => {}
+                    }
+                }
+            }
+        }
+        if pending.is_some() {
+            return Err(AppError::Invalid("unterminated <STMTTRN> element"));
+        }
+        Ok(transactions)
+    }
+}
+
+pub mod qif {
+    use super::{format_date, parse_amount, ImportedTransaction};
+    use crate::error::AppError;
+    use std::collections::HashMap;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum DateOrder {
+        #[default]
+        DayFirst,
+        MonthFirst,
+    }
+
+    /// Accepts `19/10/2026`, `10-19-26` and Quicken's `10/19'26` style dates.
+    fn parse_date(raw: &str, order: DateOrder) -> Option<String> {
+        let parts: Vec<&str> = raw
+            .split(['/', '-', '.', '\''])
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .collect();
+        let [first, second, year] = parts.as_slice() else {
+            return None;
+        };
+        let (day, month) = match order {
+            DateOrder::DayFirst => (first, second),
+            DateOrder::MonthFirst => (second, first),
+        };
+        let year: i32 = year.parse().ok()?;
+        let year = match year {
+            0..=69 => 2000 + year,
+            70..=99 => 1900 + year,
+            _ => year,
+        };
+        format_date(year, month.parse().ok()?, day.parse().ok()?)
+    }
+
+    /// QIF has no transaction ids, so one is derived from the record contents. Identical
+    /// records in the same file get an increasing suffix to stay distinct.
+    pub fn parse(
+        input: &str,
+        order: DateOrder,
+    ) -> Result<Vec<ImportedTransaction>, AppError<'static>> {
+        let mut transactions = vec![];
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+        let mut account: Option<String> = None;
+        let mut in_account_block = false;
+        let mut fields: HashMap<char, String> = HashMap::new();
+
+        for line in input.lines().map(str::trim_end) {
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('!') {
+                in_account_block = header.eq_ignore_ascii_case("account");
+                continue;
+            }
+            if line == "^" {
+                if in_account_block {
+                    in_account_block = false;
+                    fields.clear();
+                    continue;
+                }
+                if fields.is_empty() {
+                    continue;
+                }
+                let record = std::mem::take(&mut fields);
+                let index = transactions.len() + 1;
+                let raw_date = record.get(&'D').ok_or_else(|| {
+                    AppError::HardError(format!("QIF record #{index} is missing a date"))
+                })?;
+                let raw_amount = record.get(&'T').or(record.get(&'U')).ok_or_else(|| {
+                    AppError::HardError(format!("QIF record #{index} is missing an amount"))
+                })?;
+                let date = parse_date(raw_date, order)
+                    .ok_or_else(|| AppError::HardError(format!("invalid QIF date: {raw_date}")))?;
+                let amount = parse_amount(raw_amount).ok_or_else(|| {
+                    AppError::HardError(format!("invalid QIF amount: {raw_amount}"))
+                })?;
+                let payee = record.get(&'P').cloned();
+                let key = format!(
+                    "{date}|{amount}|{}|{}",
+                    payee.clone().unwrap_or_default(),
+                    record.get(&'N').cloned().unwrap_or_default()
+                );
+                let occurrence = occurrences.entry(key.clone()).or_default();
+                *occurrence += 1;
+                transactions.push(ImportedTransaction {
+                    fitid: format!("qif:{key}#{occurrence}"),
+                    date,
+                    amount: amount.abs(),
+                    payee,
+                    memo: record.get(&'M').cloned(),
+                    account: account.clone(),
+                    credit: amount > 0,
+                });
+                continue;
+            }
+            let mut chars = line.chars();
+            let Some(code) = chars.next() else {
+                continue;
+            };
+            let value = chars.as_str().trim().to_owned();
+            if in_account_block {
+                if code == 'N' {
+                    account = Some(value);
+                }
+            } else if !value.is_empty() {
+                fields.insert(code, value);
+            }
+        }
+        if !fields.is_empty() {
+            return Err(AppError::Invalid("QIF file ends in the middle of a record"));
+        }
+        Ok(transactions)
+    }
+}
+
+/// Picks the parser from the file contents.
+pub fn parse(
+    input: &str,
+    order: qif::DateOrder,
+) -> Result<Vec<ImportedTransaction>, AppError<'static>> {
+    if input.contains("<OFX>") {
+        ofx::parse(input)
+    } else if input.trim_start().starts_with('!') {
+        qif::parse(input, order)
+    } else {
+        Err(AppError::Invalid("unrecognized file, expected OFX or QIF"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OFX_SGML: &str = "OFXHEADER:100
+DATA:OFXSGML
+
+<OFX>
+<BANKMSGSRSV1><STMTTRNRS><STMTRS>
+<BANKACCTFROM><ACCTID>12345
+</BANKACCTFROM>
+<BANKTRANLIST>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20261019120000.000[-3:ART]
+<TRNAMT>-1.234,56
+<FITID>A1
+<NAME>Super &amp; Co
+<MEMO>Groceries
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>CREDIT
+<DTPOSTED>20261020
+<TRNAMT>500.00
+<FITID>A2
+<NAME>Salary
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20261021
+<TRNAMT>-10.4
+<FITID>A1
+<NAME>Repeated
+</STMTTRN>
+</BANKTRANLIST>
+</STMTRS></STMTTRNRS></BANKMSGSRSV1>
+</OFX>";
+
+    const QIF: &str = "!Account
+NChecking
+^
+!Type:Bank
+D10/19'26
+T-25.50
+PBakery
+^
+D10/19'26
+T-25.50
+PBakery
+^
+D10/20'26
+T100
+PRefund
+^
+";
+
+    #[test]
+    fn parses_sgml_ofx() {
+        let transactions = ofx::parse(OFX_SGML).unwrap();
+        assert_eq!(transactions.len(), 3);
+        let first = &transactions[0];
+        assert_eq!(first.fitid, "A1");
+        assert_eq!(first.date, "2026-10-19");
+        assert_eq!(first.amount, 1235);
+        assert_eq!(first.account.as_deref(), Some("12345"));
+        assert!(!first.credit);
+        assert_eq!(
+            first.description().as_deref(),
+            Some("Super & Co - Groceries")
+        );
+        assert!(transactions[1].credit);
+        assert_eq!(transactions[2].amount, 10);
+    }
+
+    #[test]
+    fn parses_xml_ofx() {
+        let input = "<?xml version=\"1.0\"?><OFX><STMTTRN><DTPOSTED>20260102</DTPOSTED>\
+            <TRNAMT>-3.50</TRNAMT><FITID>X</FITID></STMTTRN></OFX>";
+        let transactions = ofx::parse(input).unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].date, "2026-01-02");
+        assert_eq!(transactions[0].amount, 4);
+    }
+
+    #[test]
+    fn rejects_broken_ofx() {
+        assert!(ofx::parse("<OFX><STMTTRN><FITID>A</STMTTRN></OFX>").is_err());
+        assert!(ofx::parse("<OFX><STMTTRN><FITID>A").is_err());
+        assert!(ofx::parse("no root").is_err());
+    }
+
+    #[test]
+    fn dedups_by_fitid() {
+        let transactions = ofx::parse(OFX_SGML).unwrap();
+        let outcome = into_expenses(transactions.clone(), &HashSet::new());
+        assert_eq!(outcome.expenses.len(), 1);
+        assert_eq!(outcome.credits.len(), 1);
+        assert_eq!(outcome.already_imported.len(), 1);
+        assert_eq!(
+            outcome.already_imported[0].payee.as_deref(),
+            Some("Repeated")
+        );
+
+        let known = known_ids(&outcome.expenses);
+        let again = into_expenses(transactions, &known);
+        assert!(again.expenses.is_empty());
+        assert_eq!(again.already_imported.len(), 2);
+    }
+
+    #[test]
+    fn parses_qif_with_stable_ids() {
+        let transactions = qif::parse(QIF, qif::DateOrder::MonthFirst).unwrap();
+        assert_eq!(transactions.len(), 3);
+        assert_eq!(transactions[0].date, "2026-10-19");
+        assert_eq!(transactions[0].amount, 26);
+        assert_eq!(transactions[0].account.as_deref(), Some("Checking"));
+        assert_ne!(transactions[0].fitid, transactions[1].fitid);
+        assert!(transactions[2].credit);
+
+        let reparsed = qif::parse(QIF, qif::DateOrder::MonthFirst).unwrap();
+        let outcome = into_expenses(
+            reparsed,
+            &known_ids(&into_expenses(transactions, &HashSet::new()).expenses),
+        );
+        assert!(outcome.expenses.is_empty());
+    }
+
+    #[test]
+    fn qif_date_order() {
+        let input = "!Type:Bank\nD03/04/2026\nT-1\n^\n";
+        let day_first = qif::parse(input, qif::DateOrder::DayFirst).unwrap();
+        let month_first = qif::parse(input, qif::DateOrder::MonthFirst).unwrap();
+        assert_eq!(day_first[0].date, "2026-04-03");
+        assert_eq!(month_first[0].date, "2026-03-04");
+        assert!(qif::parse("!Type:Bank\nD03/04/2026\n", qif::DateOrder::DayFirst).is_err());
+    }
+
+    #[test]
+    fn amounts_round_to_whole_units() {
+        assert_eq!(parse_amount("-1.234,56"), Some(-1235));
+        assert_eq!(parse_amount("1500.5"), Some(1501));
+        assert_eq!(parse_amount("-0.50"), Some(-1));
+        assert_eq!(parse_amount("-0.40"), None);
+        assert_eq!(parse_amount("0"), None);
+        assert_eq!(parse_amount("2147483647"), Some(i32::MAX));
+        assert_eq!(parse_amount("2147483648"), None);
+        assert_eq!(parse_amount("99999999999999999999"), None);
+        assert_eq!(parse_amount("92233720368547758.07"), None);
+    }
+
+    #[test]
+    fn picks_the_parser() {
+        assert_eq!(parse(OFX_SGML, qif::DateOrder::DayFirst).unwrap().len(), 3);
+        assert_eq!(parse(QIF, qif::DateOrder::MonthFirst).unwrap().len(), 3);
+        assert!(parse("date,amount", qif::DateOrder::DayFirst).is_err());
+    }
+}
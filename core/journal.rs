@@ -0,0 +1,332 @@
+use crate::error::AppError;
+use crate::models::Expense;
+use regex::Regex;
+use std::collections::BTreeSet;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JournalFormat {
+    #[default]
+    Ledger,
+    Hledger,
+    Beancount,
+}
+
+#[derive(Clone, Debug)]
+pub struct JournalOptions {
+    pub format: JournalFormat,
+    pub funding_account: String,
+    pub commodity: String,
+}
+impl Default for JournalOptions {
+    fn default() -> Self {
+        JournalOptions {
+            format: JournalFormat::default(),
+            funding_account: "Assets:Cash".to_owned(),
+            commodity: "ARS".to_owned(),
+        }
+    }
+}
+impl JournalOptions {
+    pub fn new() -> Self {
+        JournalOptions::default()
+    }
+    pub fn format(self, format: JournalFormat) -> Self {
+        JournalOptions { format, ..self }
+    }
+    pub fn funding_account(self, funding_account: String) -> Self {
+        JournalOptions {
+            funding_account,
+            ..self
+        }
+    }
+    pub fn commodity(self, commodity: String) -> Self {
+        JournalOptions { commodity, ..self }
+    }
+}
+
+/// Whether `commodity` can be written unquoted. Beancount wants an uppercase currency
+/// name; ledger and hledger accept any symbol without digits, spaces or operators.
+pub fn valid_commodity(commodity: &str, format: JournalFormat) -> bool {
+    let pattern = match format {
+        JournalFormat::Ledger | JournalFormat::Hledger => {
+            r#"^[^\s\d\-+.,;:?!*/^&|=<>{}\[\]()@"]+$"#
+        }
+        JournalFormat::Beancount => r"^[A-Z][A-Z0-9'._-]*$",
+    };
+    Regex::new(pattern)
+        .map(|regex| regex.is_match(commodity))
+        .unwrap_or(false)
+}
+
+/// Whether `account` reads back as one account name. Ledger and hledger end an account at
+/// two spaces, a tab or a comment; beancount also wants a root type and capitalized parts.
+pub fn valid_account(account: &str, format: JournalFormat) -> bool {
+    match format {
+        JournalFormat::Ledger | JournalFormat::Hledger => {
+            account == account.trim()
+                && !account.contains(['\t', ';'])
+                && !account.contains("  ")
+                && !account.starts_with(['(', '['])
+                && account.split(':').all(|part| !part.trim().is_empty())
+        }
+        JournalFormat::Beancount => Regex::new(
+            r"^(Assets|Liabilities|Equity|Income|Expenses)(:[\p{Lu}\p{Nd}][\p{L}\p{Nd}-]*)+$",
+        )
+        .map(|regex| regex.is_match(account))
+        .unwrap_or(false),
+    }
+}
+
+/// Turns a free-form name into a single account component. Applying it twice gives the
+/// same result, so exported journals stay stable across re-exports.
+fn account_component(name: &str, format: JournalFormat) -> String {
+    let component = match format {
+        JournalFormat::Ledger | JournalFormat::Hledger => name
+            .replace([':', ';', '\t'], " ")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" "),
+        JournalFormat::Beancount => name
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join("-"),
+    };
+    if component.is_empty() {
+        "Uncategorized".to_owned()
+    } else {
+        component
+    }
+}
+
+pub fn expense_account(expense: &Expense, format: JournalFormat) -> String {
+    format!(
+        "Expenses:{}",
+        account_component(&expense.category_name, format)
+    )
+}
+
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn description(expense: &Expense) -> String {
+    let description = single_line(expense.description.as_deref().unwrap_or_default());
+    if description.is_empty() {
+        single_line(&expense.category_name)
+    } else {
+        description
+    }
+}
+
+/// Ledger and hledger have no escaping: `;` starts a comment and a leading `*`, `!` or `(`
+/// reads as a status or code, so those are replaced.
+fn payee(expense: &Expense) -> String {
+    let payee = description(expense).replace(';', ",");
+    let payee = payee.trim_start_matches(['*', '!', '(', ' ']);
+    if payee.is_empty() {
+        "Expense".to_owned()
+    } else {
+        payee.to_owned()
+    }
+}
+
+fn entry(expense: &Expense, date: &str, options: &JournalOptions) -> String {
+    let account = expense_account(expense, options.format);
+    let amount = format!("{} {}", expense.amount, options.commodity);
+    match options.format {
+        JournalFormat::Ledger | JournalFormat::Hledger => {
+            let payee = payee(expense);
+            format!(
+                "{date} {payee}\n    ; id: {}\n    {account}    {amount}\n    {}\n",
+                expense.id, options.funding_account
+            )
+        }
+        JournalFormat::Beancount => {
+            let narration = description(expense)
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"");
+            format!(
+                "{date} * \"{narration}\"\n  id: \"{}\"\n  {account}  {amount}\n  {}\n",
+                expense.id, options.funding_account
+            )
+        }
+    }
+}
+
+/// Renders expenses as a plain-text journal. Entries are ordered by date, account, amount
+/// and id so the same data always produces byte-identical output; undated expenses are
+/// left out because every journal format requires a date. Fails when the commodity or
+/// funding account would not read back in the chosen format.
+pub fn export(expenses: &[Expense], options: &JournalOptions) -> Result<String, AppError<'static>> {
+    if !valid_commodity(&options.commodity, options.format) {
+        return Err(AppError::Invalid(match options.format {
+            JournalFormat::Beancount => {
+                "the commodity must be uppercase letters, digits or ' . _ -, starting with a letter"
+            }
+            _ => "the commodity cannot contain digits, spaces or symbols such as - ; @",
+        }));
+    }
+    if !valid_account(&options.funding_account, options.format) {
+        return Err(AppError::Invalid(match options.format {
+            JournalFormat::Beancount => {
+                "the paid from account must start with Assets, Liabilities, Equity, Income or Expenses, followed by capitalized :Parts"
+            }
+            _ => "the paid from account cannot contain ; a tab or two spaces in a row",
+        }));
+    }
+    let mut dated: Vec<(&str, String, &Expense)> = expenses
+        .iter()
+        .filter_map(|expense| {
+            let date = expense.date.as_deref().filter(|date| !date.is_empty())?;
+            Some((date, expense_account(expense, options.format), expense))
+        })
+        .collect();
+    dated.sort_by(|(a_date, a_account, a), (b_date, b_account, b)| {
+        (a_date, a_account, a.amount, a.id).cmp(&(b_date, b_account, b.amount, b.id))
+    });
+
+    let mut journal = String::from("; Exported from Finance Home\n\n");
+    if options.format == JournalFormat::Beancount {
+        if let Some((first_date, _, _)) = dated.first() {
+            let accounts: BTreeSet<&str> = dated
+                .iter()
+                .map(|(_, account, _)| account.as_str())
+                .chain([options.funding_account.as_str()])
+                .collect();
+            for account in accounts {
+                journal.push_str(&format!("{first_date} open {account}\n"));
+            }
+            journal.push('\n');
+        }
+    }
+    let entries: Vec<String> = dated
+        .iter()
+        .map(|(date, _, expense)| entry(expense, date, options))
+        .collect();
+    journal.push_str(&entries.join("\n"));
+    Ok(journal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn expense(id: u128, date: Option<&str>, category: &str, description: &str) -> Expense {
+        let mut expense = Expense::new();
+        expense.id = Uuid::from_u128(id);
+        expense.amount(1500);
+        expense.category(Uuid::from_u128(100), category.to_owned());
+        expense.description(Some(description.to_owned()));
+        if let Some(date) = date {
+            expense.date(date.to_owned());
+        }
+        expense
+    }
+
+    fn sample() -> Vec<Expense> {
+        vec![
+            expense(2, Some("2026-10-19"), "Food: out", "Dinner ; tip"),
+            expense(1, Some("2026-10-01"), "rent", "*October"),
+            expense(3, None, "rent", "undated"),
+        ]
+    }
+
+    #[test]
+    fn ledger_export() {
+        let journal = export(&sample(), &JournalOptions::new()).unwrap();
+        assert_eq!(
+            journal,
+            "; Exported from Finance Home\n\n\
+             2026-10-01 October\n    ; id: 00000000-0000-0000-0000-000000000001\n    \
+             Expenses:rent    1500 ARS\n    Assets:Cash\n\n\
+             2026-10-19 Dinner , tip\n    ; id: 00000000-0000-0000-0000-000000000002\n    \
+             Expenses:Food out    1500 ARS\n    Assets:Cash\n"
+        );
+    }
+
+    #[test]
+    fn hledger_export_matches_ledger() {
+        let ledger = export(&sample(), &JournalOptions::new()).unwrap();
+        let hledger = export(
+            &sample(),
+            &JournalOptions::new().format(JournalFormat::Hledger),
+        )
+        .unwrap();
+        assert_eq!(ledger, hledger);
+    }
+
+    #[test]
+    fn beancount_export() {
+        let mut expenses = sample();
+        expenses[0].description(Some("Say \"hi\"".to_owned()));
+        let options = JournalOptions::new()
+            .format(JournalFormat::Beancount)
+            .commodity("USD".to_owned());
+        let journal = export(&expenses, &options).unwrap();
+        assert_eq!(
+            journal,
+            "; Exported from Finance Home\n\n\
+             2026-10-01 open Assets:Cash\n\
+             2026-10-01 open Expenses:Food-Out\n\
+             2026-10-01 open Expenses:Rent\n\n\
+             2026-10-01 * \"*October\"\n  id: \"00000000-0000-0000-0000-000000000001\"\n  \
+             Expenses:Rent  1500 USD\n  Assets:Cash\n\n\
+             2026-10-19 * \"Say \\\"hi\\\"\"\n  id: \"00000000-0000-0000-0000-000000000002\"\n  \
+             Expenses:Food-Out  1500 USD\n  Assets:Cash\n"
+        );
+    }
+
+    #[test]
+    fn export_is_stable() {
+        let mut reversed = sample();
+        reversed.reverse();
+        let options = JournalOptions::new().format(JournalFormat::Beancount);
+        assert_eq!(
+            export(&sample(), &options).unwrap(),
+            export(&reversed, &options).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_options_that_do_not_read_back() {
+        let beancount = JournalOptions::new().format(JournalFormat::Beancount);
+        assert!(export(&sample(), &beancount.clone().commodity("usd".to_owned())).is_err());
+        assert!(export(&sample(), &beancount.clone().commodity("$".to_owned())).is_err());
+        assert!(export(&sample(), &beancount.funding_account("Cash".to_owned())).is_err());
+
+        let ledger = JournalOptions::new();
+        assert!(export(&sample(), &ledger.clone().commodity("$".to_owned())).is_ok());
+        assert!(export(&sample(), &ledger.clone().commodity("U S".to_owned())).is_err());
+        assert!(export(
+            &sample(),
+            &ledger.clone().funding_account("Assets;Cash".to_owned())
+        )
+        .is_err());
+        assert!(export(
+            &sample(),
+            &ledger.funding_account("Assets:  Cash".to_owned())
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn validates_accounts() {
+        assert!(valid_account("Assets:Bank Account", JournalFormat::Ledger));
+        assert!(!valid_account("Assets::Cash", JournalFormat::Hledger));
+        assert!(valid_account(
+            "Liabilities:Visa-2026",
+            JournalFormat::Beancount
+        ));
+        assert!(!valid_account("Assets:cash", JournalFormat::Beancount));
+        assert!(valid_commodity("VACHR'S", JournalFormat::Beancount));
+    }
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+pub const MIN_PIN_DIGITS: usize = 4;
+pub const MAX_PIN_DIGITS: usize = 8;
+/// Wrong PINs allowed before each further try has to wait.
+pub const FREE_ATTEMPTS: u32 = 3;
+const MAX_WAIT_SECONDS: i64 = 60 * 60;
+
+pub fn valid_pin(pin: &str) -> bool {
+    (MIN_PIN_DIGITS..=MAX_PIN_DIGITS).contains(&pin.len())
+        && pin.chars().all(|c| c.is_ascii_digit())
+}
+
+/// How long to wait after this many wrong PINs in a row: nothing for the first few, then
+/// 30 seconds doubling with each failure, up to an hour.
+pub fn backoff_seconds(failures: u32) -> i64 {
+    if failures < FREE_ATTEMPTS {
+        return 0;
+    }
+    let doublings = (failures - FREE_ATTEMPTS).min(10);
+    (30 * 2i64.pow(doublings)).min(MAX_WAIT_SECONDS)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct LockStatus {
+    /// Whether a PIN is set.
+    pub enabled: bool,
+    /// Seconds until another PIN may be tried.
+    pub wait_seconds: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct UnlockResult {
+    pub unlocked: bool,
+    pub wait_seconds: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pins_are_four_to_eight_digits() {
+        assert!(valid_pin("0000"));
+        assert!(valid_pin("12345678"));
+        assert!(!valid_pin("123"));
+        assert!(!valid_pin("123456789"));
+        assert!(!valid_pin("12a4"));
+        assert!(!valid_pin(" 1234"));
+        assert!(!valid_pin("١٢٣٤"));
+    }
+
+    #[test]
+    fn the_wait_doubles_up_to_an_hour() {
+        assert_eq!(backoff_seconds(0), 0);
+        assert_eq!(backoff_seconds(FREE_ATTEMPTS - 1), 0);
+        assert_eq!(backoff_seconds(FREE_ATTEMPTS), 30);
+        assert_eq!(backoff_seconds(FREE_ATTEMPTS + 1), 60);
+        assert_eq!(backoff_seconds(FREE_ATTEMPTS + 6), 1920);
+        assert_eq!(backoff_seconds(FREE_ATTEMPTS + 7), MAX_WAIT_SECONDS);
+        assert_eq!(backoff_seconds(u32::MAX), MAX_WAIT_SECONDS);
+    }
+}
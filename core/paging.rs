@@ -0,0 +1,341 @@
+use crate::{
+    filters::ExpenseFilter,
+    household::Household,
+    models::Expense,
+    sorting::{GroupBy, ListOptions, SortBy},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub const PAGE_SIZE: usize = 50;
+/// The largest `limit` a server answers in full.
+pub const MAX_PAGE_SIZE: usize = 500;
+
+/// One page of `GET /expenses`, returned when the request has a `limit`. `count` and
+/// `total` cover every expense matching the filter, not only this page.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ExpensePage {
+    pub items: Vec<Expense>,
+    pub next_cursor: Option<String>,
+    pub count: usize,
+    pub total: i64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExpenseQuery {
+    pub filter: ExpenseFilter,
+    pub options: ListOptions,
+    pub limit: usize,
+    /// Opaque to clients; currently the offset of the page.
+    pub cursor: Option<String>,
+}
+
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+fn encode_params(params: &[(&str, String)]) -> String {
+    params
+        .iter()
+        .map(|(name, value)| format!("{name}={}", encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// The filter, order and grouping of a list as query parameters, leaving out the
+/// defaults. Used both for API requests, without the grouping, and for the app's own URLs.
+fn list_params(filter: &ExpenseFilter, options: &ListOptions) -> Vec<(&'static str, String)> {
+    let sort_by = match options.sort_by {
+        SortBy::Date => None,
+        SortBy::Amount => Some("amount"),
+        SortBy::Category => Some("category"),
+    };
+    let group_by = match options.group_by {
+        GroupBy::None => None,
+        GroupBy::Day => Some("day"),
+        GroupBy::Month => Some("month"),
+    };
+    let params = [
+        ("sort_by", sort_by.map(str::to_owned)),
+        ("order", (!options.descending).then(|| "asc".to_owned())),
+        ("group_by", group_by.map(str::to_owned)),
+        ("from", filter.from.clone()),
+        ("to", filter.to.clone()),
+        ("min_amount", filter.min_amount.map(|min| min.to_string())),
+        ("max_amount", filter.max_amount.map(|max| max.to_string())),
+        (
+            "category_ids",
+            (!filter.category_ids.is_empty()).then(|| {
+                let ids: Vec<String> = filter.category_ids.iter().map(Uuid::to_string).collect();
+                ids.join(",")
+            }),
+        ),
+        (
+            "text",
+            Some(filter.text.trim().to_owned()).filter(|text| !text.is_empty()),
+        ),
+        ("paid_by", filter.paid_by.map(|member| member.to_string())),
+    ];
+    params
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|value| (name, value)))
+        .collect()
+}
+
+/// Query string for a list, empty when nothing differs from the defaults.
+pub fn list_query_string(filter: &ExpenseFilter, options: &ListOptions) -> String {
+    encode_params(&list_params(filter, options))
+}
+
+/// Reads the decoded parameters written by [`list_query_string`]; unknown or missing
+/// ones fall back to the defaults.
+pub fn list_from_params(params: &HashMap<String, String>) -> (ExpenseFilter, ListOptions) {
+    let get = |name: &str| params.get(name).filter(|value| !value.is_empty()).cloned();
+    let filter = ExpenseFilter {
+        from: get("from"),
+        to: get("to"),
+        category_ids: get("category_ids")
+            .map(|ids| ids.split(',').filter_map(|id| id.parse().ok()).collect())
+            .unwrap_or_default(),
+        min_amount: get("min_amount").and_then(|min| min.parse().ok()),
+        max_amount: get("max_amount").and_then(|max| max.parse().ok()),
+        text: get("text").unwrap_or_default(),
+        paid_by: get("paid_by").and_then(|member| member.parse().ok()),
+    };
+    let options = ListOptions {
+        sort_by: match get("sort_by").as_deref() {
+            Some("amount") => SortBy::Amount,
+            Some("category") => SortBy::Category,
+            _ => SortBy::Date,
+        },
+        descending: get("order").as_deref() != Some("asc"),
+        group_by: match get("group_by").as_deref() {
+            Some("day") => GroupBy::Day,
+            Some("month") => GroupBy::Month,
+            _ => GroupBy::None,
+        },
+    };
+    (filter, options)
+}
+
+impl ExpenseQuery {
+    pub fn new(filter: ExpenseFilter, options: ListOptions) -> Self {
+        ExpenseQuery {
+            filter,
+            options,
+            limit: PAGE_SIZE,
+            cursor: None,
+        }
+    }
+
+    pub fn cursor(self, cursor: Option<String>) -> Self {
+        ExpenseQuery { cursor, ..self }
+    }
+
+    pub fn offset(&self) -> usize {
+        self.cursor
+            .as_deref()
+            .and_then(|cursor| cursor.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// The API request; grouping is done by the client, so it is left out.
+    pub fn to_query_string(&self) -> String {
+        let mut params = vec![("limit", self.limit.to_string())];
+        params.extend(
+            list_params(&self.filter, &self.options)
+                .into_iter()
+                .filter(|(name, _)| *name != "group_by"),
+        );
+        params.extend(self.cursor.clone().map(|cursor| ("cursor", cursor)));
+        encode_params(&params)
+    }
+
+    /// Reads decoded query parameters. Without a `limit` the client wants the plain
+    /// list, so there is no query.
+    pub fn from_params(params: &HashMap<String, String>) -> Option<Self> {
+        let limit = params
+            .get("limit")?
+            .parse::<usize>()
+            .ok()?
+            .clamp(1, MAX_PAGE_SIZE);
+        let (filter, options) = list_from_params(params);
+        Some(ExpenseQuery {
+            filter,
+            options,
+            limit,
+            cursor: params
+                .get("cursor")
+                .filter(|cursor| !cursor.is_empty())
+                .cloned(),
+        })
+    }
+
+    /// The page after `offset`, given how many expenses match in total.
+    pub fn next_cursor(&self, count: usize) -> Option<String> {
+        let next = self.offset() + self.limit;
+        (next < count).then(|| next.to_string())
+    }
+}
+
+/// Filters, sorts and slices `expenses` the way a paging server would.
+pub fn page(
+    expenses: &[Expense],
+    query: &ExpenseQuery,
+    household: Option<&Household>,
+) -> ExpensePage {
+    let mut matching = query.filter.apply(expenses, household);
+    query.options.sort(&mut matching);
+    let count = matching.len();
+    let total = matching
+        .iter()
+        .map(|expense| i64::from(expense.amount))
+        .sum();
+    ExpensePage {
+        items: matching
+            .into_iter()
+            .skip(query.offset())
+            .take(query.limit)
+            .collect(),
+        next_cursor: query.next_cursor(count),
+        count,
+        total,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expenses(count: i32) -> Vec<Expense> {
+        (1..=count)
+            .map(|day| {
+                let mut expense = Expense::new();
+                expense.id = Uuid::from_u128(day as u128);
+                expense.amount(day * 10);
+                expense.date(format!("2026-01-{day:02}"));
+                expense
+            })
+            .collect()
+    }
+
+    fn query(limit: usize) -> ExpenseQuery {
+        ExpenseQuery {
+            limit,
+            ..ExpenseQuery::new(ExpenseFilter::default(), ListOptions::default())
+        }
+    }
+
+    #[test]
+    fn cursors_walk_every_expense_once() {
+        let expenses = expenses(7);
+        let mut query = query(3);
+        let mut seen = vec![];
+        loop {
+            let page = page(&expenses, &query, None);
+            assert_eq!((page.count, page.total), (7, 280));
+            seen.extend(page.items.iter().map(|expense| expense.amount));
+            match page.next_cursor {
+                Some(cursor) => query = query.cursor(Some(cursor)),
+                None => break,
+            }
+        }
+        // Newest first by default.
+        assert_eq!(seen, vec![70, 60, 50, 40, 30, 20, 10]);
+    }
+
+    #[test]
+    fn totals_cover_the_filter_not_the_page() {
+        let filter = ExpenseFilter {
+            min_amount: Some(30),
+            ..ExpenseFilter::default()
+        };
+        let query = ExpenseQuery {
+            limit: 2,
+            ..ExpenseQuery::new(filter, ListOptions::default())
+        };
+        let first = page(&expenses(7), &query, None);
+        assert_eq!(first.items.len(), 2);
+        assert_eq!((first.count, first.total), (5, 250));
+        assert_eq!(first.next_cursor.as_deref(), Some("2"));
+
+        let last = page(&expenses(7), &query.cursor(Some("4".to_owned())), None);
+        assert_eq!(last.items.len(), 1);
+        assert_eq!(last.items[0].amount, 30);
+        assert_eq!(last.next_cursor, None);
+    }
+
+    #[test]
+    fn cursors_past_the_end_or_unreadable() {
+        let past = page(&expenses(3), &query(2).cursor(Some("10".to_owned())), None);
+        assert!(past.items.is_empty());
+        assert_eq!((past.count, past.next_cursor), (3, None));
+        // An unreadable cursor starts over.
+        let reset = page(&expenses(3), &query(2).cursor(Some("x".to_owned())), None);
+        assert_eq!(reset.items[0].amount, 30);
+        assert_eq!(reset.next_cursor.as_deref(), Some("2"));
+        assert_eq!(page(&[], &query(2), None), ExpensePage::default());
+    }
+
+    #[test]
+    fn grouping_stays_out_of_api_requests() {
+        let options = ListOptions {
+            group_by: GroupBy::Month,
+            ..ListOptions::default()
+        };
+        let filter = ExpenseFilter::default();
+        assert_eq!(list_query_string(&filter, &options), "group_by=month");
+        assert_eq!(
+            ExpenseQuery::new(filter.clone(), options).to_query_string(),
+            ExpenseQuery::new(filter, ListOptions::default()).to_query_string()
+        );
+    }
+
+    #[test]
+    fn queries_read_back_from_their_parameters() {
+        let filter = ExpenseFilter {
+            from: Some("2026-01-01".to_owned()),
+            text: "café & co".to_owned(),
+            category_ids: vec![Uuid::from_u128(1), Uuid::from_u128(2)],
+            ..ExpenseFilter::default()
+        };
+        let options = ListOptions {
+            sort_by: SortBy::Amount,
+            descending: false,
+            ..ListOptions::default()
+        };
+        let query = ExpenseQuery::new(filter, options).cursor(Some("50".to_owned()));
+        let params: HashMap<String, String> = query
+            .to_query_string()
+            .split('&')
+            .map(|param| {
+                let (name, value) = param.split_once('=').unwrap();
+                let value = value
+                    .replace("%20", " ")
+                    .replace("%26", "&")
+                    .replace("%2C", ",")
+                    .replace("%C3%A9", "é");
+                (name.to_owned(), value)
+            })
+            .collect();
+        assert_eq!(ExpenseQuery::from_params(&params), Some(query));
+
+        let mut huge = params.clone();
+        huge.insert("limit".to_owned(), "100000".to_owned());
+        assert_eq!(
+            ExpenseQuery::from_params(&huge).map(|query| query.limit),
+            Some(MAX_PAGE_SIZE)
+        );
+        huge.remove("limit");
+        assert_eq!(ExpenseQuery::from_params(&huge), None);
+    }
+}
@@ -0,0 +1,70 @@
+/// Shorter passwords are refused by the server.
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Strength {
+    TooShort,
+    Weak,
+    Fair,
+    Strong,
+}
+
+impl Strength {
+    /// Registration and password resets need at least a fair password.
+    pub fn acceptable(self) -> bool {
+        self >= Strength::Fair
+    }
+}
+
+/// A rough score: one point per kind of character used, and one more for a long password.
+pub fn strength(password: &str) -> Strength {
+    let length = password.chars().count();
+    if length < MIN_PASSWORD_LENGTH {
+        return Strength::TooShort;
+    }
+    let kinds = [
+        password.chars().any(|c| c.is_lowercase()),
+        password.chars().any(|c| c.is_uppercase()),
+        password.chars().any(|c| c.is_numeric()),
+        password.chars().any(|c| !c.is_alphanumeric()),
+    ];
+    let score = kinds.iter().filter(|used| **used).count() + usize::from(length >= 12);
+    match score {
+        0..=1 => Strength::Weak,
+        2..=3 => Strength::Fair,
+        _ => Strength::Strong,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strength_counts_kinds_of_characters() {
+        assert_eq!(strength("aB3$"), Strength::TooShort);
+        assert_eq!(strength("abcdefgh"), Strength::Weak);
+        assert_eq!(strength("12345678"), Strength::Weak);
+        assert_eq!(strength("abcdefg1"), Strength::Fair);
+        assert_eq!(strength("abcDef1!"), Strength::Strong);
+        // Accented letters count as letters, and length is in characters.
+        assert_eq!(strength("ñandú123"), Strength::Fair);
+        assert_eq!(strength("ñandú"), Strength::TooShort);
+    }
+
+    #[test]
+    fn long_passwords_score_a_point_more() {
+        assert_eq!(strength("abcdefghijk"), Strength::Weak);
+        assert_eq!(strength("abcdefghijkl"), Strength::Fair);
+        assert_eq!(strength("abcdefghijk1"), Strength::Fair);
+        assert_eq!(strength("abcdefghij1!"), Strength::Strong);
+    }
+
+    #[test]
+    fn fair_passwords_are_acceptable() {
+        assert!(!Strength::TooShort.acceptable());
+        assert!(!Strength::Weak.acceptable());
+        assert!(Strength::Fair.acceptable());
+        assert!(Strength::Strong.acceptable());
+    }
+}
@@ -0,0 +1,284 @@
+use crate::{alerts::month_key, household::Household, models::Expense};
+use serde::{Deserialize, Serialize};
+use time::util::days_in_month;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct CategoryTotal {
+    pub category_id: Uuid,
+    pub category_name: String,
+    pub total: i64,
+    pub count: usize,
+    pub average: i64,
+    /// Share of the month's total, from 0 to 1.
+    pub share: f32,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct MonthSummary {
+    /// `YYYY-MM`.
+    pub month: String,
+    pub total: i64,
+    pub count: usize,
+    pub average: i64,
+    /// Largest total first.
+    pub categories: Vec<CategoryTotal>,
+}
+
+fn expense_month(expense: &Expense) -> Option<String> {
+    expense.parsed_date().map(month_key)
+}
+
+/// Months that have expenses, newest first.
+pub fn months(expenses: &[Expense]) -> Vec<String> {
+    let mut months: Vec<String> = expenses.iter().filter_map(expense_month).collect();
+    months.sort_unstable_by(|a, b| b.cmp(a));
+    months.dedup();
+    months
+}
+
+pub fn month_summary(expenses: &[Expense], month: &str) -> MonthSummary {
+    let mut summary = MonthSummary {
+        month: month.to_owned(),
+        ..MonthSummary::default()
+    };
+    for expense in expenses
+        .iter()
+        .filter(|expense| expense_month(expense).as_deref() == Some(month))
+    {
+        summary.total += i64::from(expense.amount);
+        summary.count += 1;
+        let at = match summary
+            .categories
+            .iter()
+            .position(|category| category.category_id == expense.category_id)
+        {
+            Some(at) => at,
+            None => {
+                summary.categories.push(CategoryTotal {
+                    category_id: expense.category_id,
+                    category_name: expense.category_name.clone(),
+                    ..CategoryTotal::default()
+                });
+                summary.categories.len() - 1
+            }
+        };
+        summary.categories[at].total += i64::from(expense.amount);
+        summary.categories[at].count += 1;
+    }
+    if summary.count > 0 {
+        summary.average = summary.total / summary.count as i64;
+    }
+    for category in &mut summary.categories {
+        category.average = category.total / category.count as i64;
+        if summary.total != 0 {
+            category.share = category.total as f32 / summary.total as f32;
+        }
+    }
+    summary.categories.sort_by(|a, b| {
+        b.total
+            .cmp(&a.total)
+            .then_with(|| a.category_name.cmp(&b.category_name))
+    });
+    summary
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct MemberTotal {
+    pub member_id: Uuid,
+    pub email: String,
+    pub total: i64,
+    pub count: usize,
+    /// Share of the month's total, from 0 to 1.
+    pub share: f32,
+}
+
+/// What each member paid in `month`, in the household's member order. Members who paid
+/// nothing are listed with zero.
+pub fn member_totals(expenses: &[Expense], month: &str, household: &Household) -> Vec<MemberTotal> {
+    let mut totals: Vec<MemberTotal> = household
+        .members
+        .iter()
+        .map(|member| MemberTotal {
+            member_id: member.id,
+            email: member.email.clone(),
+            ..MemberTotal::default()
+        })
+        .collect();
+    let mut month_total = 0;
+    for expense in expenses
+        .iter()
+        .filter(|expense| expense_month(expense).as_deref() == Some(month))
+    {
+        let payer = household.payer(expense.paid_by);
+        // Members who left keep their expenses, but no longer get a row.
+        if let Some(total) = totals.iter_mut().find(|total| total.member_id == payer) {
+            total.total += i64::from(expense.amount);
+            total.count += 1;
+            month_total += i64::from(expense.amount);
+        }
+    }
+    if month_total != 0 {
+        for total in &mut totals {
+            total.share = total.total as f32 / month_total as f32;
+        }
+    }
+    totals
+}
+
+/// One summary per month with expenses, oldest first.
+pub fn monthly_summaries(expenses: &[Expense]) -> Vec<MonthSummary> {
+    months(expenses)
+        .iter()
+        .rev()
+        .map(|month| month_summary(expenses, month))
+        .collect()
+}
+
+/// Running total at the end of each day of `month`, one entry per day of the month.
+pub fn daily_cumulative(expenses: &[Expense], month: &str) -> Vec<i64> {
+    let Some(first) = crate::models::parse_date(&format!("{month}-01")) else {
+        return vec![];
+    };
+    let mut days = vec![0i64; usize::from(days_in_month(first.month(), first.year()))];
+    for expense in expenses {
+        if let Some(date) = expense.parsed_date() {
+            if date.year() == first.year() && date.month() == first.month() {
+                days[usize::from(date.day()) - 1] += i64::from(expense.amount);
+            }
+        }
+    }
+    let mut total = 0;
+    for day in &mut days {
+        total += *day;
+        *day = total;
+    }
+    days
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::household::Member;
+
+    const FOOD: Uuid = Uuid::from_u128(1);
+    const RENT: Uuid = Uuid::from_u128(2);
+
+    fn expense(amount: i32, date: &str, category_id: Uuid) -> Expense {
+        let mut expense = Expense::new();
+        expense.amount(amount);
+        expense.date(date.to_owned());
+        expense.category_id = category_id;
+        expense.category_name = if category_id == RENT { "Rent" } else { "Food" }.to_owned();
+        expense
+    }
+
+    #[test]
+    fn months_are_newest_first() {
+        let expenses = [
+            expense(1, "2026-02-10", FOOD),
+            expense(1, "2026-03-01", FOOD),
+            expense(1, "2026-02-20", FOOD),
+        ];
+        assert_eq!(months(&expenses), ["2026-03", "2026-02"]);
+        let summaries = monthly_summaries(&expenses);
+        assert_eq!(summaries[0].month, "2026-02");
+        assert_eq!(summaries[0].count, 2);
+    }
+
+    #[test]
+    fn month_totals_are_split_by_category() {
+        let expenses = [
+            expense(300, "2026-03-01", FOOD),
+            expense(100, "2026-03-15", FOOD),
+            expense(600, "2026-03-31", RENT),
+            expense(5000, "2026-04-01", RENT),
+        ];
+        let summary = month_summary(&expenses, "2026-03");
+        assert_eq!(
+            (summary.total, summary.count, summary.average),
+            (1000, 3, 333)
+        );
+        let categories: Vec<_> = summary
+            .categories
+            .iter()
+            .map(|category| {
+                (
+                    category.category_name.as_str(),
+                    category.total,
+                    category.count,
+                    category.average,
+                    category.share,
+                )
+            })
+            .collect();
+        assert_eq!(
+            categories,
+            [("Rent", 600, 1, 600, 0.6), ("Food", 400, 2, 200, 0.4)]
+        );
+        assert_eq!(
+            month_summary(&expenses, "2026-05"),
+            MonthSummary {
+                month: "2026-05".to_owned(),
+                ..MonthSummary::default()
+            }
+        );
+    }
+
+    #[test]
+    fn unattributed_expenses_count_for_the_owner() {
+        let (owner, member, former) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let household = Household {
+            owner_id: owner,
+            members: vec![
+                Member {
+                    id: owner,
+                    email: "owner@example.com".to_owned(),
+                },
+                Member {
+                    id: member,
+                    email: "member@example.com".to_owned(),
+                },
+            ],
+            ..Household::default()
+        };
+        let paid = |amount, paid_by| {
+            let mut expense = expense(amount, "2026-03-10", FOOD);
+            expense.paid_by = paid_by;
+            expense
+        };
+        let expenses = [
+            paid(100, None),
+            paid(200, Some(owner)),
+            paid(700, Some(member)),
+            // Members who left are not counted.
+            paid(1000, Some(former)),
+        ];
+        let totals: Vec<_> = member_totals(&expenses, "2026-03", &household)
+            .into_iter()
+            .map(|total| (total.email, total.total, total.count, total.share))
+            .collect();
+        assert_eq!(
+            totals,
+            [
+                ("owner@example.com".to_owned(), 300, 2, 0.3),
+                ("member@example.com".to_owned(), 700, 1, 0.7),
+            ]
+        );
+    }
+
+    #[test]
+    fn cumulative_totals_cover_every_day() {
+        let expenses = [
+            expense(100, "2026-02-01", FOOD),
+            expense(50, "2026-02-03", FOOD),
+            expense(25, "2026-02-28", FOOD),
+            expense(999, "2026-03-01", FOOD),
+        ];
+        let days = daily_cumulative(&expenses, "2026-02");
+        assert_eq!(days.len(), 28);
+        assert_eq!(&days[..4], [100, 100, 150, 150]);
+        assert_eq!(days[27], 175);
+        assert!(daily_cumulative(&expenses, "not a month").is_empty());
+    }
+}
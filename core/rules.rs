@@ -0,0 +1,264 @@
+use crate::error::AppError;
+use crate::models::Expense;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Condition {
+    DescriptionContains { text: String },
+    DescriptionRegex { pattern: Pattern },
+    AmountRange { min: Option<i32>, max: Option<i32> },
+    Account { account: String },
+}
+
+/// A case-insensitive description pattern, compiled once when the rule is built or
+/// loaded rather than for every expense it is checked against. Saved as the plain
+/// pattern text.
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    source: String,
+    compiled: Result<Regex, String>,
+}
+
+impl Pattern {
+    /// Invalid patterns are kept so they can be shown and fixed; they match nothing and
+    /// `Rule::validate` rejects them.
+    pub fn new(source: String) -> Self {
+        let compiled = RegexBuilder::new(&source)
+            .case_insensitive(true)
+            .build()
+            .map_err(|e| e.to_string());
+        Pattern { source, compiled }
+    }
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+    pub fn error(&self) -> Option<&str> {
+        self.compiled.as_ref().err().map(String::as_str)
+    }
+    pub fn is_match(&self, text: &str) -> bool {
+        self.compiled
+            .as_ref()
+            .is_ok_and(|regex| regex.is_match(text))
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl Serialize for Pattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Pattern::new)
+    }
+}
+
+impl Condition {
+    pub fn matches(&self, expense: &Expense) -> bool {
+        let description = expense.description.as_deref().unwrap_or_default();
+        match self {
+            Condition::DescriptionContains { text } => {
+                description.to_lowercase().contains(&text.to_lowercase())
+            }
+            Condition::DescriptionRegex { pattern } => pattern.is_match(description),
+            Condition::AmountRange { min, max } => {
+                min.is_none_or(|min| expense.amount >= min)
+                    && max.is_none_or(|max| expense.amount <= max)
+            }
+            Condition::Account { account } => expense
+                .account
+                .as_deref()
+                .is_some_and(|current| current.eq_ignore_ascii_case(account)),
+        }
+    }
+}
+
+fn condition_error(condition: &Condition) -> Option<&str> {
+    match condition {
+        Condition::DescriptionRegex { pattern } => pattern.error(),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleAction {
+    #[default]
+    Suggest,
+    Assign,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Rule {
+    pub id: Uuid,
+    pub name: String,
+    pub conditions: Vec<Condition>,
+    pub category_id: Uuid,
+    pub category_name: String,
+    pub action: RuleAction,
+}
+
+impl Rule {
+    pub fn new() -> Self {
+        Rule {
+            id: Uuid::new_v4(),
+            ..Rule::default()
+        }
+    }
+    pub fn name(self, name: String) -> Self {
+        Rule { name, ..self }
+    }
+    pub fn category(self, category_id: Uuid, category_name: String) -> Self {
+        Rule {
+            category_id,
+            category_name,
+            ..self
+        }
+    }
+    pub fn action(self, action: RuleAction) -> Self {
+        Rule { action, ..self }
+    }
+    pub fn condition(mut self, condition: Condition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), AppError<'static>> {
+        if self.conditions.is_empty() {
+            return Err(AppError::Invalid("a rule needs at least one condition"));
+        }
+        if self.category_id == Uuid::default() {
+            return Err(AppError::Invalid("a rule needs a category"));
+        }
+        if let Some(error) = self.pattern_error() {
+            return Err(AppError::HardError(error.to_owned()));
+        }
+        Ok(())
+    }
+
+    /// Why a description pattern does not compile, if one does not.
+    pub fn pattern_error(&self) -> Option<&str> {
+        self.conditions.iter().find_map(condition_error)
+    }
+
+    /// A rule matches when every one of its conditions does; a rule without conditions
+    /// never matches.
+    pub fn matches(&self, expense: &Expense) -> bool {
+        !self.conditions.is_empty()
+            && self
+                .conditions
+                .iter()
+                .all(|condition| condition.matches(expense))
+    }
+
+    /// The existing expenses this rule would have categorized.
+    pub fn preview<'a>(&self, expenses: &'a [Expense]) -> Vec<&'a Expense> {
+        expenses
+            .iter()
+            .filter(|expense| self.matches(expense))
+            .collect()
+    }
+}
+
+/// Rules are evaluated in order and the first match wins.
+pub fn first_match<'a>(rules: &'a [Rule], expense: &Expense) -> Option<&'a Rule> {
+    rules.iter().find(|rule| rule.matches(expense))
+}
+
+/// Sets the category from the first matching rule when that rule is allowed to assign.
+pub fn assign(rules: &[Rule], expense: &mut Expense) -> bool {
+    match first_match(rules, expense) {
+        Some(rule) if rule.action == RuleAction::Assign => {
+            expense.category(rule.category_id, rule.category_name.clone());
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expense(description: &str, amount: i32) -> Expense {
+        let mut expense = Expense::new();
+        expense.description(Some(description.to_owned()));
+        expense.amount(amount);
+        expense
+    }
+
+    fn regex_rule(pattern: &str) -> Rule {
+        Rule::new()
+            .category(Uuid::from_u128(1), "Food".to_owned())
+            .condition(Condition::DescriptionRegex {
+                pattern: Pattern::new(pattern.to_owned()),
+            })
+    }
+
+    #[test]
+    fn regex_conditions_ignore_case() {
+        let rule = regex_rule("^super(market)?\\b");
+        assert!(rule.matches(&expense("SUPERMARKET downtown", 10)));
+        assert!(!rule.matches(&expense("the supermarket", 10)));
+    }
+
+    #[test]
+    fn invalid_patterns_are_rejected_and_never_match() {
+        let rule = regex_rule("(unclosed");
+        assert!(rule.pattern_error().is_some());
+        assert!(rule.validate().is_err());
+        assert!(!rule.matches(&expense("(unclosed", 10)));
+        assert!(regex_rule("ok").validate().is_ok());
+    }
+
+    #[test]
+    fn patterns_are_saved_as_text() {
+        let condition = Condition::DescriptionRegex {
+            pattern: Pattern::new("caf[eé]".to_owned()),
+        };
+        let json = serde_json::to_value(&condition).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "kind": "description_regex", "pattern": "caf[eé]" })
+        );
+        let loaded: Condition = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded, condition);
+        assert!(loaded.matches(&expense("Café", 1)));
+    }
+
+    #[test]
+    fn first_matching_rule_assigns() {
+        let suggest = Rule::new()
+            .category(Uuid::from_u128(2), "Maybe".to_owned())
+            .condition(Condition::AmountRange {
+                min: Some(100),
+                max: None,
+            });
+        let bakery = regex_rule("bakery").action(RuleAction::Assign);
+        let rules = vec![suggest, bakery];
+
+        let mut bread = expense("Bakery", 5);
+        assert!(assign(&rules, &mut bread));
+        assert_eq!(bread.category_name, "Food");
+
+        let mut big = expense("Bakery", 500);
+        assert!(!assign(&rules, &mut big));
+        assert_eq!(big.category_name, "");
+    }
+}
@@ -0,0 +1,198 @@
+use crate::models::Expense;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortBy {
+    #[default]
+    Date,
+    Amount,
+    Category,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    #[default]
+    None,
+    Day,
+    Month,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ListOptions {
+    pub sort_by: SortBy,
+    pub descending: bool,
+    pub group_by: GroupBy,
+}
+impl Default for ListOptions {
+    fn default() -> Self {
+        ListOptions {
+            sort_by: SortBy::Date,
+            descending: true,
+            group_by: GroupBy::None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ExpenseGroup {
+    /// `YYYY-MM-DD` or `YYYY-MM`, empty when not grouping or for undated expenses.
+    pub key: String,
+    pub subtotal: i64,
+    pub expenses: Vec<Expense>,
+}
+
+impl ListOptions {
+    pub fn sort(&self, expenses: &mut [Expense]) {
+        expenses.sort_by(|a, b| {
+            let order = match self.sort_by {
+                SortBy::Date => a.date.cmp(&b.date),
+                SortBy::Amount => a.amount.cmp(&b.amount),
+                SortBy::Category => a
+                    .category_name
+                    .to_lowercase()
+                    .cmp(&b.category_name.to_lowercase()),
+            };
+            let order = if self.descending {
+                order.reverse()
+            } else {
+                order
+            };
+            // Ties keep a predictable order, newest first, whichever the direction.
+            order.then_with(|| b.date.cmp(&a.date))
+        });
+    }
+
+    fn group_key(&self, expense: &Expense) -> String {
+        let date = expense.date.as_deref().unwrap_or_default();
+        match self.group_by {
+            GroupBy::None => String::new(),
+            GroupBy::Day => date.get(..10).unwrap_or(date).to_owned(),
+            GroupBy::Month => date.get(..7).unwrap_or(date).to_owned(),
+        }
+    }
+
+    /// Sorts the expenses and splits them into groups with subtotals. Groups follow the
+    /// date direction when sorting by date and are newest first otherwise.
+    pub fn group(&self, mut expenses: Vec<Expense>) -> Vec<ExpenseGroup> {
+        self.sort(&mut expenses);
+        let mut groups: Vec<ExpenseGroup> = vec![];
+        for expense in expenses {
+            let key = self.group_key(&expense);
+            let at = match groups.iter().position(|group| group.key == key) {
+                Some(at) => at,
+                None => {
+                    groups.push(ExpenseGroup {
+                        key,
+                        ..ExpenseGroup::default()
+                    });
+                    groups.len() - 1
+                }
+            };
+            groups[at].subtotal += i64::from(expense.amount);
+            groups[at].expenses.push(expense);
+        }
+        let ascending = self.sort_by == SortBy::Date && !self.descending;
+        groups.sort_by(|a, b| {
+            if ascending {
+                a.key.cmp(&b.key)
+            } else {
+                b.key.cmp(&a.key)
+            }
+        });
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expense(amount: i32, date: &str, category: &str) -> Expense {
+        let mut expense = Expense::new();
+        expense.amount(amount);
+        expense.date(date.to_owned());
+        expense.category_name = category.to_owned();
+        expense
+    }
+
+    fn dates(expenses: &[Expense]) -> Vec<&str> {
+        expenses
+            .iter()
+            .map(|expense| expense.date.as_deref().unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn ties_are_newest_first_in_both_directions() {
+        let expenses = vec![
+            expense(500, "2026-03-01", "Food"),
+            expense(900, "2026-03-02", "Rent"),
+            expense(500, "2026-03-03", "food"),
+        ];
+        for descending in [true, false] {
+            let options = ListOptions {
+                sort_by: SortBy::Amount,
+                descending,
+                ..ListOptions::default()
+            };
+            let mut sorted = expenses.clone();
+            options.sort(&mut sorted);
+            let expected = if descending {
+                ["2026-03-02", "2026-03-03", "2026-03-01"]
+            } else {
+                ["2026-03-03", "2026-03-01", "2026-03-02"]
+            };
+            assert_eq!(dates(&sorted), expected);
+        }
+
+        let by_category = ListOptions {
+            sort_by: SortBy::Category,
+            descending: true,
+            ..ListOptions::default()
+        };
+        let mut sorted = expenses;
+        by_category.sort(&mut sorted);
+        assert_eq!(dates(&sorted), ["2026-03-02", "2026-03-03", "2026-03-01"]);
+    }
+
+    #[test]
+    fn groups_follow_the_date_direction() {
+        let expenses = vec![
+            expense(100, "2026-02-10", "Food"),
+            expense(200, "2026-03-05", "Food"),
+            expense(300, "2026-02-20", "Rent"),
+        ];
+        let by_month = ListOptions {
+            group_by: GroupBy::Month,
+            ..ListOptions::default()
+        };
+        let groups = by_month.group(expenses.clone());
+        let keys: Vec<&str> = groups.iter().map(|group| group.key.as_str()).collect();
+        assert_eq!(keys, ["2026-03", "2026-02"]);
+        assert_eq!(groups[1].subtotal, 400);
+        assert_eq!(dates(&groups[1].expenses), ["2026-02-20", "2026-02-10"]);
+
+        let oldest_first = ListOptions {
+            descending: false,
+            ..by_month
+        };
+        let keys: Vec<String> = oldest_first
+            .group(expenses.clone())
+            .into_iter()
+            .map(|group| group.key)
+            .collect();
+        assert_eq!(keys, ["2026-02", "2026-03"]);
+
+        // Other orders keep the groups newest first and sort inside them.
+        let by_amount = ListOptions {
+            sort_by: SortBy::Amount,
+            descending: false,
+            group_by: GroupBy::Month,
+        };
+        let groups = by_amount.group(expenses);
+        assert_eq!(groups[0].key, "2026-03");
+        assert_eq!(dates(&groups[1].expenses), ["2026-02-10", "2026-02-20"]);
+    }
+}
@@ -0,0 +1,58 @@
+use std::{sync::Mutex, time::Duration};
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+use crate::{fixtures::Rng, routes::failure};
+
+/// Slowness and failures added to every request, to exercise loading and error states.
+pub struct Faults {
+    pub latency: Duration,
+    pub jitter: Duration,
+    pub error_rate: f64,
+    pub fail_paths: Vec<String>,
+    pub rng: Mutex<Rng>,
+}
+
+impl Faults {
+    fn delay(&self) -> Duration {
+        let jitter = self.jitter.as_millis() as u64;
+        let extra = if jitter == 0 {
+            0
+        } else {
+            self.rng.lock().unwrap().below(jitter + 1)
+        };
+        self.latency + Duration::from_millis(extra)
+    }
+
+    fn should_fail(&self, path: &str) -> bool {
+        if self
+            .fail_paths
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+        {
+            return true;
+        }
+        self.error_rate > 0.0
+            && (self.rng.lock().unwrap().below(10_000) as f64) < self.error_rate * 10_000.0
+    }
+}
+
+pub async fn inject(State(faults): State<Arc<Faults>>, request: Request, next: Next) -> Response {
+    let delay = faults.delay();
+    if !delay.is_zero() {
+        tokio::time::sleep(delay).await;
+    }
+    if faults.should_fail(request.uri().path()) {
+        return failure(
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            "Injected failure",
+        )
+        .into_response();
+    }
+    next.run(request).await
+}
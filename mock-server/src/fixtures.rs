@@ -0,0 +1,94 @@
+use core_api::models::{Category, Expense};
+use time::{Date, Duration};
+use uuid::Uuid;
+
+use crate::store::Db;
+
+const CATEGORIES: [(&str, i32, i32, &[&str]); 5] = [
+    (
+        "Super",
+        2_000,
+        45_000,
+        &["Coto", "Día", "Carrefour", "Verdulería"],
+    ),
+    (
+        "Transporte",
+        500,
+        12_000,
+        &["SUBE", "Nafta", "Uber", "Peaje"],
+    ),
+    (
+        "Servicios",
+        8_000,
+        60_000,
+        &["Luz", "Gas", "Internet", "Celular"],
+    ),
+    (
+        "Salidas",
+        3_000,
+        35_000,
+        &["Cena", "Cine", "Bar", "Delivery"],
+    ),
+    ("Salud", 4_000, 50_000, &["Farmacia", "Consulta", "Prepaga"]),
+];
+
+/// Small xorshift generator, so the same seed always gives the same fixtures.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed.max(1))
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound.max(1)
+    }
+
+    pub fn uuid(&mut self) -> Uuid {
+        Uuid::from_u64_pair(self.next(), self.next())
+    }
+}
+
+/// Categories and `per_month` expenses a month for the `months` months up to `today`.
+pub fn seed(seed: u64, months: u32, per_month: u32, today: Date) -> Db {
+    let mut rng = Rng::new(seed);
+    let categories: Vec<Category> = CATEGORIES
+        .iter()
+        .map(|(name, ..)| {
+            let mut category = Category::new().name(name.to_string());
+            category.id = rng.uuid();
+            category
+        })
+        .collect();
+
+    let days = i64::from(months) * 30;
+    let expenses = (0..months * per_month)
+        .map(|_| {
+            let at = rng.below(CATEGORIES.len() as u64) as usize;
+            let (_, min, max, descriptions) = CATEGORIES[at];
+            let amount = min + rng.below((max - min) as u64) as i32;
+            let description = descriptions[rng.below(descriptions.len() as u64) as usize];
+            let date = today - Duration::days(rng.below(days as u64) as i64);
+            Expense {
+                id: rng.uuid(),
+                category_id: categories[at].id,
+                category_name: categories[at].name.clone(),
+                description: Some(description.to_owned()),
+                amount: amount / 10 * 10,
+                date: Some(date.to_string()),
+                ..Expense::default()
+            }
+        })
+        .collect();
+    Db {
+        categories,
+        expenses,
+    }
+}
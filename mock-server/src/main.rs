@@ -0,0 +1,105 @@
+mod faults;
+mod fixtures;
+mod routes;
+mod store;
+
+use std::{net::SocketAddr, path::PathBuf, sync::Mutex, time::Duration};
+
+use clap::Parser;
+use time::{Date, OffsetDateTime};
+
+use crate::{
+    faults::Faults,
+    fixtures::Rng,
+    routes::AppState,
+    store::{Db, MockStore},
+};
+
+/// Local stand-in for the Finance Home API. Point the app's server URL at it.
+#[derive(Parser)]
+#[command(name = "mock-server", version)]
+struct Args {
+    #[arg(long, default_value = "127.0.0.1:8787")]
+    bind: SocketAddr,
+    /// Keep the data in this JSON file instead of in memory. Seeded when missing.
+    #[arg(long)]
+    data: Option<PathBuf>,
+    /// Seed for the generated fixtures; the same seed gives the same data.
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+    /// Months of fixture expenses to generate, up to today.
+    #[arg(long, default_value_t = 3)]
+    months: u32,
+    /// Fixture expenses generated for each month.
+    #[arg(long, default_value_t = 25)]
+    per_month: u32,
+    /// Start without categories or expenses.
+    #[arg(long)]
+    empty: bool,
+    #[arg(long, default_value = "demo@example.com")]
+    email: String,
+    #[arg(long, default_value = "demo")]
+    password: String,
+    /// Token handed out on login and required on every other route.
+    #[arg(long, default_value = "mock-token")]
+    token: String,
+    /// Delay added to every response, in milliseconds.
+    #[arg(long, default_value_t = 0)]
+    latency_ms: u64,
+    /// Up to this many extra random milliseconds on top of the latency.
+    #[arg(long, default_value_t = 0)]
+    jitter_ms: u64,
+    /// Share of requests, from 0 to 1, that fail with a 500.
+    #[arg(long, default_value_t = 0.0)]
+    error_rate: f64,
+    /// Requests whose path starts with this always fail. Can be repeated.
+    #[arg(long = "fail")]
+    fail_paths: Vec<String>,
+}
+
+// The local date is read before the runtime starts its threads, after which the local
+// offset can no longer be looked up.
+fn main() -> std::io::Result<()> {
+    let today = OffsetDateTime::now_local()
+        .unwrap_or_else(|_| OffsetDateTime::now_utc())
+        .date();
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?
+        .block_on(serve(Args::parse(), today))
+}
+
+async fn serve(args: Args, today: Date) -> std::io::Result<()> {
+    let seed = || {
+        if args.empty {
+            Db::default()
+        } else {
+            fixtures::seed(args.seed, args.months, args.per_month, today)
+        }
+    };
+    let store = match &args.data {
+        Some(path) => MockStore::file(path.clone(), seed)?,
+        None => MockStore::in_memory(seed()),
+    };
+    let state = AppState {
+        store,
+        email: args.email.clone(),
+        password: args.password,
+        token: args.token,
+    };
+    let faults = Faults {
+        latency: Duration::from_millis(args.latency_ms),
+        jitter: Duration::from_millis(args.jitter_ms),
+        error_rate: args.error_rate.clamp(0.0, 1.0),
+        fail_paths: args.fail_paths,
+        rng: Mutex::new(Rng::new(args.seed)),
+    };
+
+    let listener = tokio::net::TcpListener::bind(args.bind).await?;
+    println!(
+        "Mock API on http://{}, log in as {}",
+        listener.local_addr()?,
+        args.email
+    );
+    axum::serve(listener, routes::router(state, faults)).await
+}
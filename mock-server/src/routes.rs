@@ -0,0 +1,162 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    middleware,
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+    Json, Router,
+};
+use core_api::{
+    models::{ApiResponse, Category, Expense, LoginBody, LoginResponse, NewExpense},
+    paging::{page, ExpenseQuery},
+};
+use tower_http::cors::CorsLayer;
+use uuid::Uuid;
+
+use crate::{
+    faults::{self, Faults},
+    store::MockStore,
+};
+
+pub struct AppState {
+    pub store: MockStore,
+    pub email: String,
+    pub password: String,
+    pub token: String,
+}
+
+type Shared = Arc<AppState>;
+type Failure = (StatusCode, Json<ApiResponse<()>>);
+type Reply<T> = Result<Json<ApiResponse<T>>, Failure>;
+
+pub fn failure(status: StatusCode, message: &str) -> Failure {
+    (
+        status,
+        Json(ApiResponse::failed((), vec![message.to_owned()])),
+    )
+}
+
+fn ok<T: serde::Serialize + Clone>(data: T) -> Reply<T> {
+    Ok(Json(ApiResponse::new(data)))
+}
+
+fn authorize(state: &AppState, headers: &HeaderMap) -> Result<(), Failure> {
+    let token = headers
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match token {
+        Some(token) if token == state.token => Ok(()),
+        _ => Err(failure(
+            StatusCode::UNAUTHORIZED,
+            "Invalid or missing token",
+        )),
+    }
+}
+
+fn storage_failure(e: std::io::Error) -> Failure {
+    failure(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string())
+}
+
+async fn login(State(state): State<Shared>, Json(body): Json<LoginBody>) -> Reply<LoginResponse> {
+    if !body.email.eq_ignore_ascii_case(&state.email) || body.password != state.password {
+        return Err(failure(StatusCode::UNAUTHORIZED, "Wrong email or password"));
+    }
+    ok(LoginResponse {
+        token: state.token.clone(),
+    })
+}
+
+async fn list_categories(State(state): State<Shared>, headers: HeaderMap) -> Reply<Vec<Category>> {
+    authorize(&state, &headers)?;
+    ok(state.store.read(|db| db.categories.clone()))
+}
+
+// Pages when asked for a `limit`, otherwise returns every expense like the hosted API.
+async fn list_expenses(
+    State(state): State<Shared>,
+    headers: HeaderMap,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Response, Failure> {
+    authorize(&state, &headers)?;
+    let mut expenses = state.store.read(|db| db.expenses.clone());
+    if let Some(query) = ExpenseQuery::from_params(&params) {
+        // There are no households here.
+        return Ok(Json(ApiResponse::new(page(&expenses, &query, None))).into_response());
+    }
+    expenses.sort_by(|a, b| b.date.cmp(&a.date));
+    Ok(Json(ApiResponse::new(expenses)).into_response())
+}
+
+async fn create_expense(
+    State(state): State<Shared>,
+    headers: HeaderMap,
+    Json(body): Json<NewExpense>,
+) -> Reply<Expense> {
+    authorize(&state, &headers)?;
+    if core_api::models::parse_date(&body.date).is_none() {
+        return Err(failure(StatusCode::BAD_REQUEST, "Invalid date"));
+    }
+    let created = state
+        .store
+        .update(|db| {
+            let category = db
+                .categories
+                .iter()
+                .find(|category| category.id == body.category_id)?;
+            let expense = Expense {
+                id: Uuid::new_v4(),
+                category_id: category.id,
+                category_name: category.name.clone(),
+                description: Some(body.description).filter(|text| !text.is_empty()),
+                amount: body.amount,
+                date: Some(body.date),
+                external_id: body.external_id,
+                account: body.account,
+                paid_by: body.paid_by,
+            };
+            db.expenses.push(expense.clone());
+            Some(expense)
+        })
+        .map_err(storage_failure)?;
+    match created {
+        Some(expense) => ok(expense),
+        None => Err(failure(StatusCode::BAD_REQUEST, "Unknown category")),
+    }
+}
+
+async fn delete_expense(
+    State(state): State<Shared>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Reply<bool> {
+    authorize(&state, &headers)?;
+    let removed = state
+        .store
+        .update(|db| {
+            let before = db.expenses.len();
+            db.expenses.retain(|expense| expense.id != id);
+            db.expenses.len() != before
+        })
+        .map_err(storage_failure)?;
+    if !removed {
+        return Err(failure(StatusCode::NOT_FOUND, "Expense not found"));
+    }
+    ok(true)
+}
+
+pub fn router(state: AppState, faults: Faults) -> Router {
+    Router::new()
+        .route("/login", post(login))
+        .route("/categories", get(list_categories))
+        .route("/expenses", get(list_expenses).post(create_expense))
+        .route("/expenses/{id}", delete(delete_expense))
+        .with_state(Arc::new(state))
+        .layer(middleware::from_fn_with_state(
+            Arc::new(faults),
+            faults::inject,
+        ))
+        .layer(CorsLayer::permissive())
+}
@@ -0,0 +1,56 @@
+use std::{fs, io, path::PathBuf, sync::Mutex};
+
+use core_api::models::{Category, Expense};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Db {
+    pub categories: Vec<Category>,
+    pub expenses: Vec<Expense>,
+}
+
+/// The mock's data, kept in memory and, when given a path, saved after every change.
+pub struct MockStore {
+    path: Option<PathBuf>,
+    db: Mutex<Db>,
+}
+
+impl MockStore {
+    pub fn in_memory(db: Db) -> Self {
+        MockStore {
+            path: None,
+            db: Mutex::new(db),
+        }
+    }
+
+    /// Opens the file at `path`, creating it from `seed` when it does not exist yet.
+    pub fn file(path: PathBuf, seed: impl FnOnce() -> Db) -> io::Result<Self> {
+        let db = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                let db = seed();
+                fs::write(&path, serde_json::to_vec_pretty(&db)?)?;
+                db
+            }
+            Err(e) => return Err(e),
+        };
+        Ok(MockStore {
+            path: Some(path),
+            db: Mutex::new(db),
+        })
+    }
+
+    pub fn read<T>(&self, query: impl FnOnce(&Db) -> T) -> T {
+        query(&self.db.lock().unwrap())
+    }
+
+    pub fn update<T>(&self, change: impl FnOnce(&mut Db) -> T) -> io::Result<T> {
+        let mut db = self.db.lock().unwrap();
+        let result = change(&mut db);
+        if let Some(path) = &self.path {
+            fs::write(path, serde_json::to_vec_pretty(&*db)?)?;
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,599 @@
+use std::sync::OnceLock;
+
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use axum::{extract::FromRequestParts, extract::State, http::request::Parts, Extension, Json};
+use core_api::{
+    models::{ApiResponse, LoginBody, LoginResponse, PasswordResetBody, PasswordResetRequest},
+    passwords::{strength, Strength, MIN_PASSWORD_LENGTH},
+};
+use rusqlite::{params, OptionalExtension};
+use sha2::{Digest, Sha256};
+use time::{format_description::well_known::Rfc3339, Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::{
+    db::{now, uuid_at, Db},
+    error::ServerError,
+};
+
+/// Tokens are refused this long after login.
+const SESSION_DAYS: i64 = 30;
+const RESET_MINUTES: i64 = 30;
+// Six digits are easy to type but easy to guess, so only a few tries are allowed per
+// window, however many codes are requested in it.
+const RESET_ATTEMPTS: i64 = 5;
+const RESET_WINDOW_MINUTES: i64 = 60;
+/// New codes are issued at most this often for an account.
+const RESET_INTERVAL_SECONDS: i64 = 60;
+const DEFAULT_CATEGORIES: [&str; 5] = ["Super", "Transporte", "Servicios", "Salidas", "Salud"];
+
+fn hash_blocking(password: &str) -> Result<String, ServerError> {
+    // A v4 uuid is 16 random bytes, the recommended salt length.
+    let salt = SaltString::encode_b64(Uuid::new_v4().as_bytes())
+        .map_err(|e| ServerError::Internal(e.to_string()))?;
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| ServerError::Internal(e.to_string()))
+}
+
+// Hashing is deliberately slow, so it runs off the async workers.
+async fn hash_password(password: String) -> Result<String, ServerError> {
+    tokio::task::spawn_blocking(move || hash_blocking(&password))
+        .await
+        .map_err(|e| ServerError::Internal(e.to_string()))?
+}
+
+/// Checked against when the email has no account, so that a login takes as long
+/// whether or not the account exists.
+async fn dummy_hash() -> Result<String, ServerError> {
+    static DUMMY_HASH: OnceLock<String> = OnceLock::new();
+    if let Some(hash) = DUMMY_HASH.get() {
+        return Ok(hash.clone());
+    }
+    let hash = hash_password(Uuid::new_v4().to_string()).await?;
+    Ok(DUMMY_HASH.get_or_init(|| hash).clone())
+}
+
+async fn verify_password(hash: String, password: String) -> bool {
+    tokio::task::spawn_blocking(move || {
+        PasswordHash::new(&hash).is_ok_and(|hash| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &hash)
+                .is_ok()
+        })
+    })
+    .await
+    .unwrap_or(false)
+}
+
+fn check_password(password: &str) -> Result<(), ServerError> {
+    let strength = strength(password);
+    if strength == Strength::TooShort {
+        return Err(ServerError::BadRequest(format!(
+            "Password must have at least {MIN_PASSWORD_LENGTH} characters"
+        )));
+    }
+    if !strength.acceptable() {
+        return Err(ServerError::BadRequest(
+            "Password is too weak, mix letters, digits and symbols".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Only a hash of each token is stored, so a leaked database cannot be used to log in.
+pub fn token_hash(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// The time `offset` from now, formatted like [`now`].
+fn from_now(offset: Duration) -> Result<String, ServerError> {
+    (OffsetDateTime::now_utc() + offset)
+        .format(&Rfc3339)
+        .map_err(|e| ServerError::Internal(e.to_string()))
+}
+
+/// Sessions created before this have expired.
+fn session_cutoff() -> Result<String, ServerError> {
+    from_now(-Duration::days(SESSION_DAYS))
+}
+
+fn issue_token(conn: &rusqlite::Connection, user_id: Uuid) -> Result<String, ServerError> {
+    conn.execute(
+        "DELETE FROM sessions WHERE user_id = ?1 AND created_at <= ?2",
+        params![user_id.to_string(), session_cutoff()?],
+    )?;
+    let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    conn.execute(
+        "INSERT INTO sessions (token_hash, user_id, created_at) VALUES (?1, ?2, ?3)",
+        params![token_hash(&token), user_id.to_string(), now()],
+    )?;
+    Ok(token)
+}
+
+/// The user owning the bearer token of the request.
+pub struct AuthUser {
+    pub id: Uuid,
+    /// Whose expenses and categories the user works on: the household owner's while in
+    /// a household, otherwise their own. The `user_id` columns of those tables hold it.
+    pub ledger_id: Uuid,
+    token_hash: String,
+}
+
+impl FromRequestParts<Db> for AuthUser {
+    type Rejection = ServerError;
+
+    async fn from_request_parts(parts: &mut Parts, db: &Db) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(ServerError::Unauthorized)?;
+        let hash = token_hash(token);
+        let oldest = session_cutoff()?;
+        db.run(move |conn| {
+            let (id, ledger_id) = conn
+                .query_row(
+                    "SELECT s.user_id, COALESCE(h.owner_id, s.user_id)
+                     FROM sessions s
+                     LEFT JOIN household_members m ON m.user_id = s.user_id
+                     LEFT JOIN households h ON h.id = m.household_id
+                     WHERE s.token_hash = ?1 AND s.created_at > ?2",
+                    [&hash, &oldest],
+                    |row| Ok((uuid_at(row, 0)?, uuid_at(row, 1)?)),
+                )
+                .optional()?
+                .ok_or(ServerError::Unauthorized)?;
+            Ok(AuthUser {
+                id,
+                ledger_id,
+                token_hash: hash,
+            })
+        })
+        .await
+    }
+}
+
+pub async fn register(
+    State(db): State<Db>,
+    Json(body): Json<LoginBody>,
+) -> Result<Json<ApiResponse<LoginResponse>>, ServerError> {
+    let email = body.email.trim().to_owned();
+    if !email.contains('@') {
+        return Err(ServerError::BadRequest("Invalid email".to_owned()));
+    }
+    check_password(&body.password)?;
+    let password_hash = hash_password(body.password).await?;
+    let token = db
+        .run(move |conn| {
+            let tx = conn.transaction()?;
+            let taken: bool = tx.query_row(
+                "SELECT EXISTS (SELECT 1 FROM users WHERE email = ?1)",
+                [&email],
+                |row| row.get(0),
+            )?;
+            if taken {
+                return Err(ServerError::Conflict(
+                    "An account with that email already exists".to_owned(),
+                ));
+            }
+            let user_id = Uuid::new_v4();
+            tx.execute(
+                "INSERT INTO users (id, email, password_hash, created_at) VALUES (?1, ?2, ?3, ?4)",
+                params![user_id.to_string(), email, password_hash, now()],
+            )?;
+            for name in DEFAULT_CATEGORIES {
+                tx.execute(
+                    "INSERT INTO categories (id, user_id, name) VALUES (?1, ?2, ?3)",
+                    params![Uuid::new_v4().to_string(), user_id.to_string(), name],
+                )?;
+            }
+            let token = issue_token(&tx, user_id)?;
+            tx.commit()?;
+            Ok(token)
+        })
+        .await?;
+    Ok(Json(ApiResponse::new(LoginResponse { token })))
+}
+
+pub async fn login(
+    State(db): State<Db>,
+    Json(body): Json<LoginBody>,
+) -> Result<Json<ApiResponse<LoginResponse>>, ServerError> {
+    let email = body.email.trim().to_owned();
+    let user = db
+        .run(move |conn| {
+            Ok(conn
+                .query_row(
+                    "SELECT id, password_hash FROM users WHERE email = ?1",
+                    [&email],
+                    |row| Ok((uuid_at(row, 0)?, row.get::<_, String>(1)?)),
+                )
+                .optional()?)
+        })
+        .await?;
+    let (user_id, hash) = match user {
+        Some((user_id, hash)) => (Some(user_id), hash),
+        None => (None, dummy_hash().await?),
+    };
+    let verified = verify_password(hash, body.password).await;
+    let Some(user_id) = user_id.filter(|_| verified) else {
+        return Err(ServerError::WrongCredentials);
+    };
+    let token = db.run(move |conn| issue_token(conn, user_id)).await?;
+    Ok(Json(ApiResponse::new(LoginResponse { token })))
+}
+
+pub async fn logout(
+    State(db): State<Db>,
+    user: AuthUser,
+) -> Result<Json<ApiResponse<bool>>, ServerError> {
+    db.run(move |conn| {
+        conn.execute(
+            "DELETE FROM sessions WHERE token_hash = ?1",
+            [&user.token_hash],
+        )?;
+        Ok(())
+    })
+    .await?;
+    Ok(Json(ApiResponse::new(true)))
+}
+
+/// Whether reset codes are written to the server log, where the administrator can pass
+/// them on. Off unless the server is started with `--log-reset-codes`.
+#[derive(Clone, Copy)]
+pub struct LogResetCodes(pub bool);
+
+/// Issues a new code for the account with `email`, if there is one. Within a window the
+/// failed attempts carry over to the new code, and codes are not issued more often than
+/// every `RESET_INTERVAL_SECONDS`; `None` then as well.
+async fn issue_reset_code(db: &Db, email: String) -> Result<Option<String>, ServerError> {
+    let code = format!("{:06}", Uuid::new_v4().as_u128() % 1_000_000);
+    let code_hash = token_hash(&code);
+    let expires_at = from_now(Duration::minutes(RESET_MINUTES))?;
+    let window_start = from_now(-Duration::minutes(RESET_WINDOW_MINUTES))?;
+    let last_allowed = from_now(-Duration::seconds(RESET_INTERVAL_SECONDS))?;
+    let issued = db
+        .run(move |conn| {
+            let tx = conn.transaction()?;
+            let user_id = tx
+                .query_row("SELECT id FROM users WHERE email = ?1", [&email], |row| {
+                    uuid_at(row, 0)
+                })
+                .optional()?;
+            let Some(user_id) = user_id else {
+                return Ok(false);
+            };
+            let previous = tx
+                .query_row(
+                    "SELECT window_started_at, requested_at FROM password_resets
+                     WHERE user_id = ?1",
+                    [user_id.to_string()],
+                    |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+                )
+                .optional()?;
+            match previous {
+                Some((started, requested)) if started > window_start => {
+                    if requested > last_allowed {
+                        return Ok(false);
+                    }
+                    tx.execute(
+                        "UPDATE password_resets
+                         SET code_hash = ?2, expires_at = ?3, requested_at = ?4
+                         WHERE user_id = ?1",
+                        params![user_id.to_string(), code_hash, expires_at, now()],
+                    )?;
+                }
+                _ => {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO password_resets
+                         (user_id, code_hash, expires_at, attempts, window_started_at, requested_at)
+                         VALUES (?1, ?2, ?3, 0, ?4, ?4)",
+                        params![user_id.to_string(), code_hash, expires_at, now()],
+                    )?;
+                }
+            }
+            tx.commit()?;
+            Ok(true)
+        })
+        .await?;
+    Ok(issued.then_some(code))
+}
+
+/// There is no mail delivery yet, so with `--log-reset-codes` the code is written to the
+/// server log. The reply never says whether the email has an account.
+pub async fn request_password_reset(
+    State(db): State<Db>,
+    Extension(LogResetCodes(log_codes)): Extension<LogResetCodes>,
+    Json(body): Json<PasswordResetRequest>,
+) -> Result<Json<ApiResponse<bool>>, ServerError> {
+    let email = body.email.trim().to_owned();
+    if let Some(code) = issue_reset_code(&db, email.clone()).await? {
+        if log_codes {
+            tracing::info!("Password reset code for {email}: {code}");
+        } else {
+            tracing::info!("Password reset requested for {email}");
+        }
+    }
+    Ok(Json(ApiResponse::new(true)))
+}
+
+/// Sets the new password, ends every other session and logs in with a fresh token. The
+/// code is checked and used up in the same transaction that changes the password.
+pub async fn reset_password(
+    State(db): State<Db>,
+    Json(body): Json<PasswordResetBody>,
+) -> Result<Json<ApiResponse<LoginResponse>>, ServerError> {
+    check_password(&body.password)?;
+    let email = body.email.trim().to_owned();
+    let code_hash = token_hash(body.code.trim());
+    let password_hash = hash_password(body.password).await?;
+    let token = db
+        .run(move |conn| {
+            let tx = conn.transaction()?;
+            let reset = tx
+                .query_row(
+                    "SELECT r.user_id, r.code_hash, r.expires_at, r.attempts
+                     FROM password_resets r JOIN users u ON u.id = r.user_id
+                     WHERE u.email = ?1",
+                    [&email],
+                    |row| {
+                        Ok((
+                            uuid_at(row, 0)?,
+                            row.get::<_, String>(1)?,
+                            row.get::<_, String>(2)?,
+                            row.get::<_, i64>(3)?,
+                        ))
+                    },
+                )
+                .optional()?;
+            let invalid = || ServerError::BadRequest("Invalid or expired reset code".to_owned());
+            let Some((user_id, expected, expires_at, attempts)) = reset else {
+                return Err(invalid());
+            };
+            // The row stays until the window ends, so that the attempts keep counting.
+            if expires_at < now() || attempts >= RESET_ATTEMPTS {
+                return Err(invalid());
+            }
+            if expected != code_hash {
+                tx.execute(
+                    "UPDATE password_resets SET attempts = attempts + 1 WHERE user_id = ?1",
+                    [user_id.to_string()],
+                )?;
+                tx.commit()?;
+                return Err(invalid());
+            }
+            tx.execute(
+                "UPDATE users SET password_hash = ?1 WHERE id = ?2",
+                params![password_hash, user_id.to_string()],
+            )?;
+            tx.execute(
+                "DELETE FROM password_resets WHERE user_id = ?1",
+                [user_id.to_string()],
+            )?;
+            tx.execute(
+                "DELETE FROM sessions WHERE user_id = ?1",
+                [user_id.to_string()],
+            )?;
+            let token = issue_token(&tx, user_id)?;
+            tx.commit()?;
+            Ok(token)
+        })
+        .await?;
+    Ok(Json(ApiResponse::new(LoginResponse { token })))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use axum::http::Request;
+
+    use super::*;
+
+    fn credentials(email: &str) -> LoginBody {
+        LoginBody {
+            email: email.to_owned(),
+            password: "correct horse 42!".to_owned(),
+        }
+    }
+
+    pub(crate) async fn sign_up(db: &Db, email: &str) -> String {
+        register(State(db.clone()), Json(credentials(email)))
+            .await
+            .unwrap()
+            .0
+            .data()
+            .token
+    }
+
+    pub(crate) async fn authenticate(db: &Db, token: &str) -> Result<AuthUser, ServerError> {
+        let (mut parts, _) = Request::builder()
+            .header("authorization", format!("Bearer {token}"))
+            .body(())
+            .unwrap()
+            .into_parts();
+        AuthUser::from_request_parts(&mut parts, db).await
+    }
+
+    #[tokio::test]
+    async fn login_needs_the_right_password() {
+        let db = Db::memory();
+        sign_up(&db, "ana@example.com").await;
+
+        let token = login(State(db.clone()), Json(credentials("ANA@example.com")))
+            .await
+            .unwrap()
+            .0
+            .data()
+            .token;
+        assert!(authenticate(&db, &token).await.is_ok());
+
+        let mut wrong = credentials("ana@example.com");
+        wrong.password = "not the password".to_owned();
+        let refused = login(State(db.clone()), Json(wrong)).await;
+        assert!(matches!(refused, Err(ServerError::WrongCredentials)));
+        // Unknown emails get the same answer as a wrong password.
+        let unknown = login(State(db.clone()), Json(credentials("bob@example.com"))).await;
+        assert!(matches!(unknown, Err(ServerError::WrongCredentials)));
+    }
+
+    #[tokio::test]
+    async fn emails_are_registered_once() {
+        let db = Db::memory();
+        sign_up(&db, "ana@example.com").await;
+        let again = register(State(db.clone()), Json(credentials("Ana@Example.com"))).await;
+        assert!(matches!(again, Err(ServerError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn tokens_end_with_logout_or_age() {
+        let db = Db::memory();
+        let token = sign_up(&db, "ana@example.com").await;
+        let user = authenticate(&db, &token).await.unwrap();
+        assert_eq!(user.id, user.ledger_id);
+        assert!(matches!(
+            authenticate(&db, "made-up").await,
+            Err(ServerError::Unauthorized)
+        ));
+
+        let old = (OffsetDateTime::now_utc() - Duration::days(SESSION_DAYS + 1))
+            .format(&Rfc3339)
+            .unwrap();
+        let hash = token_hash(&token);
+        db.run(move |conn| {
+            conn.execute(
+                "UPDATE sessions SET created_at = ?1 WHERE token_hash = ?2",
+                [old, hash],
+            )?;
+            Ok(())
+        })
+        .await
+        .unwrap();
+        assert!(matches!(
+            authenticate(&db, &token).await,
+            Err(ServerError::Unauthorized)
+        ));
+
+        let token = sign_up(&db, "bob@example.com").await;
+        let user = authenticate(&db, &token).await.unwrap();
+        assert!(logout(State(db.clone()), user).await.unwrap().0.data());
+        assert!(matches!(
+            authenticate(&db, &token).await,
+            Err(ServerError::Unauthorized)
+        ));
+    }
+
+    fn reset(email: &str, code: &str, password: &str) -> Json<PasswordResetBody> {
+        Json(PasswordResetBody {
+            email: email.to_owned(),
+            code: code.to_owned(),
+            password: password.to_owned(),
+        })
+    }
+
+    async fn age_reset(db: &Db, minutes: i64) {
+        let earlier = from_now(-Duration::minutes(minutes)).unwrap();
+        db.run(move |conn| {
+            conn.execute(
+                "UPDATE password_resets SET window_started_at = ?1, requested_at = ?1",
+                [earlier],
+            )?;
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn a_reset_code_sets_the_password_once() {
+        let db = Db::memory();
+        let old_token = sign_up(&db, "ana@example.com").await;
+        assert_eq!(
+            issue_reset_code(&db, "bob@example.com".to_owned())
+                .await
+                .unwrap(),
+            None
+        );
+        let code = issue_reset_code(&db, "ana@example.com".to_owned())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let done = reset_password(
+            State(db.clone()),
+            reset("ana@example.com", &code, "New pass 2024!"),
+        )
+        .await
+        .unwrap();
+        assert!(authenticate(&db, &done.0.data().token).await.is_ok());
+        assert!(authenticate(&db, &old_token).await.is_err());
+        let mut login_body = credentials("ana@example.com");
+        login_body.password = "New pass 2024!".to_owned();
+        assert!(login(State(db.clone()), Json(login_body)).await.is_ok());
+
+        let again = reset_password(
+            State(db.clone()),
+            reset("ana@example.com", &code, "Other pass 2024!"),
+        )
+        .await;
+        assert!(matches!(again, Err(ServerError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn new_codes_do_not_reset_the_attempts() {
+        let db = Db::memory();
+        sign_up(&db, "ana@example.com").await;
+        let code = issue_reset_code(&db, "ana@example.com".to_owned())
+            .await
+            .unwrap()
+            .unwrap();
+        // Too soon for another code.
+        assert_eq!(
+            issue_reset_code(&db, "ana@example.com".to_owned())
+                .await
+                .unwrap(),
+            None
+        );
+        let wrong = if code == "000000" { "000001" } else { "000000" };
+        for _ in 0..RESET_ATTEMPTS {
+            let guess = reset_password(
+                State(db.clone()),
+                reset("ana@example.com", wrong, "New pass 2024!"),
+            )
+            .await;
+            assert!(guess.is_err());
+        }
+
+        age_reset(&db, 2).await;
+        let code = issue_reset_code(&db, "ana@example.com".to_owned())
+            .await
+            .unwrap()
+            .unwrap();
+        let refused = reset_password(
+            State(db.clone()),
+            reset("ana@example.com", &code, "New pass 2024!"),
+        )
+        .await;
+        assert!(matches!(refused, Err(ServerError::BadRequest(_))));
+
+        // A new window starts over.
+        age_reset(&db, RESET_WINDOW_MINUTES + 1).await;
+        let code = issue_reset_code(&db, "ana@example.com".to_owned())
+            .await
+            .unwrap()
+            .unwrap();
+        let done = reset_password(
+            State(db.clone()),
+            reset("ana@example.com", &code, "New pass 2024!"),
+        )
+        .await;
+        assert!(done.is_ok());
+    }
+}
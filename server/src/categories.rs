@@ -0,0 +1,218 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use core_api::models::{ApiResponse, Category};
+use rusqlite::{params, Row};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthUser,
+    db::{ledger_id_or_new, uuid_at, Db},
+    error::ServerError,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CategoryBody {
+    /// Kept on create when given, like an expense's.
+    #[serde(default)]
+    id: Option<Uuid>,
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+impl CategoryBody {
+    fn validate(self) -> Result<(Option<Uuid>, String, Option<String>), ServerError> {
+        let name = self.name.trim().to_owned();
+        if name.is_empty() {
+            return Err(ServerError::BadRequest(
+                "Category name is required".to_owned(),
+            ));
+        }
+        Ok((
+            self.id,
+            name,
+            self.description.filter(|text| !text.is_empty()),
+        ))
+    }
+}
+
+fn category_from_row(row: &Row) -> rusqlite::Result<Category> {
+    let mut category = Category::new().name(row.get(1)?).description(row.get(2)?);
+    category.id = uuid_at(row, 0)?;
+    Ok(category)
+}
+
+fn name_taken(e: rusqlite::Error) -> ServerError {
+    match e.sqlite_error_code() {
+        Some(rusqlite::ErrorCode::ConstraintViolation) => {
+            ServerError::Conflict("A category with that name or id already exists".to_owned())
+        }
+        _ => e.into(),
+    }
+}
+
+pub async fn list(
+    State(db): State<Db>,
+    user: AuthUser,
+) -> Result<Json<ApiResponse<Vec<Category>>>, ServerError> {
+    let categories = db
+        .run(move |conn| {
+            let mut statement = conn.prepare(
+                "SELECT id, name, description FROM categories WHERE user_id = ?1
+                 ORDER BY name COLLATE NOCASE",
+            )?;
+            let rows = statement.query_map([user.ledger_id.to_string()], category_from_row)?;
+            Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+        })
+        .await?;
+    Ok(Json(ApiResponse::new(categories)))
+}
+
+pub async fn create(
+    State(db): State<Db>,
+    user: AuthUser,
+    Json(body): Json<CategoryBody>,
+) -> Result<Json<ApiResponse<Category>>, ServerError> {
+    let (id, name, description) = body.validate()?;
+    let mut category = Category::new().name(name).description(description.clone());
+    let category = db
+        .run(move |conn| {
+            category.id = ledger_id_or_new(conn, "categories", user.ledger_id, id)?;
+            conn.execute(
+                "INSERT INTO categories (id, user_id, name, description) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    category.id.to_string(),
+                    user.ledger_id.to_string(),
+                    category.name,
+                    description
+                ],
+            )
+            .map_err(name_taken)?;
+            Ok(category)
+        })
+        .await?;
+    Ok(Json(ApiResponse::new(category)))
+}
+
+pub async fn update(
+    State(db): State<Db>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<CategoryBody>,
+) -> Result<Json<ApiResponse<Category>>, ServerError> {
+    let (_, name, description) = body.validate()?;
+    let mut category = Category::new().name(name).description(description.clone());
+    category.id = id;
+    let updated = category.clone();
+    db.run(move |conn| {
+        let changed = conn
+            .execute(
+                "UPDATE categories SET name = ?1, description = ?2 WHERE id = ?3 AND user_id = ?4",
+                params![
+                    updated.name,
+                    description,
+                    id.to_string(),
+                    user.ledger_id.to_string()
+                ],
+            )
+            .map_err(name_taken)?;
+        if changed == 0 {
+            return Err(ServerError::NotFound("Category"));
+        }
+        Ok(())
+    })
+    .await?;
+    Ok(Json(ApiResponse::new(category)))
+}
+
+pub async fn delete(
+    State(db): State<Db>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<bool>>, ServerError> {
+    db.run(move |conn| {
+        let in_use: bool = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM expenses WHERE category_id = ?1 AND user_id = ?2)",
+            params![id.to_string(), user.ledger_id.to_string()],
+            |row| row.get(0),
+        )?;
+        if in_use {
+            return Err(ServerError::Conflict(
+                "The category still has expenses".to_owned(),
+            ));
+        }
+        let removed = conn.execute(
+            "DELETE FROM categories WHERE id = ?1 AND user_id = ?2",
+            params![id.to_string(), user.ledger_id.to_string()],
+        )?;
+        if removed == 0 {
+            return Err(ServerError::NotFound("Category"));
+        }
+        Ok(())
+    })
+    .await?;
+    Ok(Json(ApiResponse::new(true)))
+}
+
+#[cfg(test)]
+mod tests {
+    use core_api::models::NewExpense;
+
+    use super::*;
+    use crate::{
+        auth::tests::{authenticate, sign_up},
+        expenses,
+    };
+
+    #[tokio::test]
+    async fn categories_in_use_only_count_the_callers_expenses() {
+        let db = Db::memory();
+        let ana = sign_up(&db, "ana@example.com").await;
+        let bob = sign_up(&db, "bob@example.com").await;
+        let mine = list(State(db.clone()), authenticate(&db, &ana).await.unwrap())
+            .await
+            .unwrap()
+            .0
+            .data();
+        let category_id = mine[0].id;
+        let expense = NewExpense {
+            category_id,
+            date: "2024-03-01".to_owned(),
+            amount: 1200,
+            ..NewExpense::default()
+        };
+        let added = expenses::create(
+            State(db.clone()),
+            authenticate(&db, &ana).await.unwrap(),
+            Json(expense),
+        )
+        .await;
+        assert!(added.is_ok());
+
+        // Someone else's category is not found, whether or not it has expenses.
+        let other = delete(
+            State(db.clone()),
+            authenticate(&db, &bob).await.unwrap(),
+            Path(category_id),
+        )
+        .await;
+        assert!(matches!(other, Err(ServerError::NotFound(_))));
+        let in_use = delete(
+            State(db.clone()),
+            authenticate(&db, &ana).await.unwrap(),
+            Path(category_id),
+        )
+        .await;
+        assert!(matches!(in_use, Err(ServerError::Conflict(_))));
+        let unused = delete(
+            State(db.clone()),
+            authenticate(&db, &ana).await.unwrap(),
+            Path(mine[1].id),
+        )
+        .await;
+        assert!(unused.is_ok());
+    }
+}
@@ -0,0 +1,151 @@
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use rusqlite::{types::Type, Connection, Row};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::error::ServerError;
+
+/// Applied in order; `PRAGMA user_version` records how many already ran. Never edit a
+/// migration that has shipped, add a new one instead.
+const MIGRATIONS: &[&str] = &[
+    "
+    CREATE TABLE users (
+        id TEXT PRIMARY KEY,
+        email TEXT NOT NULL UNIQUE COLLATE NOCASE,
+        password_hash TEXT NOT NULL,
+        created_at TEXT NOT NULL
+    );
+    CREATE TABLE sessions (
+        token_hash TEXT PRIMARY KEY,
+        user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+        created_at TEXT NOT NULL
+    );
+    CREATE TABLE categories (
+        id TEXT PRIMARY KEY,
+        user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+        name TEXT NOT NULL,
+        description TEXT,
+        UNIQUE (user_id, name COLLATE NOCASE)
+    );
+    CREATE TABLE expenses (
+        id TEXT PRIMARY KEY,
+        user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+        category_id TEXT NOT NULL REFERENCES categories(id),
+        description TEXT,
+        amount INTEGER NOT NULL,
+        date TEXT NOT NULL,
+        external_id TEXT,
+        account TEXT,
+        created_at TEXT NOT NULL
+    );
+    CREATE INDEX expenses_by_user_date ON expenses (user_id, date);
+",
+    "
+    CREATE TABLE password_resets (
+        user_id TEXT PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+        code_hash TEXT NOT NULL,
+        expires_at TEXT NOT NULL,
+        attempts INTEGER NOT NULL DEFAULT 0
+    );
+",
+    "
+    CREATE TABLE households (
+        id TEXT PRIMARY KEY,
+        owner_id TEXT NOT NULL UNIQUE REFERENCES users(id) ON DELETE CASCADE,
+        name TEXT NOT NULL,
+        created_at TEXT NOT NULL
+    );
+    CREATE TABLE household_members (
+        user_id TEXT PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+        household_id TEXT NOT NULL REFERENCES households(id) ON DELETE CASCADE,
+        joined_at TEXT NOT NULL
+    );
+    CREATE TABLE household_invites (
+        code_hash TEXT PRIMARY KEY,
+        household_id TEXT NOT NULL REFERENCES households(id) ON DELETE CASCADE,
+        expires_at TEXT NOT NULL
+    );
+    ALTER TABLE expenses ADD COLUMN paid_by TEXT REFERENCES users(id) ON DELETE SET NULL;
+    UPDATE expenses SET paid_by = user_id;
+",
+    "
+    ALTER TABLE password_resets ADD COLUMN window_started_at TEXT NOT NULL DEFAULT '';
+    ALTER TABLE password_resets ADD COLUMN requested_at TEXT NOT NULL DEFAULT '';
+",
+];
+
+fn migrate(conn: &mut Connection) -> rusqlite::Result<()> {
+    let applied: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    for (version, sql) in MIGRATIONS.iter().enumerate().skip(applied as usize) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(sql)?;
+        tx.pragma_update(None, "user_version", version as i64 + 1)?;
+        tx.commit()?;
+    }
+    Ok(())
+}
+
+pub fn now() -> String {
+    OffsetDateTime::now_utc()
+        .format(&Rfc3339)
+        .unwrap_or_default()
+}
+
+pub fn uuid_at(row: &Row, index: usize) -> rusqlite::Result<Uuid> {
+    let text: String = row.get(index)?;
+    Uuid::parse_str(&text)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, Type::Text, Box::new(e)))
+}
+
+/// The id a client asked for, or a new one when it is taken in another ledger. Ids only
+/// have to be unique within a ledger for the client, and refusing one taken elsewhere
+/// would tell the caller that it exists. `table` is a table with `id` and `user_id`.
+pub fn ledger_id_or_new(
+    conn: &Connection,
+    table: &'static str,
+    ledger_id: Uuid,
+    requested: Option<Uuid>,
+) -> rusqlite::Result<Uuid> {
+    let Some(id) = requested else {
+        return Ok(Uuid::new_v4());
+    };
+    let elsewhere: bool = conn.query_row(
+        &format!("SELECT EXISTS (SELECT 1 FROM {table} WHERE id = ?1 AND user_id <> ?2)"),
+        [id.to_string(), ledger_id.to_string()],
+        |row| row.get(0),
+    )?;
+    Ok(if elsewhere { Uuid::new_v4() } else { id })
+}
+
+/// One SQLite connection shared by all requests; queries run on the blocking pool.
+#[derive(Clone)]
+pub struct Db(Arc<Mutex<Connection>>);
+
+impl Db {
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        let mut conn = Connection::open(path)?;
+        conn.execute_batch("PRAGMA foreign_keys = ON; PRAGMA journal_mode = WAL;")?;
+        migrate(&mut conn)?;
+        Ok(Db(Arc::new(Mutex::new(conn))))
+    }
+
+    #[cfg(test)]
+    pub fn memory() -> Self {
+        Db::open(Path::new(":memory:")).unwrap()
+    }
+
+    pub async fn run<T, F>(&self, query: F) -> Result<T, ServerError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<T, ServerError> + Send + 'static,
+    {
+        let conn = self.0.clone();
+        tokio::task::spawn_blocking(move || query(&mut conn.lock().unwrap()))
+            .await
+            .map_err(|e| ServerError::Internal(e.to_string()))?
+    }
+}
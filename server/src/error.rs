@@ -0,0 +1,47 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use core_api::models::ApiResponse;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ServerError {
+    #[error("{0}")]
+    BadRequest(String),
+    #[error("Invalid or missing token")]
+    Unauthorized,
+    #[error("Wrong email or password")]
+    WrongCredentials,
+    #[error("{0} not found")]
+    NotFound(&'static str),
+    #[error("{0}")]
+    Conflict(String),
+    #[error("database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl IntoResponse for ServerError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            ServerError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ServerError::Unauthorized | ServerError::WrongCredentials => StatusCode::UNAUTHORIZED,
+            ServerError::NotFound(_) => StatusCode::NOT_FOUND,
+            ServerError::Conflict(_) => StatusCode::CONFLICT,
+            ServerError::Database(_) | ServerError::Internal(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        // Internal details go to the log, not to the client.
+        let message = if status == StatusCode::INTERNAL_SERVER_ERROR {
+            tracing::error!("{self}");
+            "Internal server error".to_owned()
+        } else {
+            self.to_string()
+        };
+        (status, Json(ApiResponse::failed((), vec![message]))).into_response()
+    }
+}
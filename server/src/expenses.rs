@@ -0,0 +1,354 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+    Json,
+};
+use core_api::{
+    models::{parse_date, ApiResponse, Expense, NewExpense},
+    paging::{ExpensePage, ExpenseQuery},
+    sorting::SortBy,
+};
+use rusqlite::{params, params_from_iter, types::Value, Connection, OptionalExtension, Row};
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthUser,
+    db::{ledger_id_or_new, now, uuid_at, Db},
+    error::ServerError,
+};
+
+const COLUMNS: &str = "e.id, e.category_id, c.name, e.description, e.amount, e.date,
+        e.external_id, e.account, e.paid_by";
+const FROM: &str = "FROM expenses e JOIN categories c ON c.id = e.category_id";
+
+fn expense_from_row(row: &Row) -> rusqlite::Result<Expense> {
+    Ok(Expense {
+        id: uuid_at(row, 0)?,
+        category_id: uuid_at(row, 1)?,
+        category_name: row.get(2)?,
+        description: row.get(3)?,
+        amount: row.get(4)?,
+        date: row.get(5)?,
+        external_id: row.get(6)?,
+        account: row.get(7)?,
+        paid_by: row
+            .get::<_, Option<String>>(8)?
+            .and_then(|id| Uuid::parse_str(&id).ok()),
+    })
+}
+
+fn find(conn: &Connection, ledger_id: Uuid, id: Uuid) -> Result<Expense, ServerError> {
+    conn.query_row(
+        &format!("SELECT {COLUMNS} {FROM} WHERE e.id = ?1 AND e.user_id = ?2"),
+        params![id.to_string(), ledger_id.to_string()],
+        expense_from_row,
+    )
+    .optional()?
+    .ok_or(ServerError::NotFound("Expense"))
+}
+
+fn validate(conn: &Connection, ledger_id: Uuid, body: &NewExpense) -> Result<(), ServerError> {
+    if parse_date(&body.date).is_none() {
+        return Err(ServerError::BadRequest("Invalid date".to_owned()));
+    }
+    let owned: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM categories WHERE id = ?1 AND user_id = ?2)",
+        params![body.category_id.to_string(), ledger_id.to_string()],
+        |row| row.get(0),
+    )?;
+    if !owned {
+        return Err(ServerError::BadRequest("Unknown category".to_owned()));
+    }
+    if let Some(member) = body.paid_by.filter(|member| *member != ledger_id) {
+        let shares: bool = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM household_members m
+                JOIN households h ON h.id = m.household_id
+                WHERE h.owner_id = ?1 AND m.user_id = ?2)",
+            params![ledger_id.to_string(), member.to_string()],
+            |row| row.get(0),
+        )?;
+        if !shares {
+            return Err(ServerError::BadRequest(
+                "Unknown household member".to_owned(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Only ids in the caller's own ledger get here, see [`ledger_id_or_new`].
+fn id_taken(e: rusqlite::Error) -> ServerError {
+    match e.sqlite_error_code() {
+        Some(rusqlite::ErrorCode::ConstraintViolation) => {
+            ServerError::Conflict("An expense with that id already exists".to_owned())
+        }
+        _ => e.into(),
+    }
+}
+
+fn description(body: &NewExpense) -> Option<&str> {
+    Some(body.description.trim()).filter(|text| !text.is_empty())
+}
+
+fn list_all(conn: &Connection, ledger_id: Uuid) -> Result<Vec<Expense>, ServerError> {
+    let mut statement = conn.prepare(&format!(
+        "SELECT {COLUMNS} {FROM} WHERE e.user_id = ?1 ORDER BY e.date DESC, e.created_at DESC"
+    ))?;
+    let rows = statement.query_map([ledger_id.to_string()], expense_from_row)?;
+    Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+}
+
+/// The page is cut in SQL; `count` and `total` cover the whole filter. Text search uses
+/// `LIKE`, so unlike the client it only ignores the case of ASCII letters.
+fn list_page(
+    conn: &Connection,
+    ledger_id: Uuid,
+    query: &ExpenseQuery,
+) -> Result<ExpensePage, ServerError> {
+    let filter = &query.filter;
+    let mut conditions = vec!["e.user_id = ?".to_owned()];
+    let mut values = vec![Value::Text(ledger_id.to_string())];
+    let mut condition = |sql: &str, value: Value| {
+        conditions.push(sql.to_owned());
+        values.push(value);
+    };
+    if let Some(from) = &filter.from {
+        condition("e.date >= ?", Value::Text(from.clone()));
+    }
+    if let Some(to) = &filter.to {
+        condition("e.date <= ?", Value::Text(to.clone()));
+    }
+    if let Some(min) = filter.min_amount {
+        condition("e.amount >= ?", Value::Integer(min.into()));
+    }
+    if let Some(max) = filter.max_amount {
+        condition("e.amount <= ?", Value::Integer(max.into()));
+    }
+    if let Some(member) = filter.paid_by {
+        // Expenses from before payers were kept count as the owner's, whose ledger it is.
+        condition(
+            "COALESCE(e.paid_by, e.user_id) = ?",
+            Value::Text(member.to_string()),
+        );
+    }
+    let text = filter.text.trim();
+    if !text.is_empty() {
+        let escaped = text
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        condition(
+            "e.description LIKE ? ESCAPE '\\'",
+            Value::Text(format!("%{escaped}%")),
+        );
+    }
+    if !filter.category_ids.is_empty() {
+        let placeholders = vec!["?"; filter.category_ids.len()].join(", ");
+        conditions.push(format!("e.category_id IN ({placeholders})"));
+        values.extend(
+            filter
+                .category_ids
+                .iter()
+                .map(|id| Value::Text(id.to_string())),
+        );
+    }
+    let filtered = format!("{FROM} WHERE {}", conditions.join(" AND "));
+
+    let (count, total): (i64, i64) = conn.query_row(
+        &format!("SELECT COUNT(*), COALESCE(SUM(e.amount), 0) {filtered}"),
+        params_from_iter(values.iter()),
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    let column = match query.options.sort_by {
+        SortBy::Date => "e.date",
+        SortBy::Amount => "e.amount",
+        SortBy::Category => "c.name COLLATE NOCASE",
+    };
+    let direction = if query.options.descending {
+        "DESC"
+    } else {
+        "ASC"
+    };
+    let mut statement = conn.prepare(&format!(
+        "SELECT {COLUMNS}
+        {filtered}
+        ORDER BY {column} {direction}, e.date DESC, e.created_at DESC, e.id
+        LIMIT ? OFFSET ?"
+    ))?;
+    values.push(Value::Integer(query.limit as i64));
+    values.push(Value::Integer(query.offset() as i64));
+    let items = statement
+        .query_map(params_from_iter(values.iter()), expense_from_row)?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(ExpensePage {
+        items,
+        next_cursor: query.next_cursor(count as usize),
+        count: count as usize,
+        total,
+    })
+}
+
+/// Pages when the request has a `limit`, otherwise returns every expense.
+pub async fn list(
+    State(db): State<Db>,
+    user: AuthUser,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Response, ServerError> {
+    match ExpenseQuery::from_params(&params) {
+        Some(query) => {
+            let page = db
+                .run(move |conn| list_page(conn, user.ledger_id, &query))
+                .await?;
+            Ok(Json(ApiResponse::new(page)).into_response())
+        }
+        None => {
+            let expenses = db.run(move |conn| list_all(conn, user.ledger_id)).await?;
+            Ok(Json(ApiResponse::new(expenses)).into_response())
+        }
+    }
+}
+
+pub async fn get(
+    State(db): State<Db>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Expense>>, ServerError> {
+    let expense = db.run(move |conn| find(conn, user.ledger_id, id)).await?;
+    Ok(Json(ApiResponse::new(expense)))
+}
+
+pub async fn create(
+    State(db): State<Db>,
+    user: AuthUser,
+    Json(body): Json<NewExpense>,
+) -> Result<Json<ApiResponse<Expense>>, ServerError> {
+    let expense = db
+        .run(move |conn| {
+            validate(conn, user.ledger_id, &body)?;
+            let id = ledger_id_or_new(conn, "expenses", user.ledger_id, body.id)?;
+            conn.execute(
+                "INSERT INTO expenses (id, user_id, category_id, description, amount, date,
+                    external_id, account, paid_by, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    id.to_string(),
+                    user.ledger_id.to_string(),
+                    body.category_id.to_string(),
+                    description(&body),
+                    body.amount,
+                    body.date,
+                    body.external_id,
+                    body.account,
+                    body.paid_by.unwrap_or(user.id).to_string(),
+                    now()
+                ],
+            )
+            .map_err(id_taken)?;
+            find(conn, user.ledger_id, id)
+        })
+        .await?;
+    Ok(Json(ApiResponse::new(expense)))
+}
+
+pub async fn update(
+    State(db): State<Db>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<NewExpense>,
+) -> Result<Json<ApiResponse<Expense>>, ServerError> {
+    let expense = db
+        .run(move |conn| {
+            validate(conn, user.ledger_id, &body)?;
+            let changed = conn.execute(
+                "UPDATE expenses SET category_id = ?1, description = ?2, amount = ?3, date = ?4,
+                    external_id = ?5, account = ?6, paid_by = COALESCE(?7, paid_by)
+                 WHERE id = ?8 AND user_id = ?9",
+                params![
+                    body.category_id.to_string(),
+                    description(&body),
+                    body.amount,
+                    body.date,
+                    body.external_id,
+                    body.account,
+                    body.paid_by.map(|member| member.to_string()),
+                    id.to_string(),
+                    user.ledger_id.to_string()
+                ],
+            )?;
+            if changed == 0 {
+                return Err(ServerError::NotFound("Expense"));
+            }
+            find(conn, user.ledger_id, id)
+        })
+        .await?;
+    Ok(Json(ApiResponse::new(expense)))
+}
+
+pub async fn delete(
+    State(db): State<Db>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<bool>>, ServerError> {
+    db.run(move |conn| {
+        let removed = conn.execute(
+            "DELETE FROM expenses WHERE id = ?1 AND user_id = ?2",
+            params![id.to_string(), user.ledger_id.to_string()],
+        )?;
+        if removed == 0 {
+            return Err(ServerError::NotFound("Expense"));
+        }
+        Ok(())
+    })
+    .await?;
+    Ok(Json(ApiResponse::new(true)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        auth::tests::{authenticate, sign_up},
+        categories,
+    };
+
+    async fn add(db: &Db, token: &str, id: Uuid) -> Result<Uuid, ServerError> {
+        let user = authenticate(db, token).await?;
+        let category_id = categories::list(State(db.clone()), authenticate(db, token).await?)
+            .await?
+            .0
+            .data()[0]
+            .id;
+        let body = NewExpense {
+            id: Some(id),
+            category_id,
+            date: "2026-10-01".to_owned(),
+            amount: 700,
+            ..NewExpense::default()
+        };
+        Ok(create(State(db.clone()), user, Json(body))
+            .await?
+            .0
+            .data()
+            .id)
+    }
+
+    #[tokio::test]
+    async fn ids_are_only_kept_within_the_ledger() {
+        let db = Db::memory();
+        let ana = sign_up(&db, "ana@example.com").await;
+        let bob = sign_up(&db, "bob@example.com").await;
+        let id = Uuid::new_v4();
+        assert_eq!(add(&db, &ana, id).await.unwrap(), id);
+
+        // Someone else's id is answered like a fresh one, only under a new id.
+        let bobs = add(&db, &bob, id).await.unwrap();
+        assert_ne!(bobs, id);
+        assert!(matches!(
+            add(&db, &ana, id).await,
+            Err(ServerError::Conflict(_))
+        ));
+    }
+}
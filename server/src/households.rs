@@ -0,0 +1,357 @@
+use axum::{extract::State, Json};
+use core_api::{
+    household::{Household, Invite, JoinHousehold, Member, NewHousehold},
+    models::ApiResponse,
+};
+use rusqlite::{params, Connection, OptionalExtension};
+use time::{format_description::well_known::Rfc3339, Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::{
+    auth::{token_hash, AuthUser},
+    db::{now, uuid_at, Db},
+    error::ServerError,
+};
+
+const INVITE_DAYS: i64 = 7;
+// No 0/O or 1/I, so a code read aloud is typed right.
+const CODE_ALPHABET: &[u8] = b"23456789ABCDEFGHJKLMNPQRSTUVWXYZ";
+
+fn invite_code() -> String {
+    let bytes = Uuid::new_v4().into_bytes();
+    let code: String = bytes[..8]
+        .iter()
+        .map(|byte| CODE_ALPHABET[usize::from(*byte) % CODE_ALPHABET.len()] as char)
+        .collect();
+    format!("{}-{}", &code[..4], &code[4..])
+}
+
+// Codes are compared without case or dashes, the way people type them.
+fn normalize_code(code: &str) -> String {
+    code.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+fn household_id(conn: &Connection, user_id: Uuid) -> Result<Option<Uuid>, ServerError> {
+    Ok(conn
+        .query_row(
+            "SELECT household_id FROM household_members WHERE user_id = ?1",
+            [user_id.to_string()],
+            |row| uuid_at(row, 0),
+        )
+        .optional()?)
+}
+
+fn load(conn: &Connection, user_id: Uuid) -> Result<Option<Household>, ServerError> {
+    let Some(id) = household_id(conn, user_id)? else {
+        return Ok(None);
+    };
+    let (name, owner_id) = conn.query_row(
+        "SELECT name, owner_id FROM households WHERE id = ?1",
+        [id.to_string()],
+        |row| Ok((row.get::<_, String>(0)?, uuid_at(row, 1)?)),
+    )?;
+    let mut statement = conn.prepare(
+        "SELECT u.id, u.email FROM household_members m JOIN users u ON u.id = m.user_id
+         WHERE m.household_id = ?1 ORDER BY m.joined_at, u.email",
+    )?;
+    let members = statement
+        .query_map([id.to_string()], |row| {
+            Ok(Member {
+                id: uuid_at(row, 0)?,
+                email: row.get(1)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(Some(Household {
+        id,
+        name,
+        owner_id,
+        members,
+        current_member_id: user_id,
+    }))
+}
+
+fn not_in_household() -> ServerError {
+    ServerError::BadRequest("You are not in a household".to_owned())
+}
+
+/// `null` when the user is not in a household.
+pub async fn get(
+    State(db): State<Db>,
+    user: AuthUser,
+) -> Result<Json<ApiResponse<Option<Household>>>, ServerError> {
+    let household = db.run(move |conn| load(conn, user.id)).await?;
+    Ok(Json(ApiResponse::new(household)))
+}
+
+/// The creator's ledger becomes the household's.
+pub async fn create(
+    State(db): State<Db>,
+    user: AuthUser,
+    Json(body): Json<NewHousehold>,
+) -> Result<Json<ApiResponse<Option<Household>>>, ServerError> {
+    let name = body.name.trim().to_owned();
+    if name.is_empty() {
+        return Err(ServerError::BadRequest("A name is required".to_owned()));
+    }
+    let household = db
+        .run(move |conn| {
+            let tx = conn.transaction()?;
+            if household_id(&tx, user.id)?.is_some() {
+                return Err(ServerError::Conflict(
+                    "You are already in a household".to_owned(),
+                ));
+            }
+            let id = Uuid::new_v4();
+            tx.execute(
+                "INSERT INTO households (id, owner_id, name, created_at) VALUES (?1, ?2, ?3, ?4)",
+                params![id.to_string(), user.id.to_string(), name, now()],
+            )?;
+            tx.execute(
+                "INSERT INTO household_members (user_id, household_id, joined_at)
+                 VALUES (?1, ?2, ?3)",
+                params![user.id.to_string(), id.to_string(), now()],
+            )?;
+            let household = load(&tx, user.id)?;
+            tx.commit()?;
+            Ok(household)
+        })
+        .await?;
+    Ok(Json(ApiResponse::new(household)))
+}
+
+/// Any member can invite. Each code works once.
+pub async fn invite(
+    State(db): State<Db>,
+    user: AuthUser,
+) -> Result<Json<ApiResponse<Invite>>, ServerError> {
+    let code = invite_code();
+    let expires_at = (OffsetDateTime::now_utc() + Duration::days(INVITE_DAYS))
+        .format(&Rfc3339)
+        .map_err(|e| ServerError::Internal(e.to_string()))?;
+    let code_hash = token_hash(&normalize_code(&code));
+    let stored = expires_at.clone();
+    db.run(move |conn| {
+        let id = household_id(conn, user.id)?.ok_or_else(not_in_household)?;
+        conn.execute(
+            "DELETE FROM household_invites WHERE expires_at < ?1",
+            [now()],
+        )?;
+        conn.execute(
+            "INSERT INTO household_invites (code_hash, household_id, expires_at)
+             VALUES (?1, ?2, ?3)",
+            params![code_hash, id.to_string(), stored],
+        )?;
+        Ok(())
+    })
+    .await?;
+    Ok(Json(ApiResponse::new(Invite { code, expires_at })))
+}
+
+/// Joining switches the user to the household's ledger; their own expenses come back
+/// if they leave.
+pub async fn join(
+    State(db): State<Db>,
+    user: AuthUser,
+    Json(body): Json<JoinHousehold>,
+) -> Result<Json<ApiResponse<Option<Household>>>, ServerError> {
+    let code_hash = token_hash(&normalize_code(&body.code));
+    let household = db
+        .run(move |conn| {
+            let tx = conn.transaction()?;
+            if household_id(&tx, user.id)?.is_some() {
+                return Err(ServerError::Conflict(
+                    "Leave your household before joining another".to_owned(),
+                ));
+            }
+            let id = tx
+                .query_row(
+                    "SELECT household_id FROM household_invites
+                     WHERE code_hash = ?1 AND expires_at >= ?2",
+                    params![code_hash, now()],
+                    |row| uuid_at(row, 0),
+                )
+                .optional()?
+                .ok_or_else(|| {
+                    ServerError::BadRequest("Invalid or expired invitation code".to_owned())
+                })?;
+            tx.execute(
+                "DELETE FROM household_invites WHERE code_hash = ?1",
+                [&code_hash],
+            )?;
+            tx.execute(
+                "INSERT INTO household_members (user_id, household_id, joined_at)
+                 VALUES (?1, ?2, ?3)",
+                params![user.id.to_string(), id.to_string(), now()],
+            )?;
+            let household = load(&tx, user.id)?;
+            tx.commit()?;
+            Ok(household)
+        })
+        .await?;
+    Ok(Json(ApiResponse::new(household)))
+}
+
+/// The owner can only leave last, which ends the household.
+pub async fn leave(
+    State(db): State<Db>,
+    user: AuthUser,
+) -> Result<Json<ApiResponse<bool>>, ServerError> {
+    db.run(move |conn| {
+        let household = load(conn, user.id)?.ok_or_else(not_in_household)?;
+        if household.owner_id != user.id {
+            conn.execute(
+                "DELETE FROM household_members WHERE user_id = ?1",
+                [user.id.to_string()],
+            )?;
+            return Ok(());
+        }
+        if household.members.len() > 1 {
+            return Err(ServerError::Conflict(
+                "The owner can leave once everyone else has".to_owned(),
+            ));
+        }
+        conn.execute(
+            "DELETE FROM households WHERE id = ?1",
+            [household.id.to_string()],
+        )?;
+        Ok(())
+    })
+    .await?;
+    Ok(Json(ApiResponse::new(true)))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::Path;
+    use core_api::models::NewExpense;
+
+    use super::*;
+    use crate::{
+        auth::tests::{authenticate, sign_up},
+        categories, expenses,
+    };
+
+    async fn user(db: &Db, token: &str) -> AuthUser {
+        authenticate(db, token).await.unwrap()
+    }
+
+    async fn add_expense(db: &Db, token: &str, paid_by: Option<Uuid>) -> Result<Uuid, ServerError> {
+        let category_id = categories::list(State(db.clone()), user(db, token).await)
+            .await?
+            .0
+            .data()[0]
+            .id;
+        let body = NewExpense {
+            category_id,
+            date: "2026-10-01".to_owned(),
+            amount: 4500,
+            paid_by,
+            ..NewExpense::default()
+        };
+        let created =
+            expenses::create(State(db.clone()), user(db, token).await, Json(body)).await?;
+        Ok(created.0.data().id)
+    }
+
+    async fn sees(db: &Db, token: &str, expense_id: Uuid) -> bool {
+        expenses::get(State(db.clone()), user(db, token).await, Path(expense_id))
+            .await
+            .is_ok()
+    }
+
+    async fn join_with(db: &Db, token: &str, code: String) -> Result<(), ServerError> {
+        join(
+            State(db.clone()),
+            user(db, token).await,
+            Json(JoinHousehold { code }),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Starts a household owned by `token`'s user and returns an invite code for it.
+    async fn start_household(db: &Db, token: &str) -> String {
+        let created = create(
+            State(db.clone()),
+            user(db, token).await,
+            Json(NewHousehold {
+                name: "Home".to_owned(),
+            }),
+        )
+        .await
+        .unwrap();
+        assert!(created.0.data().is_some());
+        invite(State(db.clone()), user(db, token).await)
+            .await
+            .unwrap()
+            .0
+            .data()
+            .code
+    }
+
+    #[tokio::test]
+    async fn members_share_one_ledger_and_outsiders_see_none_of_it() {
+        let db = Db::memory();
+        let ana = sign_up(&db, "ana@example.com").await;
+        let bob = sign_up(&db, "bob@example.com").await;
+        let carol = sign_up(&db, "carol@example.com").await;
+        let bobs_own = add_expense(&db, &bob, None).await.unwrap();
+
+        let code = start_household(&db, &ana).await;
+        // Typed without the dash and in lower case.
+        join_with(&db, &bob, code.replace('-', "").to_lowercase())
+            .await
+            .unwrap();
+        assert!(matches!(
+            join_with(&db, &carol, code).await,
+            Err(ServerError::BadRequest(_))
+        ));
+
+        let ana_user = user(&db, &ana).await;
+        assert_eq!(user(&db, &bob).await.ledger_id, ana_user.id);
+        assert_eq!(
+            user(&db, &carol).await.ledger_id,
+            user(&db, &carol).await.id
+        );
+
+        let shared = add_expense(&db, &bob, None).await.unwrap();
+        assert!(sees(&db, &ana, shared).await);
+        assert!(!sees(&db, &carol, shared).await);
+        assert!(!sees(&db, &bob, bobs_own).await);
+        let removed =
+            expenses::delete(State(db.clone()), user(&db, &carol).await, Path(shared)).await;
+        assert!(matches!(removed, Err(ServerError::NotFound(_))));
+
+        let left = leave(State(db.clone()), user(&db, &bob).await).await;
+        assert!(left.is_ok());
+        assert!(sees(&db, &bob, bobs_own).await);
+        assert!(!sees(&db, &bob, shared).await);
+    }
+
+    #[tokio::test]
+    async fn only_members_can_be_the_payer() {
+        let db = Db::memory();
+        let ana = sign_up(&db, "ana@example.com").await;
+        let bob = sign_up(&db, "bob@example.com").await;
+        let carol = sign_up(&db, "carol@example.com").await;
+        let code = start_household(&db, &ana).await;
+        join_with(&db, &bob, code).await.unwrap();
+
+        let bob_id = user(&db, &bob).await.id;
+        assert!(add_expense(&db, &ana, Some(bob_id)).await.is_ok());
+        let carol_id = user(&db, &carol).await.id;
+        assert!(matches!(
+            add_expense(&db, &ana, Some(carol_id)).await,
+            Err(ServerError::BadRequest(_))
+        ));
+        // The owner leaves last.
+        assert!(matches!(
+            leave(State(db.clone()), user(&db, &ana).await).await,
+            Err(ServerError::Conflict(_))
+        ));
+    }
+}
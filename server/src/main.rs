@@ -0,0 +1,78 @@
+mod auth;
+mod categories;
+mod db;
+mod error;
+mod expenses;
+mod households;
+
+use std::{net::SocketAddr, path::PathBuf};
+
+use axum::{
+    routing::{get, post},
+    Extension, Router,
+};
+use clap::Parser;
+use tower_http::cors::CorsLayer;
+
+use crate::{auth::LogResetCodes, db::Db};
+
+/// Self-hosted Finance Home API. Point the app's server URL at it.
+#[derive(Parser)]
+#[command(name = "finance-home-server", version)]
+struct Args {
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    bind: SocketAddr,
+    /// SQLite database file, created and migrated on start.
+    #[arg(long, default_value = "finance-home.db")]
+    database: PathBuf,
+    /// Write password reset codes to the log, for the administrator to pass on. There is
+    /// no mail delivery yet, so resets only work with this set.
+    #[arg(long)]
+    log_reset_codes: bool,
+}
+
+fn router(db: Db, log_reset_codes: LogResetCodes) -> Router {
+    Router::new()
+        .route("/register", post(auth::register))
+        .route("/login", post(auth::login))
+        .route("/logout", post(auth::logout))
+        .route("/password-reset", post(auth::request_password_reset))
+        .route("/password-reset/confirm", post(auth::reset_password))
+        .route(
+            "/categories",
+            get(categories::list).post(categories::create),
+        )
+        .route(
+            "/categories/{id}",
+            axum::routing::put(categories::update).delete(categories::delete),
+        )
+        .route("/household", get(households::get).post(households::create))
+        .route("/household/invites", post(households::invite))
+        .route("/household/join", post(households::join))
+        .route("/household/leave", post(households::leave))
+        .route("/expenses", get(expenses::list).post(expenses::create))
+        .route(
+            "/expenses/{id}",
+            get(expenses::get)
+                .put(expenses::update)
+                .delete(expenses::delete),
+        )
+        .with_state(db)
+        .layer(Extension(log_reset_codes))
+        // The desktop app calls from its own webview origin.
+        .layer(CorsLayer::permissive())
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    tracing_subscriber::fmt::init();
+    let args = Args::parse();
+    let db = Db::open(&args.database).map_err(std::io::Error::other)?;
+    let listener = tokio::net::TcpListener::bind(args.bind).await?;
+    tracing::info!(
+        "Finance Home API on http://{}, data in {}",
+        listener.local_addr()?,
+        args.database.display()
+    );
+    axum::serve(listener, router(db, LogResetCodes(args.log_reset_codes))).await
+}
@@ -0,0 +1,150 @@
+use std::{fs, path::PathBuf};
+
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use core_api::backup::{
+    merge_settings, preview, Backup, RestoreMode, RestorePreview, BACKUP_VERSION,
+};
+use tauri::State;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+use crate::{lock::require_unlocked, store::Store};
+
+const MAGIC: &[u8; 4] = b"FHBK";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = MAGIC.len() + 4 + SALT_LEN + NONCE_LEN;
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key, String> {
+    if passphrase.is_empty() {
+        return Err("A passphrase is required.".to_owned());
+    }
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| e.to_string())?;
+    Ok(key)
+}
+
+/// Layout: magic, format version, Argon2 salt, nonce, then the ChaCha20-Poly1305
+/// ciphertext. The header is authenticated as associated data.
+pub fn seal(passphrase: &str, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let mut sealed = Vec::with_capacity(HEADER_LEN + plaintext.len() + 16);
+    sealed.extend_from_slice(MAGIC);
+    sealed.extend_from_slice(&BACKUP_VERSION.to_le_bytes());
+    sealed.extend_from_slice(&salt);
+    sealed.extend_from_slice(&nonce);
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad: &sealed,
+            },
+        )
+        .map_err(|_| "Could not encrypt the backup.".to_owned())?;
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+pub fn open(passphrase: &str, sealed: &[u8]) -> Result<Vec<u8>, String> {
+    if sealed.len() < HEADER_LEN || !sealed.starts_with(MAGIC) {
+        return Err("This is not a Finance Home backup.".to_owned());
+    }
+    let (header, ciphertext) = sealed.split_at(HEADER_LEN);
+    let version = u32::from_le_bytes(header[4..8].try_into().unwrap());
+    if version > BACKUP_VERSION {
+        return Err(format!("Backup version {version} is newer than this app."));
+    }
+    let salt = &header[8..8 + SALT_LEN];
+    let nonce = Nonce::from_slice(&header[8 + SALT_LEN..]);
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, salt)?);
+    cipher
+        .decrypt(
+            nonce,
+            Payload {
+                msg: ciphertext,
+                aad: header,
+            },
+        )
+        .map_err(|_| "Wrong passphrase or damaged backup.".to_owned())
+}
+
+fn read_backup(passphrase: &str, path: &str) -> Result<Backup, String> {
+    let sealed = fs::read(path).map_err(|e| e.to_string())?;
+    serde_json::from_slice(&open(passphrase, &sealed)?).map_err(|e| e.to_string())
+}
+
+fn default_path(store: &Store) -> Result<PathBuf, String> {
+    let dir = store.dir().join("backups");
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let now = OffsetDateTime::now_utc();
+    Ok(dir.join(format!(
+        "finance-home-{}{:02}{:02}-{:02}{:02}{:02}.fhbk",
+        now.year(),
+        u8::from(now.month()),
+        now.day(),
+        now.hour(),
+        now.minute(),
+        now.second()
+    )))
+}
+
+#[tauri::command]
+pub fn create_backup(
+    passphrase: String,
+    path: Option<String>,
+    store: State<'_, Store>,
+) -> Result<String, String> {
+    require_unlocked(&store)?;
+    let path = match path.filter(|path| !path.trim().is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => default_path(&store)?,
+    };
+    let created_at = OffsetDateTime::now_utc()
+        .format(&Rfc3339)
+        .map_err(|e| e.to_string())?;
+    let backup = Backup::new(created_at, store.read());
+    let plaintext = serde_json::to_vec(&backup).map_err(|e| e.to_string())?;
+    fs::write(&path, seal(&passphrase, &plaintext)?).map_err(|e| e.to_string())?;
+    Ok(path.display().to_string())
+}
+
+#[tauri::command]
+pub fn preview_restore(
+    passphrase: String,
+    path: String,
+    mode: RestoreMode,
+    store: State<'_, Store>,
+) -> Result<RestorePreview, String> {
+    require_unlocked(&store)?;
+    let backup = read_backup(&passphrase, &path)?;
+    Ok(preview(&store.read(), &backup.data, mode))
+}
+
+/// Only the settings are written here. Expenses and categories are restored on the
+/// server by the caller, from the returned changes, and reach this device's copy from
+/// there; writing them here too would be overwritten by the next refresh.
+#[tauri::command]
+pub fn restore_backup(
+    passphrase: String,
+    path: String,
+    mode: RestoreMode,
+    store: State<'_, Store>,
+) -> Result<RestorePreview, String> {
+    require_unlocked(&store)?;
+    let backup = read_backup(&passphrase, &path)?;
+    let current = store.read();
+    let changes = preview(&current, &backup.data, mode);
+    let settings = merge_settings(&current.settings, &backup.data.settings, mode);
+    store.update(|data| data.settings = settings)?;
+    Ok(changes)
+}
@@ -1,35 +1,112 @@
 use std::sync::{Arc, Mutex};
 
+use core_api::session::Session;
+use lock::require_unlocked;
+use notifications::LocalOffset;
+use store::Store;
 // Learn more about Tauri commands at https://tauri.app/v1/guides/features/command
-use tauri::State;
-use time::{format_description, OffsetDateTime};
+use tauri::{Manager, State};
+use time::format_description;
+
+mod backup;
+mod lock;
+mod notifications;
+mod store;
 
 #[tauri::command]
-fn get_now_date() -> String {
-    let now_odt = OffsetDateTime::now_utc();
+fn get_now_date(offset: State<'_, LocalOffset>) -> String {
     let format = format_description::parse("[year]-[month]-[day]").unwrap();
-    now_odt.date().format(&format).unwrap()
+    offset.today().format(&format).unwrap()
 }
 
 #[tauri::command]
-fn get_token(token: State<'_, Token>) -> String {
-    token.0.lock().unwrap().to_string()
+fn get_token(token: State<'_, Token>, store: State<'_, Store>) -> Result<String, String> {
+    require_unlocked(&store)?;
+    Ok(token.0.lock().unwrap().to_string())
 }
+// The session is saved next to the local data so the command-line client can reuse it.
 #[tauri::command(rename_all = "snake_case")]
-fn set_token(new_token: String, token: State<'_, Token>) -> String {
-    *token.0.lock().unwrap() = new_token;
-    "Successfully updated!".to_owned()
+fn set_token(
+    new_token: String,
+    email: Option<String>,
+    token: State<'_, Token>,
+    store: State<'_, Store>,
+) -> Result<String, String> {
+    require_unlocked(&store)?;
+    *token.0.lock().unwrap() = new_token.clone();
+    let session = Session {
+        token: new_token,
+        email,
+    };
+    Ok(match store.write_session(&session) {
+        Ok(_) => "Successfully updated!".to_owned(),
+        Err(e) => format!("Updated for this run only: {e}"),
+    })
+}
+
+#[tauri::command]
+fn get_session(token: State<'_, Token>, store: State<'_, Store>) -> Result<Session, String> {
+    require_unlocked(&store)?;
+    let token = token.0.lock().unwrap().to_string();
+    let email = store.read_session().email;
+    Ok(Session { token, email })
+}
+
+// Settings stay on the device; everything fetched for the account goes with the session.
+#[tauri::command]
+fn logout(token: State<'_, Token>, store: State<'_, Store>) -> Result<(), String> {
+    require_unlocked(&store)?;
+    token.0.lock().unwrap().clear();
+    store.write_session(&Session::default())?;
+    store.update(|data| {
+        data.expenses.clear();
+        data.categories.clear();
+    })?;
+    store.update_trash(|trash| trash.clear())
 }
 
 #[derive(Default)]
 struct Token(Arc<Mutex<String>>);
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let offset = LocalOffset::detect();
     tauri::Builder::default()
         .manage(Token(Default::default()))
+        .manage(offset)
+        .setup(|app| {
+            let store = Store::load(app.path().app_data_dir()?)?;
+            *app.state::<Token>().0.lock().unwrap() = store.read_session().token;
+            app.manage(store);
+            notifications::watch(app.handle().clone());
+            Ok(())
+        })
         .plugin(tauri_plugin_window::init())
         .plugin(tauri_plugin_shell::init())
-        .invoke_handler(tauri::generate_handler![get_now_date, get_token, set_token])
+        .plugin(tauri_plugin_notification::init())
+        .invoke_handler(tauri::generate_handler![
+            get_now_date,
+            get_token,
+            set_token,
+            get_session,
+            logout,
+            store::get_settings,
+            store::set_settings,
+            store::cache_expenses,
+            store::cache_categories,
+            store::get_trash,
+            store::add_to_trash,
+            store::remove_from_trash,
+            backup::create_backup,
+            backup::preview_restore,
+            backup::restore_backup,
+            lock::lock_status,
+            lock::lock_screen_settings,
+            lock::unlock,
+            lock::lock_app,
+            lock::set_pin,
+            lock::clear_pin,
+            notifications::check_notifications
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,177 @@
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use chacha20poly1305::aead::{rand_core::RngCore, OsRng};
+use core_api::{
+    lock::{backoff_seconds, valid_pin, LockStatus, UnlockResult},
+    models::Settings,
+};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use time::OffsetDateTime;
+
+use crate::store::Store;
+
+const SALT_LEN: usize = 16;
+
+/// Only a salted Argon2 hash of the PIN is kept. Failures and the time of the next allowed
+/// try are saved too, so restarting the app does not reset the backoff.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PinLock {
+    hash: Option<String>,
+    failures: u32,
+    retry_at: i64,
+}
+
+impl PinLock {
+    pub fn enabled(&self) -> bool {
+        self.hash.is_some()
+    }
+
+    fn wait_seconds(&self) -> i64 {
+        (self.retry_at - OffsetDateTime::now_utc().unix_timestamp()).max(0)
+    }
+
+    fn status(&self) -> LockStatus {
+        LockStatus {
+            enabled: self.enabled(),
+            wait_seconds: self.wait_seconds(),
+        }
+    }
+}
+
+fn hash_pin(pin: &str) -> Result<String, String> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let salt = SaltString::encode_b64(&salt).map_err(|e| e.to_string())?;
+    Argon2::default()
+        .hash_password(pin.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Checks the PIN against the saved hash, counting failures. No PIN set always passes.
+fn check_pin(store: &Store, pin: &str) -> Result<UnlockResult, String> {
+    let pin_lock = store.pin_lock();
+    let Some(hash) = pin_lock.hash.as_deref() else {
+        return Ok(UnlockResult {
+            unlocked: true,
+            wait_seconds: 0,
+        });
+    };
+    let wait_seconds = pin_lock.wait_seconds();
+    if wait_seconds > 0 {
+        return Ok(UnlockResult {
+            unlocked: false,
+            wait_seconds,
+        });
+    }
+    let unlocked = PasswordHash::new(hash).is_ok_and(|hash| {
+        Argon2::default()
+            .verify_password(pin.as_bytes(), &hash)
+            .is_ok()
+    });
+    store.update_pin_lock(|pin_lock| {
+        if unlocked {
+            pin_lock.failures = 0;
+            pin_lock.retry_at = 0;
+        } else {
+            pin_lock.failures += 1;
+            pin_lock.retry_at =
+                OffsetDateTime::now_utc().unix_timestamp() + backoff_seconds(pin_lock.failures);
+        }
+    })?;
+    if unlocked {
+        store.set_unlocked(true);
+    }
+    Ok(UnlockResult {
+        unlocked,
+        wait_seconds: store.pin_lock().wait_seconds(),
+    })
+}
+
+/// For every command that hands out or replaces the session, the settings or the data,
+/// which the lock screen alone would not keep from a script calling them directly. Only
+/// the lock commands and [`lock_screen_settings`] stay open.
+pub fn require_unlocked(store: &Store) -> Result<(), String> {
+    if store.is_unlocked() {
+        Ok(())
+    } else {
+        Err("Unlock the app first.".to_owned())
+    }
+}
+
+fn require_pin(store: &Store, current: &str) -> Result<(), String> {
+    let checked = check_pin(store, current)?;
+    match checked {
+        UnlockResult { unlocked: true, .. } => Ok(()),
+        UnlockResult {
+            wait_seconds: 0, ..
+        } => Err("The current PIN is wrong.".to_owned()),
+        UnlockResult { wait_seconds, .. } => Err(format!(
+            "Too many wrong PINs, try again in {wait_seconds} seconds."
+        )),
+    }
+}
+
+/// Fails while the lock file is damaged, which leaves the app unlocked.
+#[tauri::command]
+pub fn lock_status(store: State<'_, Store>) -> Result<LockStatus, String> {
+    match store.lock_problem() {
+        Some(problem) => Err(problem),
+        None => Ok(store.pin_lock().status()),
+    }
+}
+
+/// The language and theme, so the lock screen looks like the rest of the app before the
+/// PIN is entered. Nothing else in the settings is handed out.
+#[tauri::command]
+pub fn lock_screen_settings(store: State<'_, Store>) -> Settings {
+    let settings = store.read().settings;
+    Settings {
+        language: settings.language,
+        theme: settings.theme,
+        ..Settings::default()
+    }
+}
+
+/// Called when the app locks itself, after the idle time or from the settings.
+#[tauri::command]
+pub fn lock_app(store: State<'_, Store>) {
+    if store.pin_lock().enabled() {
+        store.set_unlocked(false);
+    }
+}
+
+#[tauri::command]
+pub fn unlock(pin: String, store: State<'_, Store>) -> Result<UnlockResult, String> {
+    check_pin(&store, &pin)
+}
+
+/// Sets or changes the PIN; changing it needs the current one.
+#[tauri::command]
+pub fn set_pin(pin: String, current: String, store: State<'_, Store>) -> Result<(), String> {
+    if !valid_pin(&pin) {
+        return Err("The PIN must be 4 to 8 digits.".to_owned());
+    }
+    require_pin(&store, &current)?;
+    let hash = hash_pin(&pin)?;
+    store.update_pin_lock(|pin_lock| {
+        *pin_lock = PinLock {
+            hash: Some(hash),
+            ..Default::default()
+        }
+    })?;
+    store.set_unlocked(true);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn clear_pin(current: String, store: State<'_, Store>) -> Result<(), String> {
+    require_pin(&store, &current)?;
+    store.update_pin_lock(|pin_lock| *pin_lock = PinLock::default())?;
+    store.set_unlocked(true);
+    Ok(())
+}
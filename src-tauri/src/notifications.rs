@@ -0,0 +1,81 @@
+use std::{sync::Mutex, thread, time::Duration};
+
+use core_api::alerts::{evaluate, Alert};
+use tauri::{AppHandle, Manager, Runtime, State};
+use tauri_plugin_notification::NotificationExt;
+use time::{Date, OffsetDateTime, UtcOffset};
+
+use crate::store::Store;
+
+const CHECK_EVERY: Duration = Duration::from_secs(60 * 60);
+
+/// The device's UTC offset. `OffsetDateTime::now_local` fails once a Unix process has a
+/// second thread, which a Tauri app always has, so the offset is read at startup before
+/// any thread exists and then kept current by the webview, which always knows it.
+pub struct LocalOffset(Mutex<UtcOffset>);
+
+impl LocalOffset {
+    /// Call before the first thread is spawned.
+    pub fn detect() -> Self {
+        LocalOffset(Mutex::new(
+            UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC),
+        ))
+    }
+
+    pub fn set_minutes(&self, minutes: i32) {
+        if let Ok(offset) = UtcOffset::from_whole_seconds(minutes * 60) {
+            *self.0.lock().unwrap() = offset;
+        }
+    }
+
+    pub fn today(&self) -> Date {
+        OffsetDateTime::now_utc()
+            .to_offset(*self.0.lock().unwrap())
+            .date()
+    }
+}
+
+/// Shows every alert that is due and has not been shown before.
+fn notify_due<R: Runtime>(app: &AppHandle<R>, store: &Store) -> Result<Vec<Alert>, String> {
+    let data = store.read();
+    let today = app.state::<LocalOffset>().today();
+    let due: Vec<Alert> = evaluate(&data.settings, &data.expenses, today)
+        .into_iter()
+        .filter(|alert| !store.was_notified(&alert.key))
+        .collect();
+    for alert in &due {
+        app.notification()
+            .builder()
+            .title(&alert.title)
+            .body(&alert.body)
+            .show()
+            .map_err(|e| e.to_string())?;
+        store.mark_notified(alert.key.clone())?;
+    }
+    Ok(due)
+}
+
+/// Bills become due while the app sits idle, so the rules are also checked periodically.
+pub fn watch<R: Runtime>(app: AppHandle<R>) {
+    thread::spawn(move || loop {
+        thread::sleep(CHECK_EVERY);
+        let store = app.state::<Store>();
+        if let Err(e) = notify_due(&app, &store) {
+            eprintln!("could not check notifications: {e}");
+        }
+    });
+}
+
+/// `offset_minutes` is the webview's UTC offset, east positive.
+#[tauri::command(rename_all = "snake_case")]
+pub fn check_notifications<R: Runtime>(
+    offset_minutes: Option<i32>,
+    app: AppHandle<R>,
+    offset: State<'_, LocalOffset>,
+    store: State<'_, Store>,
+) -> Result<Vec<Alert>, String> {
+    if let Some(minutes) = offset_minutes {
+        offset.set_minutes(minutes);
+    }
+    notify_due(&app, &store)
+}
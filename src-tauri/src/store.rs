@@ -0,0 +1,234 @@
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use core_api::{
+    backup::LocalData,
+    models::{Category, Expense, Settings, TrashedExpense},
+    session::{Session, DATA_FILE, SESSION_FILE},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use tauri::State;
+use time::{format_description::well_known::Rfc3339, Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::lock::{require_unlocked, PinLock};
+
+// Kept apart from the local data so restoring a backup does not repeat notifications.
+const NOTIFIED_FILE: &str = "notified.json";
+// Deleted expenses stay recoverable on this device; they are not part of backups.
+const TRASH_FILE: &str = "trash.json";
+const TRASH_DAYS: i64 = 30;
+// The PIN is a device setting: it is neither backed up nor cleared on logout.
+const LOCK_FILE: &str = "lock.json";
+// A damaged lock file is kept here for inspection when a new PIN replaces it.
+const DAMAGED_LOCK_FILE: &str = "lock.json.damaged";
+
+fn read_json<T: DeserializeOwned + Default>(path: &Path) -> io::Result<T> {
+    match fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Like the lock file, a damaged file does not keep the app from starting: it is moved
+/// aside as `<name>.damaged` for inspection and the app goes on as if it were missing.
+fn read_json_or_set_aside<T: DeserializeOwned + Default>(path: &Path) -> T {
+    read_json(path).unwrap_or_else(|e| {
+        let damaged = path.with_extension("json.damaged");
+        eprintln!(
+            "{} could not be read and was moved to {}: {e}",
+            path.display(),
+            damaged.display()
+        );
+        if let Err(e) = fs::rename(path, &damaged) {
+            eprintln!("Could not move {}: {e}", path.display());
+        }
+        T::default()
+    })
+}
+
+// Written to a temporary file first so a crash never leaves half a JSON document.
+fn write_json<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let temporary = path.with_extension("json.tmp");
+    fs::write(&temporary, serde_json::to_vec_pretty(value)?)?;
+    fs::rename(temporary, path)
+}
+
+pub struct Store {
+    dir: PathBuf,
+    data: Mutex<LocalData>,
+    notified: Mutex<HashSet<String>>,
+    trash: Mutex<Vec<TrashedExpense>>,
+    pin_lock: Mutex<PinLock>,
+    /// Why the lock file could not be read, until a new PIN is set or the PIN is cleared.
+    lock_problem: Mutex<Option<String>>,
+    /// Whether the app is open: no PIN, or the right one was entered since it last locked.
+    unlocked: AtomicBool,
+}
+
+impl Store {
+    /// A damaged lock file fails open: the app starts without a PIN and says so. Deleting
+    /// the file has the same effect, so failing closed would lock out the owner without
+    /// keeping out anyone able to change the file.
+    pub fn load(dir: PathBuf) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let (pin_lock, lock_problem) = match read_json::<PinLock>(&dir.join(LOCK_FILE)) {
+            Ok(pin_lock) => (pin_lock, None),
+            Err(e) => {
+                let problem = format!(
+                    "The PIN lock could not be read and is off until a new PIN is set: {e}"
+                );
+                eprintln!("{problem}");
+                (PinLock::default(), Some(problem))
+            }
+        };
+        Ok(Store {
+            data: Mutex::new(read_json_or_set_aside(&dir.join(DATA_FILE))),
+            notified: Mutex::new(read_json_or_set_aside(&dir.join(NOTIFIED_FILE))),
+            trash: Mutex::new(read_json_or_set_aside(&dir.join(TRASH_FILE))),
+            unlocked: AtomicBool::new(!pin_lock.enabled()),
+            pin_lock: Mutex::new(pin_lock),
+            lock_problem: Mutex::new(lock_problem),
+            dir,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn read(&self) -> LocalData {
+        self.data.lock().unwrap().clone()
+    }
+
+    pub fn update<F: FnOnce(&mut LocalData)>(&self, change: F) -> Result<(), String> {
+        let mut data = self.data.lock().unwrap();
+        change(&mut data);
+        write_json(&self.dir.join(DATA_FILE), &*data).map_err(|e| e.to_string())
+    }
+
+    /// A damaged session file only means logging in again.
+    pub fn read_session(&self) -> Session {
+        read_json_or_set_aside(&self.dir.join(SESSION_FILE))
+    }
+
+    pub fn write_session(&self, session: &Session) -> Result<(), String> {
+        write_json(&self.dir.join(SESSION_FILE), session).map_err(|e| e.to_string())
+    }
+
+    pub fn was_notified(&self, key: &str) -> bool {
+        self.notified.lock().unwrap().contains(key)
+    }
+
+    pub fn trash(&self) -> Vec<TrashedExpense> {
+        self.trash.lock().unwrap().clone()
+    }
+
+    pub fn update_trash<F: FnOnce(&mut Vec<TrashedExpense>)>(
+        &self,
+        change: F,
+    ) -> Result<(), String> {
+        let mut trash = self.trash.lock().unwrap();
+        change(&mut trash);
+        let oldest = (OffsetDateTime::now_utc() - Duration::days(TRASH_DAYS))
+            .format(&Rfc3339)
+            .unwrap_or_default();
+        trash.retain(|trashed| trashed.deleted_at >= oldest);
+        write_json(&self.dir.join(TRASH_FILE), &*trash).map_err(|e| e.to_string())
+    }
+
+    pub fn pin_lock(&self) -> PinLock {
+        self.pin_lock.lock().unwrap().clone()
+    }
+
+    pub fn update_pin_lock<F: FnOnce(&mut PinLock)>(&self, change: F) -> Result<(), String> {
+        let mut pin_lock = self.pin_lock.lock().unwrap();
+        change(&mut pin_lock);
+        let path = self.dir.join(LOCK_FILE);
+        if self.lock_problem.lock().unwrap().take().is_some() {
+            // Best effort: the file is about to be replaced anyway.
+            let _ = fs::rename(&path, self.dir.join(DAMAGED_LOCK_FILE));
+        }
+        write_json(&path, &*pin_lock).map_err(|e| e.to_string())
+    }
+
+    pub fn lock_problem(&self) -> Option<String> {
+        self.lock_problem.lock().unwrap().clone()
+    }
+
+    pub fn is_unlocked(&self) -> bool {
+        self.unlocked.load(Ordering::SeqCst)
+    }
+
+    pub fn set_unlocked(&self, unlocked: bool) {
+        self.unlocked.store(unlocked, Ordering::SeqCst);
+    }
+
+    pub fn mark_notified(&self, key: String) -> Result<(), String> {
+        let mut notified = self.notified.lock().unwrap();
+        notified.insert(key);
+        write_json(&self.dir.join(NOTIFIED_FILE), &*notified).map_err(|e| e.to_string())
+    }
+}
+
+#[tauri::command]
+pub fn get_settings(store: State<'_, Store>) -> Result<Settings, String> {
+    require_unlocked(&store)?;
+    Ok(store.read().settings)
+}
+
+#[tauri::command]
+pub fn set_settings(settings: Settings, store: State<'_, Store>) -> Result<(), String> {
+    require_unlocked(&store)?;
+    store.update(|data| data.settings = settings)
+}
+
+#[tauri::command]
+pub fn cache_expenses(expenses: Vec<Expense>, store: State<'_, Store>) -> Result<(), String> {
+    require_unlocked(&store)?;
+    store.update(|data| data.expenses = expenses)
+}
+
+#[tauri::command]
+pub fn cache_categories(categories: Vec<Category>, store: State<'_, Store>) -> Result<(), String> {
+    require_unlocked(&store)?;
+    store.update(|data| data.categories = categories)
+}
+
+#[tauri::command]
+pub fn get_trash(store: State<'_, Store>) -> Result<Vec<TrashedExpense>, String> {
+    require_unlocked(&store)?;
+    let mut trash = store.trash();
+    trash.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    Ok(trash)
+}
+
+#[tauri::command]
+pub fn add_to_trash(expense: Expense, store: State<'_, Store>) -> Result<(), String> {
+    require_unlocked(&store)?;
+    let deleted_at = OffsetDateTime::now_utc()
+        .format(&Rfc3339)
+        .map_err(|e| e.to_string())?;
+    store.update_trash(|trash| {
+        trash.retain(|trashed| trashed.expense.id != expense.id);
+        trash.push(TrashedExpense {
+            expense,
+            deleted_at,
+        });
+    })
+}
+
+#[tauri::command]
+pub fn remove_from_trash(id: Uuid, store: State<'_, Store>) -> Result<(), String> {
+    require_unlocked(&store)?;
+    store.update_trash(|trash| trash.retain(|trashed| trashed.expense.id != id))
+}
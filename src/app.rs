@@ -1,149 +1,292 @@
-use crate::components::{credentials_form::CredentialsForm, expense_form::ExpenseForm};
-use crate::utils::{error_alert, get_headers, API_URL};
-use core_api::icons::{BillIcon, ListIcon, RefreshIcon, UserIcon};
-use core_api::models::{ApiResponse, Category, Expense};
+use crate::components::{
+    alerts_editor::AlertsEditor,
+    backup_panel::BackupPanel,
+    duplicates_review::DuplicatesReview,
+    expense_detail::ExpenseDetail,
+    expense_form::ExpenseForm,
+    expense_list::ExpenseList,
+    filter_bar::FilterBar,
+    forecast_panel::ForecastPanel,
+    import_form::ImportForm,
+    journal_export::JournalExport,
+    list_controls::ListControls,
+    lock_screen::LockScreen,
+    password_reset_form::PasswordResetForm,
+    pin_editor::PinEditor,
+    register_form::RegisterForm,
+    reports::Reports,
+    rules_editor::RulesEditor,
+    session_panel::SessionPanel,
+    settings_form::SettingsForm,
+    theme_editor::{apply_theme, ThemeEditor},
+    trash::TrashView,
+};
+use crate::i18n::{provide_i18n, Key};
+use crate::lock::{provide_lock, LockState};
+use crate::store::provide_store;
+use crate::toasts::{provide_toaster, ToastStack};
+use core_api::filters::ExpenseFilter;
+use core_api::icons::{BillIcon, ChartIcon, ListIcon, RefreshIcon, SettingsIcon, UserIcon};
+use core_api::paging::{list_from_params, list_query_string, ExpensePage};
+use core_api::sorting::ListOptions;
 use leptos::error::Result;
 use leptos::*;
-use serde::{de::DeserializeOwned, Serialize};
+use leptos_router::{
+    use_location, use_navigate, use_query_map, NavigateOptions, ParamsMap, Redirect, Route, Router,
+    Routes, A,
+};
+use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
 
-use crate::components::expense_card::ExpenseCard;
-
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "primitives"])]
     pub async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "primitives"], js_name = invoke, catch)]
+    pub async fn try_invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
 }
-async fn fetch_all<T: Serialize + Clone + DeserializeOwned>(section: &str) -> Result<T> {
-    Ok(
-        reqwasm::http::Request::get(&format!("{}/{section}?all_listed=true", API_URL))
-            .headers(get_headers().await)
-            .send()
-            .await?
-            .json::<ApiResponse<T>>()
-            .await?
-            .data(),
-    )
+const LAST_ROUTE_KEY: &str = "last_route";
+
+fn query_params(query: &ParamsMap) -> HashMap<String, String> {
+    query
+        .0
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect()
 }
 
-#[derive(Default, Clone)]
-enum TabOpt {
-    #[default]
-    Session,
-    NewExpense,
-    AllExpenses,
+/// Where the app was last closed, so it reopens on the same screen.
+fn last_route() -> String {
+    window()
+        .local_storage()
+        .ok()
+        .flatten()
+        .and_then(|storage| storage.get_item(LAST_ROUTE_KEY).ok().flatten())
+        .filter(|route| route.starts_with('/') && route != "/")
+        .unwrap_or_else(|| "/session".to_owned())
+}
+
+#[component]
+fn route_memory() -> impl IntoView {
+    let location = use_location();
+    create_effect(move |_| {
+        let route = format!("{}{}", location.pathname.get(), location.search.get());
+        if let Ok(Some(storage)) = window().local_storage() {
+            _ = storage.set_item(LAST_ROUTE_KEY, &route);
+        }
+    });
 }
 
 #[component]
 pub fn App() -> impl IntoView {
-    let (expenses, set_expenses) = create_signal::<Vec<Expense>>(vec![]);
-    let (current_tab, set_current_tab) = create_signal(TabOpt::default());
-    let (categories, set_categories) = create_signal::<Vec<Category>>(vec![]);
+    provide_toaster();
+    let store = provide_store();
+    let i18n = provide_i18n(store.settings());
+    let lock = provide_lock(store.settings());
+    // The backend refuses the session, settings and data while locked.
+    let loaded = store_value(false);
+    create_effect(move |_| {
+        if lock.state().get() == LockState::Unlocked && !loaded.get_value() {
+            loaded.set_value(true);
+            store.load();
+        }
+    });
+    create_effect(move |_| apply_theme(store.settings().with(|settings| settings.theme)));
+    // Mirrors the query of `/expenses`, and lives here so it survives leaving the list.
+    let (filter, set_filter) = create_signal(ExpenseFilter::default());
+    let (list_options, set_list_options) = create_signal(ListOptions::default());
+    let (page, set_page) = create_signal(ExpensePage::default());
 
-    let fetch_categories = move || {
-        spawn_local(async move {
-            match fetch_all::<Vec<Category>>("categories").await {
-                Ok(categories) => {
-                    set_categories.set(categories);
-                }
-                Err(e) => error_alert(e),
-            }
-        });
+    let expenses_href = move || {
+        let query = list_query_string(&filter.get(), &list_options.get());
+        if query.is_empty() {
+            "/expenses".to_owned()
+        } else {
+            format!("/expenses?{query}")
+        }
     };
 
-    let fetch_expenses = move || {
-        spawn_local(async move {
-            match fetch_all::<Vec<Expense>>("expenses").await {
-                Ok(expenses) => {
-                    set_expenses.set(expenses);
-                }
-                Err(e) => error_alert(e),
-            }
-        });
+    let session_view = move || {
+        view! {
+            <SessionPanel/>
+            <br />
+        }
     };
-    let refresh_data = move || {
-        fetch_categories();
-        fetch_expenses();
+    let new_expense_view = move || {
+        view! {
+        <div class="container" >
+            <button on:click=move|_|store.refresh()>{move||i18n.t(Key::Refetch)}
+                <RefreshIcon  class=None/>
+            </button>
+        </div>
+        <br />
+        <ExpenseForm/>
+        <br />
+        <ImportForm/>
+        <br />
+         }
     };
-    fetch_categories();
-    fetch_expenses();
-
     let expenses_view = move || {
-        expenses
-            .get()
-            .into_iter()
-            .map(|expense| {
-                view! {
-                    <ExpenseCard expense={expense} fetch_expenses={fetch_expenses} />
-                }
-            })
-            .collect_view()
+        // The URL holds the filters and order, so links, back and forward restore them.
+        let query = use_query_map();
+        let navigate = store_value(use_navigate());
+        let own_navigations = store_value(0);
+        create_effect(move |_| {
+            let (url_filter, url_options) = list_from_params(&query_params(&query.get()));
+            if own_navigations.get_value() > 0 {
+                own_navigations.update_value(|count| *count -= 1);
+                return;
+            }
+            if filter.get_untracked() != url_filter {
+                set_filter.set(url_filter);
+            }
+            if list_options.get_untracked() != url_options {
+                set_list_options.set(url_options);
+            }
+        });
+        create_effect(move |_| {
+            let href = expenses_href();
+            let url = list_from_params(&query_params(&query.get_untracked()));
+            if url != (filter.get_untracked(), list_options.get_untracked()) {
+                own_navigations.update_value(|count| *count += 1);
+                let options = NavigateOptions {
+                    replace: true,
+                    scroll: false,
+                    ..NavigateOptions::default()
+                };
+                navigate.with_value(|navigate| navigate(&href, options));
+            }
+        });
+        view! {
+        <div class="container" >
+            <button on:click=move|_|store.refresh()>{move||i18n.t(Key::Refetch)}
+                <RefreshIcon  class=None/>
+            </button>
+        </div>
+        <div class="container" >
+            <A href="/expenses/duplicates">{move||i18n.t(Key::ReviewDuplicates)}</A>
+            <A href="/expenses/trash">{move||i18n.t(Key::RecentlyDeleted)}</A>
+        </div>
+        <FilterBar filter={filter} set_filter={set_filter} page={page}/>
+        <ListControls options={list_options} set_options={set_list_options}/>
+        <ExpenseList filter={filter} options={list_options} page={page} set_page={set_page}/>
+        }
     };
-
-    let select_tab = move |tab: TabOpt| {
-        set_current_tab.set(tab);
+    let duplicates_view = move || {
+        view! {
+        <div class="container" >
+            <A href=expenses_href>{move||i18n.t(Key::BackToExpenses)}</A>
+        </div>
+        <DuplicatesReview/>
+        }
     };
-
-    let app_content = move || match current_tab.get() {
-        TabOpt::Session => {
-            view! {
-                <CredentialsForm refresh_data={refresh_data}/>
-                <br />
-            }
+    let trash_view = move || {
+        view! {
+        <div class="container" >
+            <A href=expenses_href>{move||i18n.t(Key::BackToExpenses)}</A>
+        </div>
+        <TrashView/>
         }
-        TabOpt::NewExpense => {
-            view! {
-            <div class="container" >
-                <button on:click=move|_|{refresh_data()}>"Refetch"
-                    <RefreshIcon  class=None/>
-                </button>
-            </div>
-            <br />
-            <ExpenseForm categories={categories} set_expenses={set_expenses}/>
-            <br />
-             }
+    };
+    let expense_view = move || {
+        view! { <ExpenseDetail back_href=Signal::derive(expenses_href)/> }
+    };
+    let reports_view = move || {
+        let query = use_query_map();
+        let navigate = store_value(use_navigate());
+        let month = Signal::derive(move || query.with(|query| query.get("month").cloned()));
+        let set_month = move |month: Option<String>| {
+            let href = month.map_or("/reports".to_owned(), |month| {
+                format!("/reports?month={month}")
+            });
+            navigate.with_value(|navigate| navigate(&href, NavigateOptions::default()));
+        };
+        view! {
+        <ForecastPanel/>
+        <br />
+        <Reports month={month} set_month={set_month}/>
+        <br />
         }
-        TabOpt::AllExpenses => {
-            view! {
-            <div class="container" >
-                <button on:click=move|_|{refresh_data()}>"Refetch"
-                    <RefreshIcon  class=None/>
-                </button>
-            </div>
-            <ul>
-                {expenses_view}
-            </ul>
-
-            }
+    };
+    let settings_view = move || {
+        view! {
+        <SettingsForm/>
+        <br />
+        <ThemeEditor/>
+        <br />
+        <PinEditor/>
+        <br />
+        <RulesEditor/>
+        <br />
+        <AlertsEditor/>
+        <br />
+        <BackupPanel/>
+        <br />
+        <JournalExport/>
+        <br />
         }
     };
 
-    // let test_func=move|e|{
-    //     navi
-    // };
-
     view! {
-        <main>
-            <div class="app-content">
-            // <button on:click=test_func>Test</button>
-                {app_content}
-            </div>
-            <nav>
-                <ul>
-                    <li class="nav-container" on:click=move|_|select_tab(TabOpt::Session)>
-                        <UserIcon  class=None/>
-                        Session
-                    </li>
-                    <li class="nav-container" on:click=move|_|select_tab(TabOpt::NewExpense)>
-                        <BillIcon class=None/>
-                        New Expense
-                    </li>
-                    <li class="nav-container" on:click=move|_|select_tab(TabOpt::AllExpenses)>
-                        <ListIcon  class=None/>
-                        All Expense
-                    </li>
-                </ul>
-            </nav>
-        </main>
+        <Router>
+            <main>
+                <div class="app-content">
+                    <Routes>
+                        <Route path="/" view=|| view! { <Redirect path={last_route()}/> }/>
+                        <Route path="/session" view=session_view/>
+                        <Route path="/session/register" view=RegisterForm/>
+                        <Route path="/session/reset" view=PasswordResetForm/>
+                        <Route path="/expenses" view=expenses_view/>
+                        <Route path="/expenses/new" view=new_expense_view/>
+                        <Route path="/expenses/duplicates" view=duplicates_view/>
+                        <Route path="/expenses/trash" view=trash_view/>
+                        <Route path="/expenses/:id" view=expense_view/>
+                        <Route path="/reports" view=reports_view/>
+                        <Route path="/settings" view=settings_view/>
+                        <Route path="/*any" view=|| view! { <Redirect path="/session"/> }/>
+                    </Routes>
+                </div>
+                <Show when=move||lock.state().get() != LockState::Unlocked>
+                    <LockScreen/>
+                </Show>
+                <ToastStack/>
+                <nav>
+                    <RouteMemory/>
+                    <ul>
+                        <li>
+                            <A href="/session" class="nav-container" active_class="active">
+                                <UserIcon  class=None/>
+                                {move||i18n.t(Key::Session)}
+                            </A>
+                        </li>
+                        <li>
+                            <A href="/expenses/new" class="nav-container" active_class="active">
+                                <BillIcon class=None/>
+                                {move||i18n.t(Key::NewExpense)}
+                            </A>
+                        </li>
+                        <li>
+                            <A href=expenses_href class="nav-container" active_class="active" exact=true>
+                                <ListIcon  class=None/>
+                                {move||i18n.t(Key::AllExpenses)}
+                            </A>
+                        </li>
+                        <li>
+                            <A href="/reports" class="nav-container" active_class="active">
+                                <ChartIcon  class=None/>
+                                {move||i18n.t(Key::Reports)}
+                            </A>
+                        </li>
+                        <li>
+                            <A href="/settings" class="nav-container" active_class="active">
+                                <SettingsIcon  class=None/>
+                                {move||i18n.t(Key::Settings)}
+                            </A>
+                        </li>
+                    </ul>
+                </nav>
+            </main>
+        </Router>
     }
 }
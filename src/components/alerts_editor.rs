@@ -0,0 +1,294 @@
+use core_api::{
+    alerts::{Alert, Budget, NotificationRule, RecurringBill, Trigger},
+    models::Settings,
+};
+use leptos::{component, ev::MouseEvent, *};
+use uuid::Uuid;
+
+use crate::{
+    store::{use_store, Store},
+    utils::{call, error_toast, utc_offset_minutes},
+};
+
+fn category_named(store: Store, id: Uuid) -> String {
+    store
+        .category(id)
+        .map(|category| category.name)
+        .unwrap_or_default()
+}
+
+fn update_settings(store: Store, change: impl FnOnce(&mut Settings)) {
+    store.update_settings(change);
+    spawn_local(async move {
+        if let Err(e) = store.save_settings().await {
+            return error_toast(e);
+        }
+        if let Err(e) = call::<Vec<Alert>>(
+            "check_notifications",
+            serde_json::json!({ "offset_minutes": utc_offset_minutes() }),
+        )
+        .await
+        {
+            error_toast(e);
+        }
+    });
+}
+
+#[component]
+pub fn alerts_editor() -> impl IntoView {
+    let store = use_store();
+    let (settings, categories) = (store.settings(), store.categories());
+    let (budget, set_budget) = create_signal(Budget::default());
+    let (bill, set_bill) = create_signal(RecurringBill::default());
+    let (percent, set_percent) = create_signal(80u32);
+    let (days_before, set_days_before) = create_signal(3u32);
+    let (watched_category, set_watched_category) = create_signal(Uuid::default());
+    let (watched_bill, set_watched_bill) = create_signal(Uuid::default());
+
+    let add_budget = move |e: MouseEvent| {
+        e.prevent_default();
+        let mut budget = budget.get_untracked();
+        if budget.category_id == Uuid::default() || budget.monthly_limit <= 0 {
+            return;
+        }
+        budget.category_name = category_named(store, budget.category_id);
+        update_settings(store, move |settings| {
+            settings
+                .budgets
+                .retain(|existing| existing.category_id != budget.category_id);
+            settings.budgets.push(budget);
+        });
+        set_budget.set(Budget::default());
+    };
+    let add_bill = move |e: MouseEvent| {
+        e.prevent_default();
+        let mut bill = bill.get_untracked();
+        if bill.name.is_empty() || !(1..=31).contains(&bill.day_of_month) {
+            return;
+        }
+        bill.id = Uuid::new_v4();
+        bill.category_name = category_named(store, bill.category_id);
+        update_settings(store, move |settings| settings.bills.push(bill));
+        set_bill.set(RecurringBill::default());
+    };
+    let add_budget_alert = move |e: MouseEvent| {
+        e.prevent_default();
+        let category_id = watched_category.get_untracked();
+        if category_id == Uuid::default() {
+            return;
+        }
+        let rule = NotificationRule::new(Trigger::BudgetThreshold {
+            category_id,
+            percent: percent.get_untracked(),
+        });
+        update_settings(store, move |settings| {
+            settings.notification_rules.push(rule)
+        });
+    };
+    let add_bill_alert = move |e: MouseEvent| {
+        e.prevent_default();
+        let bill_id = watched_bill.get_untracked();
+        if bill_id == Uuid::default() {
+            return;
+        }
+        let rule = NotificationRule::new(Trigger::BillDue {
+            bill_id,
+            days_before: days_before.get_untracked(),
+        });
+        update_settings(store, move |settings| {
+            settings.notification_rules.push(rule)
+        });
+    };
+
+    let categories_view = move || {
+        categories
+            .get()
+            .iter()
+            .map(|category| {
+                view! {
+                <option value={category.id.to_string()}>
+                    <p>{category.name.clone()}</p>
+                </option>}
+            })
+            .collect_view()
+    };
+    let budgets_view = move || {
+        settings
+            .get()
+            .budgets
+            .into_iter()
+            .map(|budget| {
+                let category_id = budget.category_id;
+                view! {
+                    <li class="row">
+                        <p>{budget.category_name} ": $" {budget.monthly_limit} " a month"</p>
+                        <button on:click=move|_|update_settings(store, move |settings| {
+                            settings.budgets.retain(|budget| budget.category_id != category_id)
+                        })>"Delete"</button>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+    let bills_view = move || {
+        settings
+            .get()
+            .bills
+            .into_iter()
+            .map(|bill| {
+                let id = bill.id;
+                view! {
+                    <li class="row">
+                        <p>{bill.name} ": $" {bill.amount} " on day " {bill.day_of_month}</p>
+                        <button on:click=move|_|update_settings(store, move |settings| {
+                            settings.bills.retain(|bill| bill.id != id)
+                        })>"Delete"</button>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+    let bill_options = move || {
+        settings
+            .get()
+            .bills
+            .into_iter()
+            .map(|bill| view! { <option value={bill.id.to_string()}>{bill.name}</option> })
+            .collect_view()
+    };
+    let rules_view = move || {
+        let current = settings.get();
+        current
+            .notification_rules
+            .iter()
+            .map(|rule| {
+                let id = rule.id;
+                let enabled = rule.enabled;
+                let label = match &rule.trigger {
+                    Trigger::BudgetThreshold {
+                        category_id,
+                        percent,
+                    } => format!(
+                        "{} reaches {percent}% of its budget",
+                        category_named(store, *category_id)
+                    ),
+                    Trigger::BillDue {
+                        bill_id,
+                        days_before,
+                    } => format!(
+                        "{} is due within {days_before} days",
+                        current
+                            .bills
+                            .iter()
+                            .find(|bill| bill.id == *bill_id)
+                            .map(|bill| bill.name.clone())
+                            .unwrap_or_default()
+                    ),
+                };
+                view! {
+                    <li class="row">
+                        <label>
+                            <input
+                                type="checkbox"
+                                prop:checked=enabled
+                                on:change=move|_|update_settings(store, move |settings| {
+                                    if let Some(rule) = settings.notification_rules.iter_mut().find(|rule| rule.id == id) {
+                                        rule.enabled = !enabled;
+                                    }
+                                })
+                            />
+                            {label}
+                        </label>
+                        <button on:click=move|_|update_settings(store, move |settings| {
+                            settings.notification_rules.retain(|rule| rule.id != id)
+                        })>"Delete"</button>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+            <form class="container">
+               <p>"Monthly budgets"</p>
+               <ul>{budgets_view}</ul>
+               <div class="row" >
+                   <select
+                        on:change=move|ev|set_budget.update(|budget| budget.category_id = Uuid::parse_str(&event_target_value(&ev)).unwrap_or_default())
+                        prop:value={move||budget.get().category_id.to_string()}
+                        >
+                        <option value={Uuid::default().to_string()}>Select category</option>
+                        {categories_view}
+                   </select>
+                    <input
+                        type="number"
+                        placeholder="Monthly limit"
+                        on:input=move|ev|set_budget.update(|budget| budget.monthly_limit = event_target_value(&ev).parse().unwrap_or(0))
+                        prop:value={move||budget.get().monthly_limit}
+                    />
+               </div>
+               <button on:click=add_budget>"Set budget"</button>
+               <br />
+               <p>"Recurring bills"</p>
+               <ul>{bills_view}</ul>
+               <div class="row" >
+                    <input
+                        placeholder="Bill name"
+                        on:input=move|ev|set_bill.update(|bill| bill.name = event_target_value(&ev))
+                        prop:value={move||bill.get().name}
+                    />
+                    <input
+                        type="number"
+                        placeholder="Amount"
+                        on:input=move|ev|set_bill.update(|bill| bill.amount = event_target_value(&ev).parse().unwrap_or(0))
+                        prop:value={move||bill.get().amount}
+                    />
+                    <input
+                        type="number"
+                        min="1"
+                        max="31"
+                        placeholder="Day of month"
+                        on:input=move|ev|set_bill.update(|bill| bill.day_of_month = event_target_value(&ev).parse().unwrap_or(0))
+                        prop:value={move||bill.get().day_of_month}
+                    />
+               </div>
+                   <select
+                        on:change=move|ev|set_bill.update(|bill| bill.category_id = Uuid::parse_str(&event_target_value(&ev)).unwrap_or_default())
+                        prop:value={move||bill.get().category_id.to_string()}
+                        >
+                        <option value={Uuid::default().to_string()}>Select category</option>
+                        {categories_view}
+                   </select>
+               <button on:click=add_bill>"Add bill"</button>
+               <br />
+               <p>"Notifications"</p>
+               <ul>{rules_view}</ul>
+               <div class="row" >
+                   <select on:change=move|ev|set_watched_category.set(Uuid::parse_str(&event_target_value(&ev)).unwrap_or_default())>
+                        <option value={Uuid::default().to_string()}>Select category</option>
+                        {categories_view}
+                   </select>
+                    <input
+                        type="number"
+                        placeholder="Percent of budget"
+                        on:input=move|ev|set_percent.set(event_target_value(&ev).parse().unwrap_or(80))
+                        prop:value={move||percent.get()}
+                    />
+                   <button on:click=add_budget_alert>"Notify at budget %"</button>
+               </div>
+               <div class="row" >
+                   <select on:change=move|ev|set_watched_bill.set(Uuid::parse_str(&event_target_value(&ev)).unwrap_or_default())>
+                        <option value={Uuid::default().to_string()}>Select bill</option>
+                        {bill_options}
+                   </select>
+                    <input
+                        type="number"
+                        placeholder="Days before"
+                        on:input=move|ev|set_days_before.set(event_target_value(&ev).parse().unwrap_or(3))
+                        prop:value={move||days_before.get()}
+                    />
+                   <button on:click=add_bill_alert>"Notify before due"</button>
+               </div>
+            </form>
+    }
+}
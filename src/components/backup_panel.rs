@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use core_api::{
+    backup::{RestoreMode, RestorePreview},
+    error::AppError,
+    models::{ApiResponse, Category, Expense},
+};
+use leptos::{component, error::Result, ev::MouseEvent, *};
+use uuid::Uuid;
+use wasm_bindgen::JsValue;
+
+use crate::{
+    components::expense_card::remove_post,
+    store::{use_store, Store},
+    toasts::{toaster, ToastLevel},
+    utils::{api_url, call, error_toast, get_headers, response_errors},
+};
+
+/// Backups are made from, and compared with, this device's copy, so it has to hold every
+/// expense first.
+async fn cache_expenses(store: Store) -> Result<()> {
+    let expenses = store.all_expenses().await?;
+    call::<()>(
+        "cache_expenses",
+        serde_json::json!({ "expenses": expenses }),
+    )
+    .await
+}
+
+// Sent with its id, which servers that pick their own ids ignore.
+async fn post_category(category: Category) -> Result<Category> {
+    let resp = reqwasm::http::Request::post(&format!("{}/categories", api_url().await))
+        .headers(get_headers().await)
+        .body(JsValue::from_str(&serde_json::json!(category).to_string()))
+        .send()
+        .await?;
+    if resp.ok() {
+        return Ok(resp.json::<ApiResponse<Category>>().await?.data());
+    }
+    let problem = format!(
+        "Could not restore the category {}: {}",
+        category.name,
+        response_errors(resp).await.join(" ")
+    );
+    Err(AppError::HardError(problem).into())
+}
+
+/// Expenses and categories live on the server, so that is where they are restored; the
+/// device copy follows on the next refresh. Ids are kept, so a second restore of the same
+/// backup finds nothing new. Missing categories go first so every expense has one.
+async fn sync_restore(store: Store, changes: &RestorePreview) -> Result<()> {
+    let mut category_ids = HashMap::<Uuid, Uuid>::new();
+    for category in &changes.categories_added {
+        let created = post_category(category.clone()).await?;
+        category_ids.insert(category.id, created.id);
+    }
+    for expense in &changes.removed {
+        remove_post(expense.clone()).await?;
+    }
+    let with_category = |expense: &Expense| {
+        let mut expense = expense.clone();
+        if let Some(id) = category_ids.get(&expense.category_id) {
+            expense.category_id = *id;
+        }
+        expense
+    };
+    for expense in &changes.updated {
+        store.update_expense(with_category(expense)).await?;
+    }
+    for expense in &changes.added {
+        store.restore_expense(with_category(expense)).await?;
+    }
+    Ok(())
+}
+
+#[component]
+pub fn backup_panel() -> impl IntoView {
+    let store = use_store();
+    let (path, set_path) = create_signal(String::new());
+    let (passphrase, set_passphrase) = create_signal(String::new());
+    let (mode, set_mode) = create_signal(RestoreMode::default());
+    let (preview, set_preview) = create_signal(None::<RestorePreview>);
+
+    let update_path = move |ev| {
+        set_path.set(event_target_value(&ev));
+        set_preview.set(None);
+    };
+    let update_passphrase = move |ev| set_passphrase.set(event_target_value(&ev));
+    let update_mode = move |ev| {
+        let mode = match event_target_value(&ev).as_str() {
+            "replace" => RestoreMode::Replace,
+            _ => RestoreMode::Merge,
+        };
+        set_mode.set(mode);
+        set_preview.set(None);
+    };
+    let args = move || {
+        serde_json::json!({
+            "passphrase": passphrase.get_untracked(),
+            "path": path.get_untracked(),
+            "mode": mode.get_untracked(),
+        })
+    };
+
+    let create_backup = move |e: MouseEvent| {
+        e.prevent_default();
+        spawn_local(async move {
+            if let Err(e) = cache_expenses(store).await {
+                return error_toast(e);
+            }
+            match call::<String>("create_backup", args()).await {
+                Ok(saved_to) => {
+                    toaster().show(ToastLevel::Success, format!("Backup saved to {saved_to}"));
+                }
+                Err(e) => error_toast(e),
+            }
+        });
+    };
+    let preview_restore = move |e: MouseEvent| {
+        e.prevent_default();
+        spawn_local(async move {
+            if let Err(e) = cache_expenses(store).await {
+                return error_toast(e);
+            }
+            match call::<RestorePreview>("preview_restore", args()).await {
+                Ok(changes) => set_preview.set(Some(changes)),
+                Err(e) => error_toast(e),
+            }
+        });
+    };
+    let restore_backup = move |e: MouseEvent| {
+        e.prevent_default();
+        spawn_local(async move {
+            let changes = match call::<RestorePreview>("restore_backup", args()).await {
+                Ok(changes) => changes,
+                Err(e) => return error_toast(e),
+            };
+            set_preview.set(None);
+            match sync_restore(store, &changes).await {
+                Ok(_) => {
+                    toaster().show(ToastLevel::Success, "Backup restored.");
+                }
+                Err(e) => error_toast(e),
+            }
+            store.refresh();
+        });
+    };
+
+    let preview_view = move || {
+        preview.get().map(|changes| {
+            let expenses_list = |expenses: Vec<Expense>| {
+                expenses
+                    .into_iter()
+                    .map(|expense| {
+                        view! {
+                            <li>{expense.date.unwrap_or_default()} " " ${expense.amount} " " {expense.category_name}</li>
+                        }
+                    })
+                    .collect_view()
+            };
+            view! {
+                <div class="container">
+                    <p>
+                        {changes.added.len()} " added, " {changes.updated.len()} " updated, "
+                        {changes.removed.len()} " removed, " {changes.unchanged} " unchanged."
+                    </p>
+                    <p>
+                        {changes.categories_added.len()} " categories added, "
+                        {changes.categories_removed.len()} " removed."
+                        {changes.settings_changed.then_some(match mode.get_untracked() {
+                            RestoreMode::Replace => " Settings will be replaced, except the server URL.",
+                            RestoreMode::Merge => " Rules, budgets, bills, notifications and colors missing here will be added; other settings stay.",
+                        })}
+                    </p>
+                    <ul>
+                        {expenses_list(changes.added)}
+                        {expenses_list(changes.updated)}
+                        {expenses_list(changes.removed)}
+                    </ul>
+                    <button on:click=restore_backup>"Restore"</button>
+                </div>
+            }
+        })
+    };
+
+    view! {
+            <form class="container">
+               <div class="row" >
+                    <input
+                        id="backup-path-input"
+                        placeholder="Backup file (empty saves to the app folder)"
+                        on:input=update_path
+                        prop:value={move||path.get()}
+                    />
+               </div>
+               <br />
+               <div class="row" >
+                    <input
+                        id="backup-passphrase-input"
+                        type="password"
+                        placeholder="Backup passphrase"
+                        on:input=update_passphrase
+                        prop:value={move||passphrase.get()}
+                    />
+               </div>
+               <br />
+                   <select on:change=update_mode>
+                        <option value="merge">Merge into current data</option>
+                        <option value="replace">Replace current data</option>
+                   </select>
+               <br />
+               <button on:click=create_backup>"Create backup"</button>
+               <button on:click=preview_restore>"Preview restore"</button>
+               {preview_view}
+            </form>
+    }
+}
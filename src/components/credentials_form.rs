@@ -1,6 +1,9 @@
 use crate::{
-    app::invoke,
-    utils::{error_alert, get_headers, API_URL},
+    app::try_invoke,
+    i18n::{use_i18n, Key},
+    store::use_store,
+    toasts::{toaster, ToastLevel},
+    utils::{api_url, error_toast, get_headers},
 };
 use core_api::{
     error::AppError,
@@ -10,6 +13,7 @@ use leptos::{
     component, create_signal, error::Result, ev::SubmitEvent, event_target_value, logging,
     spawn_local, *,
 };
+use leptos_router::A;
 use serde_wasm_bindgen::to_value;
 use wasm_bindgen::JsValue;
 
@@ -22,31 +26,40 @@ async fn post_login(credentials: LoginBody) -> Result<LoginResponse> {
         .as_str(),
     );
 
-    let resp = reqwasm::http::Request::post(&format!("{}/login", API_URL))
+    let resp = reqwasm::http::Request::post(&format!("{}/login", api_url().await))
         .headers(get_headers().await)
         .body(value)
         .send()
         .await?;
     if resp.ok() {
         let res = resp.json::<ApiResponse<LoginResponse>>().await?.data();
-        let args = to_value(&SetTokenArgs {
-            new_token: res.token.clone(),
-        })
-        .unwrap();
-        match invoke("set_token", args).await.as_string() {
-            Some(msg) => logging::debug_warn!("{msg}",),
-            None => logging::debug_warn!("Error here -.-",),
-        };
+        save_token(res.token.clone(), credentials.email).await;
         return Ok(res);
     }
     Err(AppError::HardError(resp.text().await?).into())
 }
 
+/// Keeps the token for this and later runs, along with who it belongs to.
+pub async fn save_token(token: String, email: String) {
+    let args = to_value(&SetTokenArgs {
+        new_token: token,
+        email: Some(email),
+    })
+    .unwrap();
+    match try_invoke("set_token", args)
+        .await
+        .ok()
+        .and_then(|msg| msg.as_string())
+    {
+        Some(msg) => logging::debug_warn!("{msg}",),
+        None => logging::debug_warn!("Error here -.-",),
+    };
+}
+
 #[component]
-pub fn credentials_form<F>(refresh_data: F) -> impl IntoView
-where
-    F: Fn() + 'static + Copy,
-{
+pub fn credentials_form() -> impl IntoView {
+    let store = use_store();
+    let i18n = use_i18n();
     let (credentials, set_credentials) = create_signal(LoginBody::default());
     let update_email = move |ev| {
         let v = event_target_value(&ev);
@@ -57,35 +70,35 @@ where
         set_credentials.update(|credential| credential.password(v));
     };
 
-    let reset_session = move |ev: SubmitEvent| {
+    let log_in = move |ev: SubmitEvent| {
         ev.prevent_default();
         spawn_local(async move {
             let credentials = move || credentials.get_untracked();
 
-            if credentials().password.is_empty() {
-                return;
-            }
-            if credentials().email.is_empty() {
+            if credentials().email.is_empty() || credentials().password.is_empty() {
+                toaster().show(ToastLevel::Warning, i18n.t(Key::MissingCredentials));
                 return;
             }
 
             match post_login(credentials()).await {
                 Ok(res) => {
                     logging::debug_warn!("RESPONSE POST: {:?}", res);
-                    refresh_data();
+                    toaster().show(ToastLevel::Success, i18n.t(Key::SignedIn));
+                    store.load_session();
+                    store.refresh();
                 }
-                Err(e) => error_alert(e),
+                Err(e) => error_toast(e),
             }
         });
     };
 
     view! {
-            <form class="container" on:submit=reset_session>
+            <form class="container" on:submit=log_in>
                <div class="row" >
                     <input
                         id="email-input"
                         type="email"
-                        placeholder="Enter your email"
+                        placeholder=move||i18n.t(Key::EnterEmail)
                         on:input=update_email
                         prop:value={move||credentials.get().email}
                     />
@@ -95,13 +108,17 @@ where
                     <input
                         id="password-input"
                         type="password"
-                        placeholder="Enter your password."
+                        placeholder=move||i18n.t(Key::EnterPassword)
                         on:input=update_password
                         prop:value={move||credentials.get().password}
                     />
                </div>
                <br />
-               <button type="submit">"Reset session."</button>
+               <button type="submit">{move||i18n.t(Key::LogIn)}</button>
+               <div class="row">
+                    <A href="/session/register">{move||i18n.t(Key::CreateAccount)}</A>
+                    <A href="/session/reset">{move||i18n.t(Key::ForgotPassword)}</A>
+               </div>
             </form>
 
     }
@@ -0,0 +1,87 @@
+use core_api::{
+    duplicates::{duplicate_groups, is_duplicate},
+    models::Expense,
+};
+use leptos::{component, *};
+use uuid::Uuid;
+
+use crate::{
+    components::trash::delete_expense,
+    i18n::{use_i18n, Key},
+    store::use_store,
+    utils::error_toast,
+};
+
+#[component]
+pub fn duplicates_review() -> impl IntoView {
+    let store = use_store();
+    let i18n = use_i18n();
+    let (expenses, settings) = (store.expenses(), store.settings());
+    let groups = create_memo(move |_| {
+        duplicate_groups(&expenses.get(), settings.get().duplicate_criteria())
+    });
+
+    let remove_all = move |to_remove: Vec<Expense>| {
+        spawn_local(async move {
+            for expense in to_remove {
+                if let Err(e) = delete_expense(expense).await {
+                    error_toast(e);
+                    break;
+                }
+            }
+            store.reload_expenses();
+        });
+    };
+    // Merging keeps the chosen expense and deletes the rest of its group, but only those
+    // that are duplicates of the one kept: members are only sure to match the group's first.
+    let keep_only = move |group: Vec<Expense>, keep: Uuid| {
+        let criteria = settings.get_untracked().duplicate_criteria();
+        let Some(kept) = group.iter().find(|expense| expense.id == keep).cloned() else {
+            return;
+        };
+        remove_all(
+            group
+                .into_iter()
+                .filter(|expense| expense.id != keep && is_duplicate(&kept, expense, criteria))
+                .collect(),
+        );
+    };
+
+    let groups_view = move || {
+        groups
+            .get()
+            .into_iter()
+            .map(|group| {
+                let items = group
+                    .clone()
+                    .into_iter()
+                    .map(|expense| {
+                        let group = group.clone();
+                        let id = expense.id;
+                        let removed = expense.clone();
+                        view! {
+                            <li class="row">
+                                <p>
+                                    {i18n.date(&expense.date.unwrap_or_default())} " " {i18n.amount(expense.amount)} " "
+                                    {expense.category_name} " / " {expense.description.unwrap_or_default()}
+                                </p>
+                                <button on:click=move|_|keep_only(group.clone(), id)>{move||i18n.t(Key::KeepOnlyThis)}</button>
+                                <button on:click=move|_|remove_all(vec![removed.clone()])>{move||i18n.t(Key::Delete)}</button>
+                            </li>
+                        }
+                    })
+                    .collect_view();
+                view! { <ul class="warning">{items}</ul> }
+            })
+            .collect_view()
+    };
+
+    view! {
+            <div class="container">
+                <p>
+                    {move||i18n.t(Key::DuplicateGroups).replace("{count}", &groups.get().len().to_string())}
+                </p>
+                {groups_view}
+            </div>
+    }
+}
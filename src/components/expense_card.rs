@@ -1,17 +1,20 @@
-use crate::utils::{error_alert, get_headers, API_URL};
+use crate::i18n::use_i18n;
+use crate::store::use_store;
+use crate::utils::{api_url, get_headers};
 use core_api::error::AppError;
 use core_api::models::ApiResponse;
 use core_api::{icons::TrashIcon, models::Expense};
-use gloo::dialogs::alert;
 use leptos::error::Result;
 use leptos::ev::MouseEvent;
-use leptos::{component, spawn_local, view, IntoView};
+use leptos::{component, view, IntoView, SignalWith};
+use leptos_router::A;
 
-async fn remove_post(expense: Expense) -> Result<()> {
-    let resp = reqwasm::http::Request::delete(&format!("{}/expenses/{}", API_URL, expense.id))
-        .headers(get_headers().await)
-        .send()
-        .await?;
+pub async fn remove_post(expense: Expense) -> Result<()> {
+    let resp =
+        reqwasm::http::Request::delete(&format!("{}/expenses/{}", api_url().await, expense.id))
+            .headers(get_headers().await)
+            .send()
+            .await?;
     if resp.ok() {
         resp.json::<ApiResponse<bool>>().await?.data();
         return Ok(());
@@ -20,26 +23,36 @@ async fn remove_post(expense: Expense) -> Result<()> {
 }
 
 #[component]
-pub fn ExpenseCard<F>(expense: Expense, fetch_expenses: F) -> impl IntoView
-where
-    F: Fn() + 'static + Copy,
-{
-    let remove_expense = move |e: MouseEvent, expense: Expense| {
-        e.prevent_default();
-        spawn_local(async move {
-            match remove_post(expense).await {
-                Ok(_) => {
-                    alert("Successfully deleted!");
-                    fetch_expenses();
+pub fn ExpenseCard(expense: Expense) -> impl IntoView {
+    let store = use_store();
+    let i18n = use_i18n();
+    let category_id = expense.category_id;
+    let summary = {
+        let expense = expense.clone();
+        move || {
+            let payer = store.payer_name(expense.paid_by);
+            format!(
+                "{} - {} / {} - {} -{}",
+                i18n.amount(expense.amount),
+                expense.category_name,
+                expense.description.clone().unwrap_or_default(),
+                i18n.date(&expense.date.clone().unwrap_or_default()),
+                if payer.is_empty() {
+                    payer
+                } else {
+                    format!(" {payer}")
                 }
-                Err(e) => error_alert(e),
-            }
-        });
+            )
+        }
     };
-
     view! {
-    <li class="text-black row">
-        <p>${expense.amount} - {expense.category_name.clone()} {" / "} {expense.description.clone()} - {expense.date.clone()} -</p>
-        <TrashIcon on:click=move|e|{remove_expense(e,expense.clone());}  class=Some("text-red icon".to_owned()) />
+    <li
+        class="expense-card row"
+        style:border-left-color=move||store.settings().with(|settings| settings.category_color(category_id))
+    >
+        <A href=format!("/expenses/{}", expense.id)>
+            <p>{summary}</p>
+        </A>
+        <TrashIcon on:click=move|e: MouseEvent|{e.prevent_default(); store.remove_expense(expense.clone());}  class=Some("text-red icon".to_owned()) />
     </li>}
 }
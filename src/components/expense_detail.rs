@@ -0,0 +1,92 @@
+use core_api::{
+    error::AppError,
+    icons::TrashIcon,
+    models::{ApiResponse, Expense},
+};
+use leptos::{component, error::Result, *};
+use leptos_router::{use_navigate, use_params_map, NavigateOptions, A};
+use uuid::Uuid;
+
+use crate::{
+    i18n::{use_i18n, Key},
+    store::{use_store, Store},
+    utils::{api_url, error_toast, get_headers},
+};
+
+/// Servers without `GET /expenses/{id}`, like the mock one, answer 404 or 405; the
+/// expense is then looked up in the full copy.
+async fn fetch_expense(store: Store, id: Uuid) -> Result<Option<Expense>> {
+    let resp = reqwasm::http::Request::get(&format!("{}/expenses/{id}", api_url().await))
+        .headers(get_headers().await)
+        .send()
+        .await?;
+    if resp.ok() {
+        return Ok(Some(resp.json::<ApiResponse<Expense>>().await?.data()));
+    }
+    if matches!(resp.status(), 404 | 405) {
+        let expenses = store.all_expenses().await?;
+        return Ok(expenses.into_iter().find(|expense| expense.id == id));
+    }
+    Err(AppError::HardError(resp.text().await?).into())
+}
+
+/// `back_href` is the list with the filters and order it was left with.
+#[component]
+pub fn expense_detail(back_href: Signal<String>) -> impl IntoView {
+    let store = use_store();
+    let i18n = use_i18n();
+    let revision = store.revision();
+    let params = use_params_map();
+    let navigate = store_value(use_navigate());
+    let loaded = create_resource(
+        move || {
+            let id = params.with(|params| params.get("id").and_then(|id| id.parse::<Uuid>().ok()));
+            (id, revision.get())
+        },
+        move |(id, _)| async move {
+            match fetch_expense(store, id?).await {
+                Ok(expense) => expense,
+                Err(e) => {
+                    error_toast(e);
+                    None
+                }
+            }
+        },
+    );
+    let expense = move || loaded.get().flatten();
+    let delete = move |expense: Expense| {
+        store.remove_expense(expense);
+        let href = back_href.get_untracked();
+        navigate.with_value(|navigate| navigate(&href, NavigateOptions::default()));
+    };
+
+    let detail = move || {
+        expense().map(|expense| {
+            let deleted = expense.clone();
+            view! {
+                <table>
+                    <tbody>
+                        <tr><th>{i18n.t(Key::Date)}</th><td>{i18n.date(&expense.date.unwrap_or_default())}</td></tr>
+                        <tr><th>{i18n.t(Key::Amount)}</th><td>{i18n.amount(expense.amount)}</td></tr>
+                        <tr><th>{i18n.t(Key::Category)}</th><td>{expense.category_name}</td></tr>
+                        <tr><th>{i18n.t(Key::Description)}</th><td>{expense.description.unwrap_or_default()}</td></tr>
+                        <tr><th>{i18n.t(Key::Account)}</th><td>{expense.account.unwrap_or_default()}</td></tr>
+                    </tbody>
+                </table>
+                <button on:click=move|_|delete(deleted.clone())>
+                    {i18n.t(Key::Delete)}
+                    <TrashIcon class=Some("text-red icon".to_owned())/>
+                </button>
+            }
+        })
+    };
+
+    view! {
+            <div class="container">
+                <A href=move||back_href.get()>{move||i18n.t(Key::BackToExpenses)}</A>
+                <Show when=move||expense().is_some() fallback=move||(!loaded.loading().get()).then(||view! { <p>{i18n.t(Key::ExpenseNotFound)}</p> })>
+                    {detail}
+                </Show>
+            </div>
+    }
+}
@@ -1,6 +1,8 @@
 use core_api::{
+    duplicates::{candidates_filter, find_duplicates},
     error::AppError,
-    models::{ApiResponse, Category, Expense},
+    models::{ApiResponse, Expense},
+    rules::{first_match, RuleAction},
 };
 use leptos::{component, error::Result, ev::SubmitEvent, *};
 use uuid::Uuid;
@@ -8,24 +10,35 @@ use wasm_bindgen::JsValue;
 
 use crate::{
     app::invoke,
-    utils::{error_alert, get_headers, API_URL},
+    i18n::{use_i18n, Key},
+    store::use_store,
+    toasts::{toaster, ToastLevel},
+    utils::{api_url, error_toast, get_headers},
 };
 
-async fn post_expense(expense: Expense) -> Result<Expense> {
-    let value = JsValue::from_str(
-        format!(
-            r#"{{"category_id": "{}","date": "{}","amount":{}, "description":"{}" }}"#,
-            expense.category_id,
-            expense.date.clone().unwrap_or(String::default()),
-            expense.amount,
-            expense.description.unwrap_or(String::default())
-        )
+fn expense_body(expense: Expense, keep_id: bool) -> JsValue {
+    JsValue::from_str(
+        serde_json::json!({
+            "id": keep_id.then_some(expense.id),
+            "category_id": expense.category_id,
+            "date": expense.date.unwrap_or_default(),
+            "amount": expense.amount,
+            "description": expense.description.unwrap_or_default(),
+            "external_id": expense.external_id,
+            "account": expense.account,
+            "paid_by": expense.paid_by,
+        })
+        .to_string()
         .as_str(),
-    );
+    )
+}
 
-    let resp = reqwasm::http::Request::post(&format!("{}/expenses", API_URL))
+/// With `keep_id` the server is asked to keep the expense's id; servers that pick their
+/// own ids ignore it, so callers use the returned expense.
+pub async fn post_expense(expense: Expense, keep_id: bool) -> Result<Expense> {
+    let resp = reqwasm::http::Request::post(&format!("{}/expenses", api_url().await))
         .headers(get_headers().await)
-        .body(value)
+        .body(expense_body(expense, keep_id))
         .send()
         .await?;
     if resp.ok() {
@@ -34,12 +47,33 @@ async fn post_expense(expense: Expense) -> Result<Expense> {
     Err(AppError::HardError(resp.text().await?).into())
 }
 
+/// Updates the expense in place. `None` when the server has no update endpoint, or no
+/// longer has the expense.
+pub async fn put_expense(expense: Expense) -> Result<Option<Expense>> {
+    let resp = reqwasm::http::Request::put(&format!("{}/expenses/{}", api_url().await, expense.id))
+        .headers(get_headers().await)
+        .body(expense_body(expense, false))
+        .send()
+        .await?;
+    if resp.ok() {
+        return Ok(Some(resp.json::<ApiResponse<Expense>>().await?.data()));
+    }
+    if matches!(resp.status(), 404 | 405) {
+        return Ok(None);
+    }
+    Err(AppError::HardError(resp.text().await?).into())
+}
+
 #[component]
-pub fn expense_form(
-    categories: ReadSignal<Vec<Category>>,
-    set_expenses: WriteSignal<Vec<Expense>>,
-) -> impl IntoView {
+pub fn expense_form() -> impl IntoView {
+    let store = use_store();
+    let i18n = use_i18n();
+    let (categories, settings) = (store.categories(), store.settings());
+    let household = store.household();
     let (new_expense, set_new_expense) = create_signal(Expense::default());
+    let (category_touched, set_category_touched) = create_signal(false);
+    let (saving, set_saving) = create_signal(false);
+    let (duplicates, set_duplicates) = create_signal(Vec::<Expense>::new());
     let update_description = move |ev| {
         let v = event_target_value(&ev);
         set_new_expense.update(|expense| expense.description(Some(v)));
@@ -60,15 +94,47 @@ pub fn expense_form(
             return;
         }
         let v = Uuid::parse_str(v.as_str()).unwrap();
-        let category_selected = categories
-            .get_untracked()
-            .into_iter()
-            .find(|category| category.id == v)
-            .unwrap();
+        let category_selected = store.category(v).unwrap();
 
+        set_category_touched.set(true);
         set_new_expense.update(|expense| expense.category(v, category_selected.name));
     };
 
+    let matched_rule = move || first_match(&settings.get().rules, &new_expense.get()).cloned();
+    // Assigning rules only fill in the category until the user picks one by hand.
+    create_effect(move |_| {
+        let Some(rule) = matched_rule().filter(|rule| rule.action == RuleAction::Assign) else {
+            return;
+        };
+        if !category_touched.get_untracked()
+            && new_expense.get_untracked().category_id != rule.category_id
+        {
+            set_new_expense
+                .update(|expense| expense.category(rule.category_id, rule.category_name));
+        }
+    });
+    let suggestion_view = move || {
+        matched_rule()
+            .filter(|rule| {
+                rule.action == RuleAction::Suggest
+                    && rule.category_id != new_expense.get().category_id
+            })
+            .map(|rule| {
+                let category_name = rule.category_name.clone();
+                let use_suggestion = move |_| {
+                    set_category_touched.set(true);
+                    set_new_expense.update(|expense| {
+                        expense.category(rule.category_id, rule.category_name.clone())
+                    });
+                };
+                view! {
+                    <button type="button" on:click=use_suggestion>
+                        {i18n.t(Key::SuggestedCategory)} {category_name}
+                    </button>
+                }
+            })
+    };
+
     let get_now = move || {
         spawn_local(async move {
             let now = invoke("get_now_date", JsValue::default())
@@ -79,35 +145,126 @@ pub fn expense_form(
         });
     };
 
-    let add_expense = move |ev: SubmitEvent| {
-        ev.prevent_default();
+    // Editing the expense again dismisses an earlier duplicate warning.
+    create_effect(move |_| {
+        new_expense.track();
+        set_duplicates.set(vec![]);
+    });
+
+    let save_expense = move |confirmed: bool| {
+        if saving.get_untracked() {
+            return;
+        }
+        let expense = new_expense.get_untracked();
+        if expense.amount <= 1 {
+            return;
+        }
+        if expense.date.is_none() || expense.date.clone().unwrap().is_empty() {
+            return;
+        }
+        if expense.category_id == Uuid::default() {
+            return;
+        }
+        set_duplicates.set(vec![]);
+        set_saving.set(true);
         spawn_local(async move {
-            let expense = move || new_expense.get_untracked();
-            if expense().amount <= 1 {
-                return;
-            }
-            if expense().date.is_none() || expense().date.unwrap().is_empty() {
-                return;
-            }
-            if expense().category_id == Uuid::default() {
-                return;
+            if !confirmed {
+                let criteria = settings.get_untracked().duplicate_criteria();
+                let found: Vec<Expense> =
+                    match store.matching(candidates_filter(&expense, criteria)).await {
+                        Ok(candidates) => find_duplicates(&expense, &candidates, criteria)
+                            .into_iter()
+                            .cloned()
+                            .collect(),
+                        Err(e) => {
+                            set_saving.set(false);
+                            return error_toast(e);
+                        }
+                    };
+                if !found.is_empty() {
+                    set_saving.set(false);
+                    return set_duplicates.set(found);
+                }
             }
-
-            match post_expense(expense()).await {
+            match store.add_expense(expense).await {
                 Ok(res) => {
-                    set_expenses.update(|expenses_listed| expenses_listed.push(res));
+                    toaster().show(
+                        ToastLevel::Success,
+                        i18n.t(Key::Saved)
+                            .replace("{amount}", &i18n.amount(res.amount))
+                            .replace("{category}", &res.category_name),
+                    );
                 }
-                Err(e) => error_alert(e),
+                Err(e) => error_toast(e),
             }
+            set_saving.set(false);
         });
     };
+    let add_expense = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        save_expense(false);
+    };
+    let duplicates_view = move || {
+        let found = duplicates.get();
+        (!found.is_empty()).then(|| {
+            let items = found
+                .into_iter()
+                .map(|expense| {
+                    view! {
+                        <li>
+                            {i18n.date(&expense.date.unwrap_or_default())} " " {i18n.amount(expense.amount)} " "
+                            {expense.category_name} " / " {expense.description.unwrap_or_default()}
+                        </li>
+                    }
+                })
+                .collect_view();
+            view! {
+                <div class="warning">
+                    <p>{i18n.t(Key::AlreadySaved)}</p>
+                    <ul>{items}</ul>
+                    <button type="button" on:click=move|_|save_expense(true)>{i18n.t(Key::SaveAnyway)}</button>
+                </div>
+            }
+        })
+    };
+    // Left empty, the server records whoever adds the expense.
+    let members_view = move || {
+        household.get().map(|household| {
+            let options = household
+                .members
+                .into_iter()
+                .map(|member| {
+                    let selected = move || {
+                        new_expense.with(|expense| {
+                            expense.paid_by.unwrap_or(household.current_member_id) == member.id
+                        })
+                    };
+                    view! {
+                        <option value={member.id.to_string()} selected=selected>{member.email}</option>
+                    }
+                })
+                .collect_view();
+            view! {
+                <div class="row" >
+                    <label for="paid-by-select">{move||i18n.t(Key::PaidBy)}</label>
+                    <select
+                        id="paid-by-select"
+                        on:change=move|ev|set_new_expense.update(|expense| expense.paid_by(Uuid::parse_str(&event_target_value(&ev)).ok()))
+                    >
+                        {options}
+                    </select>
+                </div>
+                <br />
+            }
+        })
+    };
     let categories_view = move || {
         categories
             .get()
             .iter()
             .map(|category| {
                 view! {
-                <option class="text-black" value={category.id.clone().to_string()}>
+                <option value={category.id.clone().to_string()}>
                     <p>{category.name.clone()}</p>
                 </option>}
             })
@@ -121,7 +278,7 @@ pub fn expense_form(
                         <input
                             id="amount-input"
                             type="number"
-                            placeholder="Enter an amount"
+                            placeholder=move||i18n.t(Key::EnterAmount)
                             on:input=update_amount
                             prop:value={move||new_expense.get().amount}
                         />
@@ -131,9 +288,9 @@ pub fn expense_form(
                         <input
                             id="date-input"
                             type="date"
-                            placeholder="Enter a date"
+                            placeholder=move||i18n.t(Key::EnterDate)
                             on:input=update_date
-                            prop:value={move||new_expense.get().date.unwrap_or("Enter a Date.".to_owned())}
+                            prop:value={move||new_expense.get().date.unwrap_or_default()}
                         />
                    </div>
                    <br />
@@ -141,19 +298,22 @@ pub fn expense_form(
                             on:change=update_category_id
                             prop:value={move||new_expense.get().category_id.to_string()}
                             >
-                            <option value={Some(Uuid::default().to_string())}>Select category</option>
+                            <option value={Some(Uuid::default().to_string())}>{move||i18n.t(Key::SelectCategory)}</option>
                             {categories_view}
                        </select>
+                   {suggestion_view}
                    <br />
+                   {members_view}
                    <div class="row" >
                         <input
                             id="description-input"
-                            placeholder="Enter a description"
+                            placeholder=move||i18n.t(Key::EnterDescription)
                             on:input=update_description
                         />
                    </div>
                    <br />
-                   <button type="submit">"Add Expense"</button>
+                   <button type="submit" prop:disabled=saving>{move||i18n.t(Key::AddExpense)}</button>
+                   {duplicates_view}
                 </form>
 
     }
@@ -0,0 +1,186 @@
+use crate::components::expense_card::ExpenseCard;
+use crate::i18n::{use_i18n, Key};
+use crate::store::use_store;
+use crate::utils::{api_url, error_toast, get_headers};
+use core_api::{
+    error::AppError,
+    filters::ExpenseFilter,
+    models::{ApiResponse, Expense},
+    paging::{self, ExpensePage, ExpenseQuery},
+    sorting::ListOptions,
+};
+use leptos::{component, error::Result, *};
+
+// Start loading the next page this close to the bottom of the list, in pixels.
+const SCROLL_MARGIN: i32 = 200;
+
+/// `None` when the server does not page and answered with every expense instead.
+pub async fn fetch_page(query: &ExpenseQuery) -> Result<Option<ExpensePage>> {
+    let resp = reqwasm::http::Request::get(&format!(
+        "{}/expenses?{}",
+        api_url().await,
+        query.to_query_string()
+    ))
+    .headers(get_headers().await)
+    .send()
+    .await?;
+    if !resp.ok() {
+        return Err(AppError::HardError(resp.text().await?).into());
+    }
+    let body = resp.text().await?;
+    match serde_json::from_str::<ApiResponse<ExpensePage>>(&body) {
+        Ok(page) => Ok(Some(page.data())),
+        Err(_) if serde_json::from_str::<ApiResponse<Vec<Expense>>>(&body).is_ok() => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[component]
+pub fn expense_list(
+    filter: ReadSignal<ExpenseFilter>,
+    options: ReadSignal<ListOptions>,
+    page: ReadSignal<ExpensePage>,
+    set_page: WriteSignal<ExpensePage>,
+) -> impl IntoView {
+    let store = use_store();
+    let i18n = use_i18n();
+    let (revision, pending) = (store.revision(), store.pending_deletes());
+    let (server_paging, set_server_paging) = create_signal(true);
+    let (loading, set_loading) = create_signal(false);
+    // Counts loads, so only the answer to the latest one is shown or clears `loading`.
+    let generation = store_value(0u64);
+
+    let load = move |cursor: Option<String>| {
+        let query =
+            ExpenseQuery::new(filter.get_untracked(), options.get_untracked()).cursor(cursor);
+        generation.update_value(|generation| *generation += 1);
+        let this_load = generation.get_value();
+        set_loading.set(true);
+        spawn_local(async move {
+            let fetched = if server_paging.get_untracked() {
+                fetch_page(&query).await
+            } else {
+                Ok(None)
+            };
+            let next = match fetched {
+                Ok(Some(next)) => Ok(next),
+                // Page the full copy instead, so totals still cover the whole filter.
+                Ok(None) => {
+                    set_server_paging.set(false);
+                    store.all_expenses().await.map(|expenses| {
+                        let household = store.household().get_untracked();
+                        paging::page(&expenses, &query, household.as_ref())
+                    })
+                }
+                Err(e) => Err(e),
+            };
+            // A newer load started while this one was running, after a filter, sort or
+            // store change. It has the page to show and clears `loading` itself.
+            if generation.get_value() != this_load {
+                return;
+            }
+            set_loading.set(false);
+            let next = match next {
+                Ok(next) => next,
+                Err(e) => return error_toast(e),
+            };
+            if query.cursor.is_none() {
+                return set_page.set(next);
+            }
+            set_page.update(|page| {
+                page.items.extend(next.items);
+                page.next_cursor = next.next_cursor;
+                page.count = next.count;
+                page.total = next.total;
+            });
+        });
+    };
+    let load_more = move || {
+        if loading.get_untracked() {
+            return;
+        }
+        if let Some(cursor) = page.get_untracked().next_cursor {
+            load(Some(cursor));
+        }
+    };
+
+    // Grouping is done on the loaded items, so only the sort triggers a reload. So does
+    // any add, edit or delete through the store.
+    let sort = create_memo(move |_| {
+        let options = options.get();
+        (options.sort_by, options.descending)
+    });
+    create_effect(move |_| {
+        filter.track();
+        sort.track();
+        revision.track();
+        load(None);
+    });
+
+    let on_scroll = move |ev: ev::Event| {
+        let list = event_target::<web_sys::Element>(&ev);
+        if list.scroll_top() + list.client_height() >= list.scroll_height() - SCROLL_MARGIN {
+            load_more();
+        }
+    };
+
+    let groups_view = move || {
+        let pending = pending.get();
+        let ExpensePage {
+            mut items,
+            next_cursor,
+            ..
+        } = page.get();
+        // Deletes still in their undo window are hidden, not removed.
+        items.retain(|expense| {
+            !pending
+                .iter()
+                .any(|deleting| deleting.expense.id == expense.id)
+        });
+        options
+            .get()
+            .group(items)
+            .into_iter()
+            .map(|group| {
+                // Subtotals only add up the loaded pages, so say so until they all are.
+                let subtotal = match next_cursor {
+                    Some(_) => i18n
+                        .t(Key::SubtotalSoFar)
+                        .replace("{amount}", &i18n.amount(group.subtotal)),
+                    None => i18n.amount(group.subtotal),
+                };
+                let header = (!group.key.is_empty()).then(|| {
+                    view! {
+                        <li class="group-header">
+                            <span>{group.key}</span>
+                            <span>{subtotal}</span>
+                        </li>
+                    }
+                });
+                let cards = group
+                    .expenses
+                    .into_iter()
+                    .map(|expense| {
+                        view! {
+                            <ExpenseCard expense={expense} />
+                        }
+                    })
+                    .collect_view();
+                view! { {header} {cards} }
+            })
+            .collect_view()
+    };
+
+    view! {
+            <ul class="expense-list" on:scroll=on_scroll>
+                {groups_view}
+                <Show when=move||page.get().next_cursor.is_some() fallback=||()>
+                    <li class="container">
+                        <button on:click=move|_|load_more() disabled=loading>
+                            {move||i18n.t(if loading.get() { Key::Loading } else { Key::LoadMore })}
+                        </button>
+                    </li>
+                </Show>
+            </ul>
+    }
+}
@@ -0,0 +1,186 @@
+use core_api::{
+    filters::{ActiveFilter, ExpenseFilter},
+    paging::ExpensePage,
+};
+use leptos::{component, *};
+use uuid::Uuid;
+
+use crate::{
+    i18n::{use_i18n, Key},
+    store::use_store,
+};
+
+fn optional_text(value: String) -> Option<String> {
+    Some(value).filter(|value| !value.is_empty())
+}
+
+#[component]
+pub fn filter_bar(
+    filter: ReadSignal<ExpenseFilter>,
+    set_filter: WriteSignal<ExpenseFilter>,
+    page: ReadSignal<ExpensePage>,
+) -> impl IntoView {
+    let store = use_store();
+    let i18n = use_i18n();
+    let (categories, household) = (store.categories(), store.household());
+    let category_name = move |id: Uuid| {
+        store
+            .category(id)
+            .map(|category| category.name)
+            .unwrap_or_default()
+    };
+
+    let categories_view = move || {
+        categories
+            .get()
+            .into_iter()
+            .map(|category| {
+                let id = category.id;
+                view! {
+                    <label>
+                        <input
+                            type="checkbox"
+                            prop:checked=move||filter.get().category_ids.contains(&id)
+                            on:change=move|_|set_filter.update(|filter| filter.toggle_category(id))
+                        />
+                        {category.name}
+                    </label>
+                }
+            })
+            .collect_view()
+    };
+
+    let member_name = move |id: Uuid| {
+        store
+            .member(id)
+            .map(|member| member.email)
+            .unwrap_or_default()
+    };
+    let describe = move |active: ActiveFilter| match active {
+        ActiveFilter::Dates { from, to } => {
+            let (from, to) = (
+                from.map(|from| i18n.date(&from)),
+                to.map(|to| i18n.date(&to)),
+            );
+            match (from, to) {
+                (Some(from), Some(to)) => i18n
+                    .t(Key::Between)
+                    .replace("{from}", &from)
+                    .replace("{to}", &to),
+                (Some(from), None) => i18n.t(Key::Since).replace("{from}", &from),
+                (None, to) => i18n.t(Key::Until).replace("{to}", &to.unwrap_or_default()),
+            }
+        }
+        ActiveFilter::Categories(ids) => ids
+            .into_iter()
+            .map(category_name)
+            .collect::<Vec<_>>()
+            .join(", "),
+        ActiveFilter::Amounts { min, max } => {
+            let (min, max) = (
+                min.map(|min| i18n.amount(min)),
+                max.map(|max| i18n.amount(max)),
+            );
+            match (min, max) {
+                (Some(min), Some(max)) => i18n
+                    .t(Key::Between)
+                    .replace("{from}", &min)
+                    .replace("{to}", &max),
+                (Some(min), None) => i18n.t(Key::AtLeast).replace("{amount}", &min),
+                (None, max) => i18n
+                    .t(Key::UpTo)
+                    .replace("{amount}", &max.unwrap_or_default()),
+            }
+        }
+        ActiveFilter::Text(text) => format!("\"{text}\""),
+        ActiveFilter::PaidBy(id) => i18n
+            .t(Key::PaidByMember)
+            .replace("{member}", &member_name(id)),
+    };
+    let members_view = move || {
+        household.get().map(|household| {
+            let options = household
+                .members
+                .into_iter()
+                .map(|member| {
+                    let id = member.id;
+                    view! {
+                        <option value={id.to_string()} selected=move||filter.get().paid_by == Some(id)>
+                            {member.email}
+                        </option>
+                    }
+                })
+                .collect_view();
+            view! {
+                <select on:change=move|ev|set_filter.update(|filter| filter.paid_by = Uuid::parse_str(&event_target_value(&ev)).ok())>
+                    <option value="" selected=move||filter.get().paid_by.is_none()>{move||i18n.t(Key::AnyonePaid)}</option>
+                    {options}
+                </select>
+            }
+        })
+    };
+
+    let active_view = move || {
+        filter
+            .get()
+            .active()
+            .into_iter()
+            .map(|active| view! { <span class="chip">{describe(active)}</span> })
+            .collect_view()
+    };
+
+    view! {
+            <div class="container">
+                <input
+                    type="search"
+                    placeholder=move||i18n.t(Key::SearchDescriptions)
+                    on:input=move|ev|set_filter.update(|filter| filter.text = event_target_value(&ev))
+                    prop:value=move||filter.get().text
+                />
+                <div class="row">
+                    <input
+                        type="date"
+                        title=move||i18n.t(Key::FromDate)
+                        on:change=move|ev|set_filter.update(|filter| filter.from = optional_text(event_target_value(&ev)))
+                        prop:value=move||filter.get().from.unwrap_or_default()
+                    />
+                    <input
+                        type="date"
+                        title=move||i18n.t(Key::ToDate)
+                        on:change=move|ev|set_filter.update(|filter| filter.to = optional_text(event_target_value(&ev)))
+                        prop:value=move||filter.get().to.unwrap_or_default()
+                    />
+                </div>
+                <div class="row">
+                    <input
+                        type="number"
+                        placeholder=move||i18n.t(Key::MinAmount)
+                        on:input=move|ev|set_filter.update(|filter| filter.min_amount = event_target_value(&ev).parse().ok())
+                        prop:value=move||filter.get().min_amount.map(|min| min.to_string()).unwrap_or_default()
+                    />
+                    <input
+                        type="number"
+                        placeholder=move||i18n.t(Key::MaxAmount)
+                        on:input=move|ev|set_filter.update(|filter| filter.max_amount = event_target_value(&ev).parse().ok())
+                        prop:value=move||filter.get().max_amount.map(|max| max.to_string()).unwrap_or_default()
+                    />
+                </div>
+                <div class="chips">{categories_view}</div>
+                {members_view}
+                <Show when=move||filter.get().is_active() fallback=||()>
+                    <div class="chips">
+                        {active_view}
+                        <button type="button" on:click=move|_|set_filter.set(ExpenseFilter::default())>{move||i18n.t(Key::ClearFilters)}</button>
+                    </div>
+                </Show>
+                <p>
+                    {move||page.with(|page| {
+                        i18n.t(Key::ShowingExpenses)
+                            .replace("{shown}", &page.items.len().to_string())
+                            .replace("{count}", &page.count.to_string())
+                            .replace("{total}", &i18n.amount(page.total))
+                    })}
+                </p>
+            </div>
+    }
+}
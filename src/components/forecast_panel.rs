@@ -0,0 +1,105 @@
+use core_api::{
+    charts::CumulativeLine,
+    forecast::{forecast, BudgetStatus},
+    models::parse_date,
+    reports::daily_cumulative,
+};
+use leptos::{component, *};
+
+use crate::{
+    store::use_store,
+    utils::{call, error_toast},
+};
+
+fn status_label(status: BudgetStatus) -> &'static str {
+    match status {
+        BudgetStatus::NoBudget => "",
+        BudgetStatus::OnTrack => "On track",
+        BudgetStatus::AtRisk => "At risk",
+        BudgetStatus::Over => "Over budget",
+    }
+}
+
+fn status_class(status: BudgetStatus) -> &'static str {
+    match status {
+        BudgetStatus::Over => "text-red",
+        _ => "",
+    }
+}
+
+#[component]
+pub fn forecast_panel() -> impl IntoView {
+    let store = use_store();
+    let (expenses, settings) = (store.expenses(), store.settings());
+    let (today, set_today) = create_signal(None);
+    spawn_local(async move {
+        match call::<String>("get_now_date", ()).await {
+            Ok(now) => set_today.set(parse_date(&now)),
+            Err(e) => error_toast(e),
+        }
+    });
+
+    let projection = create_memo(move |_| {
+        today
+            .get()
+            .map(|today| forecast(&settings.get(), &expenses.get(), today))
+    });
+
+    let rows = move || {
+        projection
+            .get()
+            .map(|projection| projection.categories)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|category| {
+                let status = category.status();
+                view! {
+                    <tr>
+                        <td>{category.category_name}</td>
+                        <td>"$" {category.spent}</td>
+                        <td>"$" {category.projected}</td>
+                        <td>"$" {category.low} " – $" {category.high}</td>
+                        <td>{category.budget.map(|budget| format!("${budget}"))}</td>
+                        <td class={status_class(status)}>{status_label(status)}</td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+
+    let summary = move || {
+        projection.get().map(|projection| {
+            let status = projection.status();
+            let days = daily_cumulative(&expenses.get(), &projection.month);
+            view! {
+                <p>
+                    "Day " {projection.day} " of " {projection.days_in_month} ": spent $" {projection.spent}
+                    ", heading for $" {projection.projected}
+                    " ($" {projection.low} " – $" {projection.high} ")."
+                </p>
+                <p class={status_class(status)}>{status_label(status)}</p>
+                <CumulativeLine days={days} limit={projection.budget}/>
+            }
+        })
+    };
+
+    view! {
+            <div class="container">
+                <p>"Month-end forecast"</p>
+                {summary}
+                <table class="report">
+                    <thead>
+                        <tr>
+                            <th>"Category"</th>
+                            <th>"Spent"</th>
+                            <th>"Projected"</th>
+                            <th>"Range"</th>
+                            <th>"Budget"</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>{rows}</tbody>
+                </table>
+            </div>
+    }
+}
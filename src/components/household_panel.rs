@@ -0,0 +1,174 @@
+use core_api::{
+    error::AppError,
+    household::{Household, Invite, JoinHousehold, NewHousehold},
+    models::ApiResponse,
+};
+use leptos::{component, error::Result, ev::SubmitEvent, *};
+use serde::{de::DeserializeOwned, Serialize};
+use wasm_bindgen::JsValue;
+
+use crate::{
+    i18n::{use_i18n, Key},
+    store::use_store,
+    toasts::{toaster, ToastLevel},
+    utils::{api_url, error_toast, get_headers, response_errors},
+};
+
+pub async fn fetch_household() -> Result<Option<Household>> {
+    let resp = reqwasm::http::Request::get(&format!("{}/household", api_url().await))
+        .headers(get_headers().await)
+        .send()
+        .await?;
+    if resp.ok() {
+        return Ok(resp.json::<ApiResponse<Option<Household>>>().await?.data());
+    }
+    Err(AppError::HardError(resp.text().await?).into())
+}
+
+async fn post_household<T: Serialize + Clone + DeserializeOwned>(
+    path: &str,
+    body: serde_json::Value,
+) -> Result<T> {
+    let resp = reqwasm::http::Request::post(&format!("{}/household{path}", api_url().await))
+        .headers(get_headers().await)
+        .body(JsValue::from_str(&body.to_string()))
+        .send()
+        .await?;
+    if resp.ok() {
+        return Ok(resp.json::<ApiResponse<T>>().await?.data());
+    }
+    Err(AppError::HardError(response_errors(resp).await.join(" ")).into())
+}
+
+#[component]
+pub fn household_panel() -> impl IntoView {
+    let store = use_store();
+    let i18n = use_i18n();
+    let household = store.household();
+    let (name, set_name) = create_signal(String::new());
+    let (code, set_code) = create_signal(String::new());
+    let (invite, set_invite) = create_signal(None::<Invite>);
+
+    let changed = move |joined: Option<Household>| {
+        set_name.set(String::new());
+        set_code.set(String::new());
+        set_invite.set(None);
+        store.set_household(joined);
+    };
+    let create = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let body = serde_json::json!(NewHousehold {
+            name: name.get_untracked()
+        });
+        spawn_local(async move {
+            match post_household::<Option<Household>>("", body).await {
+                Ok(created) => changed(created),
+                Err(e) => error_toast(e),
+            }
+        });
+    };
+    let join = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let body = serde_json::json!(JoinHousehold {
+            code: code.get_untracked()
+        });
+        spawn_local(async move {
+            match post_household::<Option<Household>>("/join", body).await {
+                Ok(joined) => {
+                    toaster().show(ToastLevel::Success, i18n.t(Key::JoinedHousehold));
+                    changed(joined);
+                }
+                Err(e) => error_toast(e),
+            }
+        });
+    };
+    let create_invite = move |_| {
+        spawn_local(async move {
+            match post_household::<Invite>("/invites", serde_json::json!({})).await {
+                Ok(created) => set_invite.set(Some(created)),
+                Err(e) => error_toast(e),
+            }
+        });
+    };
+    let leave = move |_| {
+        spawn_local(async move {
+            match post_household::<bool>("/leave", serde_json::json!({})).await {
+                Ok(_) => {
+                    toaster().show(ToastLevel::Success, i18n.t(Key::LeftHousehold));
+                    changed(None);
+                }
+                Err(e) => error_toast(e),
+            }
+        });
+    };
+
+    let members_view = move || {
+        household.get().map(|household| {
+            household
+                .members
+                .iter()
+                .map(|member| {
+                    let mut labels = vec![];
+                    if member.id == household.owner_id {
+                        labels.push(i18n.t(Key::Owner));
+                    }
+                    if member.id == household.current_member_id {
+                        labels.push(i18n.t(Key::You));
+                    }
+                    let labels = (!labels.is_empty()).then(|| format!(" ({})", labels.join(", ")));
+                    view! { <li>{member.email.clone()} {labels}</li> }
+                })
+                .collect_view()
+        })
+    };
+    let invite_view = move || {
+        invite.get().map(|invite| {
+            view! {
+                <p>
+                    {i18n.t(Key::InvitationCode)} ": " <strong>{invite.code}</strong> " "
+                    {i18n.t(Key::InviteWorksOnce).replace("{date}", &i18n.date(&invite.expires_at))}
+                </p>
+            }
+        })
+    };
+    let no_household = move || {
+        view! {
+            <form class="container" on:submit=create>
+                <div class="row">
+                    <input
+                        placeholder=move||i18n.t(Key::HouseholdName)
+                        on:input=move|ev|set_name.set(event_target_value(&ev))
+                        prop:value=name
+                    />
+                    <button type="submit">{move||i18n.t(Key::CreateHousehold)}</button>
+                </div>
+            </form>
+            <form class="container" on:submit=join>
+                <div class="row">
+                    <input
+                        placeholder=move||i18n.t(Key::InvitationCode)
+                        autocomplete="off"
+                        on:input=move|ev|set_code.set(event_target_value(&ev))
+                        prop:value=code
+                    />
+                    <button type="submit">{move||i18n.t(Key::JoinHousehold)}</button>
+                </div>
+            </form>
+        }
+    };
+
+    view! {
+        <div class="container">
+            <p>{move||i18n.t(Key::Household)}</p>
+            <Show when=move||household.with(Option::is_some) fallback=no_household>
+                <p>{move||household.get().map(|household| household.name)}</p>
+                <ul>{members_view}</ul>
+                {invite_view}
+                <div class="row">
+                    <button on:click=create_invite>{move||i18n.t(Key::InviteSomeone)}</button>
+                    <button on:click=leave>{move||i18n.t(Key::LeaveHousehold)}</button>
+                </div>
+            </Show>
+        </div>
+    }
+}
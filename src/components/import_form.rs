@@ -0,0 +1,147 @@
+use core_api::{
+    import::{into_expenses, known_ids, parse, qif::DateOrder, ImportOutcome},
+    models::Expense,
+    rules,
+};
+use gloo::file::{futures::read_as_text, File};
+use leptos::{component, error::Result, ev::SubmitEvent, html::Input, *};
+use uuid::Uuid;
+
+use crate::{
+    i18n::{use_i18n, Key},
+    store::use_store,
+    toasts::{toaster, ToastLevel},
+    utils::error_toast,
+};
+
+async fn read_import(file: File, order: DateOrder, known: Vec<Expense>) -> Result<ImportOutcome> {
+    let content = read_as_text(&file).await?;
+    Ok(into_expenses(parse(&content, order)?, &known_ids(&known)))
+}
+
+#[component]
+pub fn import_form() -> impl IntoView {
+    let store = use_store();
+    let i18n = use_i18n();
+    let (categories, settings) = (store.categories(), store.settings());
+    let file_input = create_node_ref::<Input>();
+    let (category_id, set_category_id) = create_signal(Uuid::default());
+    let (date_order, set_date_order) = create_signal(DateOrder::default());
+    let (summary, set_summary) = create_signal(None::<String>);
+
+    let update_category_id = move |ev| {
+        let v = event_target_value(&ev);
+        set_category_id.set(Uuid::parse_str(v.as_str()).unwrap_or_default());
+    };
+    let update_date_order = move |ev| {
+        let order = match event_target_value(&ev).as_str() {
+            "month-first" => DateOrder::MonthFirst,
+            _ => DateOrder::DayFirst,
+        };
+        set_date_order.set(order);
+    };
+
+    let import_file = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let Some(file) = file_input
+            .get_untracked()
+            .and_then(|input| input.files())
+            .and_then(|files| files.get(0))
+        else {
+            toaster().show(ToastLevel::Warning, i18n.t(Key::ChooseImportFile));
+            return;
+        };
+        // Rules come first; the fallback is only needed for rows none of them match.
+        let fallback = store.category(category_id.get_untracked());
+        spawn_local(async move {
+            // Every expense is needed to recognise transactions imported before.
+            let known = match store.all_expenses().await {
+                Ok(known) => known,
+                Err(e) => return error_toast(e),
+            };
+            let outcome = match read_import(file.into(), date_order.get_untracked(), known).await {
+                Ok(outcome) => outcome,
+                Err(e) => return error_toast(e),
+            };
+            let rules = settings.get_untracked().rules;
+            let mut imported = 0;
+            let mut categorized = 0;
+            let mut uncategorized = 0;
+            for mut expense in outcome.expenses {
+                if rules::assign(&rules, &mut expense) {
+                    categorized += 1;
+                } else if let Some(category) = &fallback {
+                    expense.category(category.id, category.name.clone());
+                } else {
+                    uncategorized += 1;
+                    continue;
+                }
+                match store.add_expense(expense).await {
+                    Ok(_) => imported += 1,
+                    Err(e) => {
+                        error_toast(e);
+                        break;
+                    }
+                }
+            }
+            let mut summary = i18n
+                .t(Key::ImportSummary)
+                .replace("{imported}", &imported.to_string())
+                .replace("{categorized}", &categorized.to_string())
+                .replace("{skipped}", &outcome.already_imported.len().to_string())
+                .replace("{credits}", &outcome.credits.len().to_string());
+            if uncategorized > 0 {
+                let count = uncategorized.to_string();
+                summary.push(' ');
+                summary.push_str(&i18n.t(Key::ImportLeftOut).replace("{count}", &count));
+                toaster().show(
+                    ToastLevel::Warning,
+                    i18n.t(Key::ExpensesNeedCategory).replace("{count}", &count),
+                );
+            }
+            set_summary.set(Some(summary));
+        });
+    };
+
+    let categories_view = move || {
+        categories
+            .get()
+            .iter()
+            .map(|category| {
+                view! {
+                <option value={category.id.to_string()}>
+                    <p>{category.name.clone()}</p>
+                </option>}
+            })
+            .collect_view()
+    };
+
+    view! {
+                <form class="container" on:submit=import_file>
+                   <div class="row" >
+                        <input
+                            id="import-input"
+                            type="file"
+                            accept=".ofx,.qfx,.qif"
+                            node_ref=file_input
+                        />
+                   </div>
+                   <br />
+                       <select
+                            on:change=update_category_id
+                            prop:value={move||category_id.get().to_string()}
+                            >
+                            <option value={Uuid::default().to_string()}>{move||i18n.t(Key::FallbackCategory)}</option>
+                            {categories_view}
+                       </select>
+                   <br />
+                       <select on:change=update_date_order>
+                            <option value="day-first">{move||i18n.t(Key::QifDayFirst)}</option>
+                            <option value="month-first">{move||i18n.t(Key::QifMonthFirst)}</option>
+                       </select>
+                   <br />
+                   <button type="submit">{move||i18n.t(Key::ImportFile)}</button>
+                   <p>{summary}</p>
+                </form>
+    }
+}
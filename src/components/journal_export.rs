@@ -0,0 +1,92 @@
+use core_api::{
+    error::AppError,
+    journal::{export, JournalFormat, JournalOptions},
+    models::Expense,
+};
+use leptos::{component, ev::MouseEvent, *};
+
+use crate::{store::use_store, utils::error_toast};
+
+/// Needs every expense, so they are only loaded once an export is asked for.
+#[component]
+pub fn journal_export() -> impl IntoView {
+    let store = use_store();
+    let (expenses, set_expenses) = create_signal(None::<Vec<Expense>>);
+    let (options, set_options) = create_signal(JournalOptions::new());
+
+    let update_format = move |ev| {
+        let format = match event_target_value(&ev).as_str() {
+            "hledger" => JournalFormat::Hledger,
+            "beancount" => JournalFormat::Beancount,
+            _ => JournalFormat::Ledger,
+        };
+        set_options.update(|options| *options = options.clone().format(format));
+    };
+    let update_funding_account = move |ev| {
+        let v = event_target_value(&ev);
+        if v.is_empty() {
+            return;
+        }
+        set_options.update(|options| *options = options.clone().funding_account(v));
+    };
+    let update_commodity = move |ev| {
+        let v = event_target_value(&ev);
+        if v.is_empty() {
+            return;
+        }
+        set_options.update(|options| *options = options.clone().commodity(v));
+    };
+
+    let load = move |e: MouseEvent| {
+        e.prevent_default();
+        spawn_local(async move {
+            match store.all_expenses().await {
+                Ok(all) => set_expenses.set(Some(all)),
+                Err(e) => error_toast(e),
+            }
+        });
+    };
+
+    let exported = move || {
+        expenses.with(|expenses| {
+            expenses
+                .as_ref()
+                .map(|expenses| export(expenses, &options.get()))
+        })
+    };
+    let journal = move || exported().and_then(Result::ok).unwrap_or_default();
+    let problem = move || match exported() {
+        Some(Err(AppError::Invalid(problem))) => Some(format!("Cannot export: {problem}.")),
+        Some(Err(AppError::HardError(problem))) => Some(problem),
+        _ => None,
+    };
+
+    view! {
+                <div class="container">
+                       <select on:change=update_format>
+                            <option value="ledger">ledger</option>
+                            <option value="hledger">hledger</option>
+                            <option value="beancount">beancount</option>
+                       </select>
+                   <div class="row" >
+                        <input
+                            id="funding-account-input"
+                            placeholder="Paid from account"
+                            on:change=update_funding_account
+                            prop:value={move||options.get().funding_account}
+                        />
+                        <input
+                            id="commodity-input"
+                            placeholder="Commodity"
+                            on:change=update_commodity
+                            prop:value={move||options.get().commodity}
+                        />
+                   </div>
+                   <button on:click=load>"Export journal"</button>
+                   <p class="text-red">{problem}</p>
+                   <Show when=move||expenses.with(Option::is_some) fallback=||()>
+                        <textarea class="journal" readonly=true rows="12" prop:value=journal></textarea>
+                   </Show>
+                </div>
+    }
+}
@@ -0,0 +1,44 @@
+use core_api::sorting::{GroupBy, ListOptions, SortBy};
+use leptos::{component, *};
+
+#[component]
+pub fn list_controls(
+    options: ReadSignal<ListOptions>,
+    set_options: WriteSignal<ListOptions>,
+) -> impl IntoView {
+    let update_sort = move |ev| {
+        let sort_by = match event_target_value(&ev).as_str() {
+            "amount" => SortBy::Amount,
+            "category" => SortBy::Category,
+            _ => SortBy::Date,
+        };
+        set_options.update(|options| options.sort_by = sort_by);
+    };
+    let update_group = move |ev| {
+        let group_by = match event_target_value(&ev).as_str() {
+            "day" => GroupBy::Day,
+            "month" => GroupBy::Month,
+            _ => GroupBy::None,
+        };
+        set_options.update(|options| options.group_by = group_by);
+    };
+
+    view! {
+            <div class="row">
+                <select on:change=update_sort>
+                    <option value="date" selected=move||options.get().sort_by == SortBy::Date>"Sort by date"</option>
+                    <option value="amount" selected=move||options.get().sort_by == SortBy::Amount>"Sort by amount"</option>
+                    <option value="category" selected=move||options.get().sort_by == SortBy::Category>"Sort by category"</option>
+                </select>
+                <select on:change=move|ev|set_options.update(|options| options.descending = event_target_value(&ev) == "desc")>
+                    <option value="desc" selected=move||options.get().descending>"Descending"</option>
+                    <option value="asc" selected=move||!options.get().descending>"Ascending"</option>
+                </select>
+                <select on:change=update_group>
+                    <option value="none" selected=move||options.get().group_by == GroupBy::None>"No grouping"</option>
+                    <option value="day" selected=move||options.get().group_by == GroupBy::Day>"Group by day"</option>
+                    <option value="month" selected=move||options.get().group_by == GroupBy::Month>"Group by month"</option>
+                </select>
+            </div>
+    }
+}
@@ -0,0 +1,101 @@
+use core_api::lock::{LockStatus, UnlockResult, MAX_PIN_DIGITS};
+use gloo::timers::future::TimeoutFuture;
+use leptos::{component, ev::SubmitEvent, *};
+
+use crate::{
+    i18n::{use_i18n, Key},
+    lock::{use_lock, LockState},
+    utils::{call, error_toast},
+};
+
+/// Covers the app until the PIN is entered. Blank while the lock is still being checked.
+#[component]
+pub fn lock_screen() -> impl IntoView {
+    let lock = use_lock();
+    let i18n = use_i18n();
+    let (pin, set_pin) = create_signal(String::new());
+    let (message, set_message) = create_signal(None::<String>);
+    let (wait_seconds, set_wait_seconds) = create_signal(0i64);
+
+    // Counts down a backoff, also one left from before a restart.
+    let wait = move |seconds: i64| {
+        set_wait_seconds.set(seconds);
+        spawn_local(async move {
+            while wait_seconds.get_untracked() > 0 {
+                TimeoutFuture::new(1000).await;
+                set_wait_seconds.update(|seconds| *seconds -= 1);
+            }
+        });
+    };
+    create_effect(move |_| {
+        if lock.state().get() != LockState::Locked {
+            return;
+        }
+        spawn_local(async move {
+            if let Ok(status) = call::<LockStatus>("lock_status", ()).await {
+                if status.wait_seconds > 0 && wait_seconds.get_untracked() == 0 {
+                    wait(status.wait_seconds);
+                }
+            }
+        });
+    });
+
+    let unlock = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        if wait_seconds.get_untracked() > 0 {
+            return;
+        }
+        let args = serde_json::json!({ "pin": pin.get_untracked() });
+        set_pin.set(String::new());
+        spawn_local(async move {
+            match call::<UnlockResult>("unlock", args).await {
+                Ok(UnlockResult { unlocked: true, .. }) => {
+                    set_message.set(None);
+                    lock.unlocked();
+                }
+                Ok(UnlockResult { wait_seconds, .. }) => {
+                    set_message.set(Some(i18n.t(Key::WrongPin).to_owned()));
+                    if wait_seconds > 0 {
+                        wait(wait_seconds);
+                    }
+                }
+                Err(e) => error_toast(e),
+            }
+        });
+    };
+
+    let waiting = move || wait_seconds.get() > 0;
+    let status_view = move || {
+        if waiting() {
+            Some(
+                i18n.t(Key::PinBackoff)
+                    .replace("{seconds}", &wait_seconds.get().to_string()),
+            )
+        } else {
+            message.get()
+        }
+    };
+
+    view! {
+        <div class="lock-screen">
+            <Show when=move||lock.state().get() == LockState::Locked>
+                <form class="container" on:submit=unlock>
+                    <p>{move||i18n.t(Key::EnterPin)}</p>
+                    <div class="row">
+                        <input
+                            type="password"
+                            inputmode="numeric"
+                            autocomplete="off"
+                            maxlength=MAX_PIN_DIGITS
+                            on:input=move|ev|set_pin.set(event_target_value(&ev))
+                            prop:value=pin
+                            prop:disabled=waiting
+                        />
+                    </div>
+                    <p class="text-red">{status_view}</p>
+                    <button type="submit" prop:disabled=waiting>{move||i18n.t(Key::Unlock)}</button>
+                </form>
+            </Show>
+        </div>
+    }
+}
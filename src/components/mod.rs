@@ -1,3 +1,25 @@
+pub mod alerts_editor;
+pub mod backup_panel;
 pub mod credentials_form;
+pub mod duplicates_review;
 pub mod expense_card;
+pub mod expense_detail;
 pub mod expense_form;
+pub mod expense_list;
+pub mod filter_bar;
+pub mod forecast_panel;
+pub mod household_panel;
+pub mod import_form;
+pub mod journal_export;
+pub mod list_controls;
+pub mod lock_screen;
+pub mod password_fields;
+pub mod password_reset_form;
+pub mod pin_editor;
+pub mod register_form;
+pub mod reports;
+pub mod rules_editor;
+pub mod session_panel;
+pub mod settings_form;
+pub mod theme_editor;
+pub mod trash;
@@ -0,0 +1,68 @@
+use core_api::passwords::{strength, Strength, MIN_PASSWORD_LENGTH};
+use leptos::{component, *};
+
+use crate::i18n::{use_i18n, I18n, Key};
+
+/// What is wrong with a new password, in the current language.
+pub fn password_problems(i18n: I18n, password: &str, confirm: &str) -> Vec<String> {
+    let mut problems = vec![];
+    let strength = strength(password);
+    if strength == Strength::TooShort {
+        problems.push(
+            i18n.t(Key::PasswordTooShort)
+                .replace("{min}", &MIN_PASSWORD_LENGTH.to_string()),
+        );
+    } else if !strength.acceptable() {
+        problems.push(i18n.t(Key::PasswordTooWeak).to_owned());
+    }
+    if password != confirm {
+        problems.push(i18n.t(Key::PasswordsDiffer).to_owned());
+    }
+    problems
+}
+
+/// A new password typed twice, with a strength meter.
+#[component]
+pub fn password_fields(password: RwSignal<String>, confirm: RwSignal<String>) -> impl IntoView {
+    let i18n = use_i18n();
+    let meter = move || {
+        let current = password.get();
+        (!current.is_empty()).then(|| {
+            let (key, class) = match strength(&current) {
+                Strength::TooShort | Strength::Weak => (Key::StrengthWeak, "text-red"),
+                Strength::Fair => (Key::StrengthFair, ""),
+                Strength::Strong => (Key::StrengthStrong, ""),
+            };
+            view! { <p class={class}>{i18n.t(Key::PasswordStrength)} {i18n.t(key)}</p> }
+        })
+    };
+    view! {
+       <div class="row" >
+            <input
+                type="password"
+                placeholder=move||i18n.t(Key::NewPassword)
+                on:input=move|ev|password.set(event_target_value(&ev))
+                prop:value=password
+            />
+       </div>
+       {meter}
+       <div class="row" >
+            <input
+                type="password"
+                placeholder=move||i18n.t(Key::ConfirmPassword)
+                on:input=move|ev|confirm.set(event_target_value(&ev))
+                prop:value=confirm
+            />
+       </div>
+    }
+}
+
+/// Validation messages under a form, from the app or the server.
+#[component]
+pub fn form_errors(errors: RwSignal<Vec<String>>) -> impl IntoView {
+    view! {
+        <ul class="form-errors text-red">
+            {move||errors.get().into_iter().map(|error| view! { <li>{error}</li> }).collect_view()}
+        </ul>
+    }
+}
@@ -0,0 +1,151 @@
+use core_api::models::{ApiResponse, LoginResponse, PasswordResetBody, PasswordResetRequest};
+use leptos::{component, ev::SubmitEvent, *};
+use leptos_router::{use_navigate, NavigateOptions, A};
+use serde::{de::DeserializeOwned, Serialize};
+use wasm_bindgen::JsValue;
+
+use crate::{
+    components::{
+        credentials_form::save_token,
+        password_fields::{password_problems, FormErrors, PasswordFields},
+    },
+    i18n::{use_i18n, Key},
+    store::use_store,
+    toasts::{toaster, ToastLevel},
+    utils::{api_url, get_headers, response_errors},
+};
+
+/// The reply's data, or the reasons the server refused the request.
+async fn post_reset<B: Serialize, T: Serialize + Clone + DeserializeOwned>(
+    path: &str,
+    body: &B,
+) -> Result<T, Vec<String>> {
+    let value = JsValue::from_str(&serde_json::to_string(body).unwrap_or_default());
+    let resp = reqwasm::http::Request::post(&format!("{}/{path}", api_url().await))
+        .headers(get_headers().await)
+        .body(value)
+        .send()
+        .await
+        .map_err(|e| vec![e.to_string()])?;
+    if resp.ok() {
+        return resp
+            .json::<ApiResponse<T>>()
+            .await
+            .map(|response| response.data())
+            .map_err(|e| vec![e.to_string()]);
+    }
+    Err(response_errors(resp).await)
+}
+
+/// Asks for a code by email first, then sets the new password with it and logs in.
+#[component]
+pub fn password_reset_form() -> impl IntoView {
+    let store = use_store();
+    let i18n = use_i18n();
+    let email = create_rw_signal(String::new());
+    let code = create_rw_signal(String::new());
+    let password = create_rw_signal(String::new());
+    let confirm = create_rw_signal(String::new());
+    let errors = create_rw_signal(Vec::<String>::new());
+    let (code_sent, set_code_sent) = create_signal(false);
+    let (saving, set_saving) = create_signal(false);
+    let navigate = store_value(use_navigate());
+
+    let request_code = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        if !email.get_untracked().contains('@') {
+            return errors.set(vec![i18n.t(Key::EnterEmail).to_owned()]);
+        }
+        if saving.get_untracked() {
+            return;
+        }
+        errors.set(vec![]);
+        let body = PasswordResetRequest {
+            email: email.get_untracked().trim().to_owned(),
+        };
+        set_saving.set(true);
+        spawn_local(async move {
+            match post_reset::<_, bool>("password-reset", &body).await {
+                Ok(_) => {
+                    toaster().show(ToastLevel::Info, i18n.t(Key::ResetCodeSent));
+                    set_code_sent.set(true);
+                }
+                Err(found) => errors.set(found),
+            }
+            set_saving.set(false);
+        });
+    };
+
+    let reset = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let mut problems =
+            password_problems(i18n, &password.get_untracked(), &confirm.get_untracked());
+        if code.get_untracked().trim().is_empty() {
+            problems.insert(0, i18n.t(Key::EnterResetCode).to_owned());
+        }
+        errors.set(problems);
+        if !errors.get_untracked().is_empty() || saving.get_untracked() {
+            return;
+        }
+        let body = PasswordResetBody {
+            email: email.get_untracked().trim().to_owned(),
+            code: code.get_untracked().trim().to_owned(),
+            password: password.get_untracked(),
+        };
+        set_saving.set(true);
+        spawn_local(async move {
+            match post_reset::<_, LoginResponse>("password-reset/confirm", &body).await {
+                Ok(res) => {
+                    save_token(res.token, body.email).await;
+                    toaster().show(ToastLevel::Success, i18n.t(Key::PasswordChanged));
+                    store.load_session();
+                    store.refresh();
+                    navigate
+                        .with_value(|navigate| navigate("/session", NavigateOptions::default()));
+                }
+                Err(found) => errors.set(found),
+            }
+            set_saving.set(false);
+        });
+    };
+
+    let email_form = move || {
+        view! {
+            <form class="container" on:submit=request_code>
+               <p>{move||i18n.t(Key::ForgotPassword)}</p>
+               <div class="row" >
+                    <input
+                        type="email"
+                        placeholder=move||i18n.t(Key::EnterEmail)
+                        on:input=move|ev|email.set(event_target_value(&ev))
+                        prop:value=email
+                    />
+               </div>
+               <FormErrors errors=errors/>
+               <button type="submit" prop:disabled=saving>{move||i18n.t(Key::SendResetCode)}</button>
+               <A href="/session">{move||i18n.t(Key::BackToLogin)}</A>
+            </form>
+        }
+    };
+
+    view! {
+        <Show when=move||code_sent.get() fallback=email_form>
+            <form class="container" on:submit=reset>
+               <p>{move||i18n.t(Key::ResetCodeSent)}</p>
+               <div class="row" >
+                    <input
+                        inputmode="numeric"
+                        autocomplete="one-time-code"
+                        placeholder=move||i18n.t(Key::EnterResetCode)
+                        on:input=move|ev|code.set(event_target_value(&ev))
+                        prop:value=code
+                    />
+               </div>
+               <PasswordFields password=password confirm=confirm/>
+               <FormErrors errors=errors/>
+               <button type="submit" prop:disabled=saving>{move||i18n.t(Key::SetNewPassword)}</button>
+               <button type="button" on:click=move|_|set_code_sent.set(false)>{move||i18n.t(Key::SendResetCode)}</button>
+            </form>
+        </Show>
+    }
+}
@@ -0,0 +1,131 @@
+use core_api::lock::{valid_pin, MAX_PIN_DIGITS};
+use leptos::{component, ev::MouseEvent, *};
+
+use crate::{
+    lock::use_lock,
+    store::use_store,
+    toasts::{toaster, ToastLevel},
+    utils::{call, error_toast},
+};
+
+const IDLE_CHOICES: [u32; 5] = [1, 5, 15, 30, 60];
+
+#[component]
+pub fn pin_editor() -> impl IntoView {
+    let store = use_store();
+    let lock = use_lock();
+    let settings = store.settings();
+    let (current, set_current) = create_signal(String::new());
+    let (pin, set_pin) = create_signal(String::new());
+    let (repeated, set_repeated) = create_signal(String::new());
+
+    let clear_inputs = move || {
+        set_current.set(String::new());
+        set_pin.set(String::new());
+        set_repeated.set(String::new());
+    };
+    let save_pin = move |e: MouseEvent| {
+        e.prevent_default();
+        let new_pin = pin.get_untracked();
+        if !valid_pin(&new_pin) {
+            toaster().show(ToastLevel::Warning, "The PIN must be 4 to 8 digits.");
+            return;
+        }
+        if new_pin != repeated.get_untracked() {
+            toaster().show(ToastLevel::Warning, "The PINs do not match.");
+            return;
+        }
+        let args = serde_json::json!({ "pin": new_pin, "current": current.get_untracked() });
+        spawn_local(async move {
+            match call::<()>("set_pin", args).await {
+                Ok(_) => {
+                    lock.set_enabled(true);
+                    clear_inputs();
+                    toaster().show(ToastLevel::Success, "PIN saved.");
+                }
+                Err(e) => error_toast(e),
+            }
+        });
+    };
+    let remove_pin = move |e: MouseEvent| {
+        e.prevent_default();
+        let args = serde_json::json!({ "current": current.get_untracked() });
+        spawn_local(async move {
+            match call::<()>("clear_pin", args).await {
+                Ok(_) => {
+                    lock.set_enabled(false);
+                    clear_inputs();
+                    toaster().show(ToastLevel::Success, "PIN removed.");
+                }
+                Err(e) => error_toast(e),
+            }
+        });
+    };
+    let set_idle = move |ev| {
+        let minutes = event_target_value(&ev).parse().ok();
+        store.update_settings(|settings| settings.idle_lock_minutes = minutes);
+        spawn_local(async move {
+            if let Err(e) = store.save_settings().await {
+                error_toast(e);
+            }
+        });
+    };
+
+    let idle_options = move || {
+        let selected = settings.with(|settings| settings.idle_lock_minutes);
+        IDLE_CHOICES
+            .iter()
+            .map(|minutes| {
+                view! {
+                    <option value={minutes.to_string()} selected={selected == Some(*minutes)}>
+                        "After " {*minutes} " minutes idle"
+                    </option>
+                }
+            })
+            .collect_view()
+    };
+    let pin_input = move |placeholder: &'static str,
+                          value: ReadSignal<String>,
+                          set_value: WriteSignal<String>| {
+        view! {
+            <input
+                type="password"
+                inputmode="numeric"
+                autocomplete="off"
+                maxlength=MAX_PIN_DIGITS
+                placeholder=placeholder
+                on:input=move|ev|set_value.set(event_target_value(&ev))
+                prop:value=value
+            />
+        }
+    };
+
+    view! {
+            <form class="container">
+               <p>"App lock"</p>
+               <Show when=move||lock.enabled().get()>
+                   <div class="row" >{pin_input("Current PIN", current, set_current)}</div>
+               </Show>
+               <div class="row" >
+                    {pin_input("New PIN (4 to 8 digits)", pin, set_pin)}
+                    {pin_input("Repeat the new PIN", repeated, set_repeated)}
+               </div>
+               <button on:click=save_pin>
+                    {move||if lock.enabled().get() { "Change PIN" } else { "Set PIN" }}
+               </button>
+               <Show when=move||lock.enabled().get()>
+                   <button on:click=remove_pin>"Remove PIN"</button>
+                   <button on:click=move|e: MouseEvent|{e.prevent_default(); lock.lock();}>"Lock now"</button>
+               </Show>
+               <div class="row" >
+                   <label for="idle-lock-select">"Lock again"</label>
+                   <select id="idle-lock-select" on:change=set_idle>
+                        <option value="" selected={move||settings.with(|settings| settings.idle_lock_minutes.is_none())}>
+                            "Only when the app opens"
+                        </option>
+                        {idle_options}
+                   </select>
+               </div>
+            </form>
+    }
+}
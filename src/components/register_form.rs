@@ -0,0 +1,97 @@
+use core_api::models::{ApiResponse, LoginBody, LoginResponse};
+use leptos::{component, ev::SubmitEvent, *};
+use leptos_router::{use_navigate, NavigateOptions, A};
+use wasm_bindgen::JsValue;
+
+use crate::{
+    components::{
+        credentials_form::save_token,
+        password_fields::{password_problems, FormErrors, PasswordFields},
+    },
+    i18n::{use_i18n, Key},
+    store::use_store,
+    toasts::{toaster, ToastLevel},
+    utils::{api_url, get_headers, response_errors},
+};
+
+/// The new account's token, or the reasons the server refused it.
+async fn post_register(body: LoginBody) -> Result<LoginResponse, Vec<String>> {
+    let value = JsValue::from_str(&serde_json::to_string(&body).unwrap_or_default());
+    let resp = reqwasm::http::Request::post(&format!("{}/register", api_url().await))
+        .headers(get_headers().await)
+        .body(value)
+        .send()
+        .await
+        .map_err(|e| vec![e.to_string()])?;
+    if resp.ok() {
+        return resp
+            .json::<ApiResponse<LoginResponse>>()
+            .await
+            .map(|response| response.data())
+            .map_err(|e| vec![e.to_string()]);
+    }
+    Err(response_errors(resp).await)
+}
+
+#[component]
+pub fn register_form() -> impl IntoView {
+    let store = use_store();
+    let i18n = use_i18n();
+    let email = create_rw_signal(String::new());
+    let password = create_rw_signal(String::new());
+    let confirm = create_rw_signal(String::new());
+    let errors = create_rw_signal(Vec::<String>::new());
+    let (saving, set_saving) = create_signal(false);
+    let navigate = store_value(use_navigate());
+
+    let register = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let mut problems =
+            password_problems(i18n, &password.get_untracked(), &confirm.get_untracked());
+        if !email.get_untracked().contains('@') {
+            problems.insert(0, i18n.t(Key::EnterEmail).to_owned());
+        }
+        errors.set(problems);
+        if !errors.get_untracked().is_empty() || saving.get_untracked() {
+            return;
+        }
+        let body = LoginBody {
+            email: email.get_untracked().trim().to_owned(),
+            password: password.get_untracked(),
+        };
+        set_saving.set(true);
+        spawn_local(async move {
+            let email = body.email.clone();
+            match post_register(body).await {
+                Ok(res) => {
+                    save_token(res.token, email).await;
+                    toaster().show(ToastLevel::Success, i18n.t(Key::Registered));
+                    store.load_session();
+                    store.refresh();
+                    navigate
+                        .with_value(|navigate| navigate("/session", NavigateOptions::default()));
+                }
+                Err(found) => errors.set(found),
+            }
+            set_saving.set(false);
+        });
+    };
+
+    view! {
+            <form class="container" on:submit=register>
+               <p>{move||i18n.t(Key::CreateAccount)}</p>
+               <div class="row" >
+                    <input
+                        type="email"
+                        placeholder=move||i18n.t(Key::EnterEmail)
+                        on:input=move|ev|email.set(event_target_value(&ev))
+                        prop:value=email
+                    />
+               </div>
+               <PasswordFields password=password confirm=confirm/>
+               <FormErrors errors=errors/>
+               <button type="submit" prop:disabled=saving>{move||i18n.t(Key::Register)}</button>
+               <A href="/session">{move||i18n.t(Key::BackToLogin)}</A>
+            </form>
+    }
+}
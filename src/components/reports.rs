@@ -0,0 +1,143 @@
+use core_api::{
+    charts::{CategoryDonut, CumulativeLine, MonthlyBars},
+    reports::{daily_cumulative, member_totals},
+};
+use leptos::{component, *};
+
+use crate::{
+    i18n::{use_i18n, Key},
+    store::use_store,
+};
+
+// Months shown in the bar chart, ending at the selected one.
+const HISTORY_MONTHS: usize = 6;
+
+fn percent(share: f32) -> String {
+    format!("{:.1}%", share * 100.0)
+}
+
+#[component]
+pub fn reports<F>(month: Signal<Option<String>>, set_month: F) -> impl IntoView
+where
+    F: Fn(Option<String>) + 'static + Copy,
+{
+    let store = use_store();
+    let i18n = use_i18n();
+    let (expenses, settings, monthly) = (store.expenses(), store.settings(), store.monthly());
+    let household = store.household();
+    let colors = move || settings.with(|settings| settings.category_colors.clone());
+    // Until a month is picked, show the newest one with expenses.
+    let selected = create_memo(move |_| {
+        month
+            .get()
+            .or_else(|| monthly.with(|monthly| monthly.last().map(|last| last.month.clone())))
+            .unwrap_or_default()
+    });
+    let summary = create_memo(move |_| store.month_summary(&selected.get()));
+    let history = move || {
+        let selected = selected.get();
+        let mut history: Vec<_> = monthly
+            .get()
+            .into_iter()
+            .filter(|month| month.month <= selected)
+            .collect();
+        history.drain(..history.len().saturating_sub(HISTORY_MONTHS));
+        history
+    };
+
+    let rows = move || {
+        summary
+            .get()
+            .categories
+            .into_iter()
+            .map(|category| {
+                view! {
+                    <tr>
+                        <td>
+                            <span class="swatch" style:background-color={settings.with(|settings| settings.category_color(category.category_id))}></span>
+                            {category.category_name}
+                        </td>
+                        <td>{i18n.amount(category.total)}</td>
+                        <td>{category.count}</td>
+                        <td>{i18n.amount(category.average)}</td>
+                        <td>{percent(category.share)}</td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+
+    let members_view = move || {
+        household.get().map(|household| {
+            let rows = expenses
+                .with(|expenses| member_totals(expenses, &selected.get(), &household))
+                .into_iter()
+                .map(|member| {
+                    view! {
+                        <tr>
+                            <td>{member.email}</td>
+                            <td>{i18n.amount(member.total)}</td>
+                            <td>{member.count}</td>
+                            <td>{percent(member.share)}</td>
+                        </tr>
+                    }
+                })
+                .collect_view();
+            view! {
+                <table class="report">
+                    <thead>
+                        <tr>
+                            <th>{i18n.t(Key::PaidBy)}</th>
+                            <th>{i18n.t(Key::Total)}</th>
+                            <th>{i18n.t(Key::Count)}</th>
+                            <th>{i18n.t(Key::Share)}</th>
+                        </tr>
+                    </thead>
+                    <tbody>{rows}</tbody>
+                </table>
+            }
+        })
+    };
+
+    view! {
+            <div class="container">
+                <input
+                    type="month"
+                    on:change=move|ev|set_month(Some(event_target_value(&ev)).filter(|v| !v.is_empty()))
+                    prop:value=move||selected.get()
+                />
+                <Show
+                    when={move||summary.get().count != 0}
+                    fallback=move||view! { <p>{i18n.t(Key::NoExpensesThisMonth)}</p> }
+                >
+                    <div class="charts">
+                        {move||view! { <CategoryDonut categories={summary.get().categories} colors={colors()}/> }}
+                        {move||view! { <CumulativeLine days={daily_cumulative(&expenses.get(), &selected.get())}/> }}
+                    </div>
+                    <table class="report">
+                        <thead>
+                            <tr>
+                                <th>{move||i18n.t(Key::Category)}</th>
+                                <th>{move||i18n.t(Key::Total)}</th>
+                                <th>{move||i18n.t(Key::Count)}</th>
+                                <th>{move||i18n.t(Key::Average)}</th>
+                                <th>{move||i18n.t(Key::Share)}</th>
+                            </tr>
+                        </thead>
+                        <tbody>{rows}</tbody>
+                        <tfoot>
+                            <tr>
+                                <th>{move||i18n.t(Key::Month)}</th>
+                                <th>{move||i18n.amount(summary.get().total)}</th>
+                                <th>{move||summary.get().count}</th>
+                                <th>{move||i18n.amount(summary.get().average)}</th>
+                                <th>{percent(1.0)}</th>
+                            </tr>
+                        </tfoot>
+                    </table>
+                    {members_view}
+                    {move||view! { <MonthlyBars months={history()} colors={colors()}/> }}
+                </Show>
+            </div>
+    }
+}
@@ -0,0 +1,269 @@
+use core_api::{
+    error::AppError,
+    models::{Category, Expense},
+    rules::{Condition, Pattern, Rule, RuleAction},
+};
+use leptos::{component, ev::MouseEvent, *};
+use uuid::Uuid;
+
+use crate::{
+    i18n::{use_i18n, I18n, Key},
+    store::use_store,
+    utils::error_toast,
+};
+
+#[derive(Clone, Default)]
+struct RuleDraft {
+    name: String,
+    category_id: Uuid,
+    action: RuleAction,
+    contains: String,
+    pattern: String,
+    min: Option<i32>,
+    max: Option<i32>,
+    account: String,
+}
+
+impl RuleDraft {
+    fn to_rule(&self, categories: &[Category]) -> Rule {
+        let category_name = categories
+            .iter()
+            .find(|category| category.id == self.category_id)
+            .map(|category| category.name.clone())
+            .unwrap_or_default();
+        let mut rule = Rule::new()
+            .name(self.name.clone())
+            .category(self.category_id, category_name)
+            .action(self.action);
+        if !self.contains.is_empty() {
+            rule = rule.condition(Condition::DescriptionContains {
+                text: self.contains.clone(),
+            });
+        }
+        if !self.pattern.is_empty() {
+            rule = rule.condition(Condition::DescriptionRegex {
+                pattern: Pattern::new(self.pattern.clone()),
+            });
+        }
+        if self.min.is_some() || self.max.is_some() {
+            rule = rule.condition(Condition::AmountRange {
+                min: self.min,
+                max: self.max,
+            });
+        }
+        if !self.account.is_empty() {
+            rule = rule.condition(Condition::Account {
+                account: self.account.clone(),
+            });
+        }
+        rule
+    }
+}
+
+fn describe(i18n: I18n, condition: &Condition) -> String {
+    match condition {
+        Condition::DescriptionContains { text } => {
+            i18n.t(Key::RuleContains).replace("{text}", text)
+        }
+        Condition::DescriptionRegex { pattern } => i18n
+            .t(Key::RuleMatches)
+            .replace("{pattern}", &pattern.to_string()),
+        Condition::AmountRange {
+            min: Some(min),
+            max: Some(max),
+        } => i18n
+            .t(Key::RuleAmountBetween)
+            .replace("{min}", &i18n.amount(*min))
+            .replace("{max}", &i18n.amount(*max)),
+        Condition::AmountRange { min, max: None } => i18n
+            .t(Key::RuleAmountAtLeast)
+            .replace("{min}", &i18n.amount(min.unwrap_or_default())),
+        Condition::AmountRange {
+            min: None,
+            max: Some(max),
+        } => i18n
+            .t(Key::RuleAmountUpTo)
+            .replace("{max}", &i18n.amount(*max)),
+        Condition::Account { account } => i18n.t(Key::RuleAccount).replace("{account}", account),
+    }
+}
+
+#[component]
+pub fn rules_editor() -> impl IntoView {
+    let store = use_store();
+    let i18n = use_i18n();
+    let (settings, categories) = (store.settings(), store.categories());
+    let (draft, set_draft) = create_signal(RuleDraft::default());
+    let (tested, set_tested) = create_signal(None::<Vec<Expense>>);
+
+    let persist = move |rules: Vec<Rule>| {
+        store.update_settings(|settings| settings.rules = rules);
+        spawn_local(async move {
+            if let Err(e) = store.save_settings().await {
+                error_toast(e);
+            }
+        });
+    };
+
+    let test_rule = move |e: MouseEvent| {
+        e.prevent_default();
+        let rule = draft.get_untracked().to_rule(&categories.get_untracked());
+        if let Some(error) = rule.pattern_error() {
+            return error_toast(AppError::HardError(error.to_owned()).into());
+        }
+        spawn_local(async move {
+            match store.all_expenses().await {
+                Ok(expenses) => {
+                    set_tested.set(Some(rule.preview(&expenses).into_iter().cloned().collect()))
+                }
+                Err(e) => error_toast(e),
+            }
+        });
+    };
+    let add_rule = move |e: MouseEvent| {
+        e.prevent_default();
+        let rule = draft.get_untracked().to_rule(&categories.get_untracked());
+        if let Err(e) = rule.validate() {
+            return error_toast(e.into());
+        }
+        let mut rules = settings.get_untracked().rules;
+        rules.push(rule);
+        persist(rules);
+        set_draft.set(RuleDraft::default());
+        set_tested.set(None);
+    };
+
+    let rules_view = move || {
+        settings
+            .get()
+            .rules
+            .into_iter()
+            .map(|rule| {
+                let id = rule.id;
+                let conditions: Vec<String> = rule
+                    .conditions
+                    .iter()
+                    .map(|condition| describe(i18n, condition))
+                    .collect();
+                let and = format!(" {} ", i18n.t(Key::RuleAnd));
+                let action = match rule.action {
+                    RuleAction::Assign => i18n.t(Key::RuleAssigns),
+                    RuleAction::Suggest => i18n.t(Key::RuleSuggests),
+                };
+                view! {
+                    <li class="row">
+                        <p>
+                            {rule.name} ": " {conditions.join(&and)} " → " {action} " "
+                            {rule.category_name}
+                        </p>
+                        <button on:click=move|_|{
+                            let mut rules = settings.get_untracked().rules;
+                            rules.retain(|rule| rule.id != id);
+                            persist(rules);
+                        }>
+                            {i18n.t(Key::Delete)}
+                        </button>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+    let categories_view = move || {
+        categories
+            .get()
+            .iter()
+            .map(|category| {
+                view! {
+                <option value={category.id.to_string()}>
+                    <p>{category.name.clone()}</p>
+                </option>}
+            })
+            .collect_view()
+    };
+    let tested_view = move || {
+        tested.get().map(|matched| {
+            let count = matched.len();
+            let items = matched
+                .into_iter()
+                .map(|expense| {
+                    view! {
+                        <li>
+                            {i18n.date(&expense.date.unwrap_or_default())} " " {i18n.amount(expense.amount)} " "
+                            {expense.description.unwrap_or_default()}
+                        </li>
+                    }
+                })
+                .collect_view();
+            view! {
+                <p>{i18n.t(Key::RuleMatchesExpenses).replace("{count}", &count.to_string())}</p>
+                <ul>{items}</ul>
+            }
+        })
+    };
+
+    view! {
+            <ul>{rules_view}</ul>
+            <form class="container">
+               <div class="row" >
+                    <input
+                        placeholder=move||i18n.t(Key::RuleName)
+                        on:input=move|ev|set_draft.update(|draft| draft.name = event_target_value(&ev))
+                        prop:value={move||draft.get().name}
+                    />
+               </div>
+               <div class="row" >
+                    <input
+                        placeholder=move||i18n.t(Key::DescriptionContains)
+                        on:input=move|ev|set_draft.update(|draft| draft.contains = event_target_value(&ev))
+                        prop:value={move||draft.get().contains}
+                    />
+                    <input
+                        placeholder=move||i18n.t(Key::DescriptionRegex)
+                        on:input=move|ev|set_draft.update(|draft| draft.pattern = event_target_value(&ev))
+                        prop:value={move||draft.get().pattern}
+                    />
+               </div>
+               <div class="row" >
+                    <input
+                        type="number"
+                        placeholder=move||i18n.t(Key::MinAmount)
+                        on:input=move|ev|set_draft.update(|draft| draft.min = event_target_value(&ev).parse().ok())
+                        prop:value={move||draft.get().min.map(|min| min.to_string()).unwrap_or_default()}
+                    />
+                    <input
+                        type="number"
+                        placeholder=move||i18n.t(Key::MaxAmount)
+                        on:input=move|ev|set_draft.update(|draft| draft.max = event_target_value(&ev).parse().ok())
+                        prop:value={move||draft.get().max.map(|max| max.to_string()).unwrap_or_default()}
+                    />
+               </div>
+               <div class="row" >
+                    <input
+                        placeholder=move||i18n.t(Key::Account)
+                        on:input=move|ev|set_draft.update(|draft| draft.account = event_target_value(&ev))
+                        prop:value={move||draft.get().account}
+                    />
+               </div>
+                   <select
+                        on:change=move|ev|set_draft.update(|draft| draft.category_id = Uuid::parse_str(&event_target_value(&ev)).unwrap_or_default())
+                        prop:value={move||draft.get().category_id.to_string()}
+                        >
+                        <option value={Uuid::default().to_string()}>{move||i18n.t(Key::SelectCategory)}</option>
+                        {categories_view}
+                   </select>
+                   <select on:change=move|ev|set_draft.update(|draft| {
+                        draft.action = match event_target_value(&ev).as_str() {
+                            "assign" => RuleAction::Assign,
+                            _ => RuleAction::Suggest,
+                        }
+                   })>
+                        <option value="suggest">{move||i18n.t(Key::SuggestCategory)}</option>
+                        <option value="assign">{move||i18n.t(Key::AssignCategory)}</option>
+                   </select>
+               <br />
+               <button on:click=test_rule>{move||i18n.t(Key::TestRule)}</button>
+               <button on:click=add_rule>{move||i18n.t(Key::AddRule)}</button>
+               {tested_view}
+            </form>
+    }
+}
@@ -0,0 +1,101 @@
+use core_api::error::AppError;
+use leptos::{component, error::Result, *};
+
+use crate::{
+    components::{credentials_form::CredentialsForm, household_panel::HouseholdPanel},
+    i18n::{use_i18n, Key},
+    store::use_store,
+    toasts::{toaster, ToastLevel},
+    utils::{api_url, error_toast, get_headers},
+};
+
+/// Whether the server still accepts the saved token; any authenticated request tells.
+pub async fn check_token() -> Result<bool> {
+    let resp = reqwasm::http::Request::get(&format!("{}/categories", api_url().await))
+        .headers(get_headers().await)
+        .send()
+        .await?;
+    match resp.status() {
+        401 => Ok(false),
+        _ if resp.ok() => Ok(true),
+        _ => Err(AppError::HardError(resp.text().await?).into()),
+    }
+}
+
+pub async fn post_logout() -> Result<()> {
+    let resp = reqwasm::http::Request::post(&format!("{}/logout", api_url().await))
+        .headers(get_headers().await)
+        .send()
+        .await?;
+    if resp.ok() || resp.status() == 401 {
+        return Ok(());
+    }
+    Err(AppError::HardError(resp.text().await?).into())
+}
+
+#[component]
+pub fn session_panel() -> impl IntoView {
+    let store = use_store();
+    let i18n = use_i18n();
+    let session = store.session();
+    let signed_in = move || session.with(|session| !session.token.is_empty());
+    let server = create_resource(|| (), |_| api_url());
+    let validity = create_resource(
+        move || session.get().token,
+        |token| async move {
+            if token.is_empty() {
+                return None;
+            }
+            match check_token().await {
+                Ok(valid) => Some(valid),
+                Err(e) => {
+                    error_toast(e);
+                    None
+                }
+            }
+        },
+    );
+    let validity_view = move || match validity.get() {
+        None => i18n.t(Key::Checking),
+        Some(None) => i18n.t(Key::TokenUnknown),
+        Some(Some(true)) => i18n.t(Key::TokenValid),
+        Some(Some(false)) => i18n.t(Key::TokenExpired),
+    };
+
+    let log_out = move |_| {
+        spawn_local(async move {
+            match store.sign_out().await {
+                Ok(_) => {
+                    toaster().show(ToastLevel::Success, i18n.t(Key::LoggedOut));
+                }
+                Err(e) => error_toast(e),
+            }
+        });
+    };
+
+    view! {
+        <Show when=signed_in fallback=|| view! { <CredentialsForm/> }>
+            <div class="container">
+                <table class="report">
+                    <tbody>
+                        <tr>
+                            <th>{move||i18n.t(Key::User)}</th>
+                            <td>{move||session.get().email.unwrap_or_else(|| i18n.t(Key::UnknownUser).to_owned())}</td>
+                        </tr>
+                        <tr>
+                            <th>{move||i18n.t(Key::Server)}</th>
+                            <td>{move||server.get().unwrap_or_default()}</td>
+                        </tr>
+                        <tr>
+                            <th>{move||i18n.t(Key::Token)}</th>
+                            <td>{validity_view}</td>
+                        </tr>
+                    </tbody>
+                </table>
+                <button on:click=log_out>{move||i18n.t(Key::LogOut)}</button>
+            </div>
+            <br />
+            <HouseholdPanel/>
+        </Show>
+    }
+}
@@ -0,0 +1,99 @@
+use core_api::models::Language;
+use leptos::{component, ev::SubmitEvent, *};
+
+use crate::{
+    i18n::{language_code, language_from_code, language_name, use_i18n, Key},
+    store::use_store,
+    toasts::{toaster, ToastLevel},
+    utils::error_toast,
+};
+
+#[component]
+pub fn settings_form() -> impl IntoView {
+    let store = use_store();
+    let settings = store.settings();
+    let i18n = use_i18n();
+    let update_server_url = move |ev| {
+        let v = event_target_value(&ev);
+        store.update_settings(|settings| settings.server_url(Some(v)));
+    };
+
+    let update_duplicate_window = move |ev| {
+        let v = event_target_value(&ev).parse().unwrap_or(0);
+        store.update_settings(|settings| settings.duplicate_window_days = v);
+    };
+
+    // Applies right away, without waiting for the form to be saved.
+    let update_language = move |ev| {
+        let language = language_from_code(&event_target_value(&ev));
+        store.update_settings(|settings| settings.language = language);
+        spawn_local(async move {
+            if let Err(e) = store.save_settings().await {
+                error_toast(e);
+            }
+        });
+    };
+    let language_options = [Language::English, Language::Spanish]
+        .into_iter()
+        .map(|language| {
+            view! {
+                <option
+                    value={language_code(language)}
+                    selected={move||settings.get().language == Some(language)}
+                >
+                    {language_name(language)}
+                </option>
+            }
+        })
+        .collect_view();
+
+    let save_settings = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        spawn_local(async move {
+            match store.save_settings().await {
+                Ok(_) => {
+                    toaster().show(ToastLevel::Success, "Settings saved.");
+                    store.refresh();
+                }
+                Err(e) => error_toast(e),
+            }
+        });
+    };
+
+    view! {
+            <form class="container" on:submit=save_settings>
+               <div class="row" >
+                    <input
+                        id="server-url-input"
+                        type="url"
+                        placeholder="Server URL (leave empty for the default)"
+                        on:input=update_server_url
+                        prop:value={move||settings.get().server_url.unwrap_or_default()}
+                    />
+               </div>
+               <br />
+               <div class="row" >
+                    <label for="duplicate-window-input">"Possible duplicates within (days)"</label>
+                    <input
+                        id="duplicate-window-input"
+                        type="number"
+                        min="0"
+                        on:input=update_duplicate_window
+                        prop:value={move||settings.get().duplicate_window_days}
+                    />
+               </div>
+               <br />
+               <div class="row" >
+                    <label for="language-select">{move||i18n.t(Key::Language)}</label>
+                    <select id="language-select" on:change=update_language>
+                        <option value="" selected={move||settings.get().language.is_none()}>
+                            {move||i18n.t(Key::SystemLanguage)}
+                        </option>
+                        {language_options}
+                    </select>
+               </div>
+               <br />
+               <button type="submit">"Save settings"</button>
+            </form>
+    }
+}
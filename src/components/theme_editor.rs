@@ -0,0 +1,106 @@
+use core_api::models::Theme;
+use leptos::{component, *};
+use uuid::Uuid;
+
+use crate::{
+    store::{use_store, Store},
+    utils::error_toast,
+};
+
+/// `styles.css` picks the palette from `data-theme`, and follows the system without it.
+pub fn apply_theme(theme: Theme) {
+    let Some(root) = document().document_element() else {
+        return;
+    };
+    _ = match theme {
+        Theme::System => root.remove_attribute("data-theme"),
+        Theme::Light => root.set_attribute("data-theme", "light"),
+        Theme::Dark => root.set_attribute("data-theme", "dark"),
+    };
+}
+
+fn save(store: Store) {
+    spawn_local(async move {
+        if let Err(e) = store.save_settings().await {
+            error_toast(e);
+        }
+    });
+}
+
+#[component]
+pub fn theme_editor() -> impl IntoView {
+    let store = use_store();
+    let (settings, categories) = (store.settings(), store.categories());
+
+    let update_theme = move |ev| {
+        let theme = match event_target_value(&ev).as_str() {
+            "light" => Theme::Light,
+            "dark" => Theme::Dark,
+            _ => Theme::System,
+        };
+        store.update_settings(|settings| settings.theme = theme);
+        save(store);
+    };
+    let pick_color = move |category_id: Uuid, color: Option<String>| {
+        store.update_settings(|settings| match color {
+            Some(color) => {
+                settings.category_colors.insert(category_id, color);
+            }
+            None => {
+                settings.category_colors.remove(&category_id);
+            }
+        });
+        save(store);
+    };
+
+    let theme_options = [
+        (Theme::System, "system", "System"),
+        (Theme::Light, "light", "Light"),
+        (Theme::Dark, "dark", "Dark"),
+    ]
+    .into_iter()
+    .map(|(theme, value, label)| {
+        view! {
+            <option value={value} selected={move||settings.get().theme == theme}>{label}</option>
+        }
+    })
+    .collect_view();
+    let colors_view = move || {
+        categories
+            .get()
+            .into_iter()
+            .map(|category| {
+                let id = category.id;
+                let custom = move || settings.with(|settings| settings.category_colors.contains_key(&id));
+                view! {
+                    <li class="row">
+                        <label for={format!("color-{id}")}>{category.name}</label>
+                        <input
+                            id={format!("color-{id}")}
+                            type="color"
+                            class="color-input"
+                            on:change=move|ev|pick_color(id, Some(event_target_value(&ev)))
+                            prop:value={move||settings.with(|settings| settings.category_color(id))}
+                        />
+                        <Show when=custom fallback=||()>
+                            <button type="button" class="inline-button" on:click=move|_|pick_color(id, None)>"Reset"</button>
+                        </Show>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+            <div class="container">
+               <div class="row" >
+                    <label for="theme-select">"Theme"</label>
+                    <select id="theme-select" on:change=update_theme>
+                        {theme_options}
+                    </select>
+               </div>
+               <p>"Category colors"</p>
+               <ul>{colors_view}</ul>
+            </div>
+    }
+}
@@ -0,0 +1,108 @@
+use core_api::models::{Expense, TrashedExpense};
+use leptos::{component, error::Result, *};
+use uuid::Uuid;
+
+use crate::{
+    components::expense_card::remove_post,
+    i18n::{use_i18n, Key},
+    store::use_store,
+    utils::{call, error_toast},
+};
+
+/// How long a delete can be undone before it reaches the server.
+pub const UNDO_SECONDS: u32 = 5;
+
+/// A delete waiting for its undo window to pass. The token tells apart deleting,
+/// undoing and deleting the same expense again.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendingDelete {
+    pub token: Uuid,
+    pub expense: Expense,
+}
+
+/// Keeps a copy in this device's trash, then deletes on the server. The copy goes first so
+/// nothing is deleted that could not be restored, and is taken back if the delete fails.
+pub async fn delete_expense(expense: Expense) -> Result<()> {
+    let id = expense.id;
+    call::<()>("add_to_trash", serde_json::json!({ "expense": expense })).await?;
+    if let Err(e) = remove_post(expense).await {
+        if let Err(undo) = call::<()>("remove_from_trash", serde_json::json!({ "id": id })).await {
+            error_toast(undo);
+        }
+        return Err(e);
+    }
+    Ok(())
+}
+
+#[component]
+pub fn trash_view() -> impl IntoView {
+    let store = use_store();
+    let i18n = use_i18n();
+    let (trash, set_trash) = create_signal(Vec::<TrashedExpense>::new());
+    let fetch_trash = move || {
+        spawn_local(async move {
+            match call::<Vec<TrashedExpense>>("get_trash", ()).await {
+                Ok(trash) => set_trash.set(trash),
+                Err(e) => error_toast(e),
+            }
+        });
+    };
+    fetch_trash();
+
+    let forget = move |id: Uuid| async move {
+        call::<()>("remove_from_trash", serde_json::json!({ "id": id })).await
+    };
+    // Added back under its old id where the server keeps ids, so links and backups still
+    // find it; other servers give it a new one.
+    let restore = move |expense: Expense| {
+        spawn_local(async move {
+            let id = expense.id;
+            if let Err(e) = store.restore_expense(expense).await {
+                return error_toast(e);
+            }
+            if let Err(e) = forget(id).await {
+                error_toast(e);
+            }
+            fetch_trash();
+        });
+    };
+    let delete_forever = move |id: Uuid| {
+        spawn_local(async move {
+            if let Err(e) = forget(id).await {
+                error_toast(e);
+            }
+            fetch_trash();
+        });
+    };
+
+    let trash_view = move || {
+        trash
+            .get()
+            .into_iter()
+            .map(|trashed| {
+                let expense = trashed.expense;
+                let id = expense.id;
+                let restored = expense.clone();
+                view! {
+                    <li class="row">
+                        <p>
+                            {i18n.date(&expense.date.unwrap_or_default())} " " {i18n.amount(expense.amount)} " "
+                            {expense.category_name} " / " {expense.description.unwrap_or_default()}
+                        </p>
+                        <button on:click=move|_|restore(restored.clone())>{move||i18n.t(Key::Restore)}</button>
+                        <button on:click=move|_|delete_forever(id)>{move||i18n.t(Key::DeleteForever)}</button>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+            <div class="container">
+                <p>{move||i18n.t(Key::TrashExplained)}</p>
+                <Show when=move||!trash.get().is_empty() fallback=move||view! { <p>{i18n.t(Key::TrashEmpty)}</p> }>
+                    <ul>{trash_view}</ul>
+                </Show>
+            </div>
+    }
+}
@@ -0,0 +1,501 @@
+use core_api::models::{parse_date, Language, Settings};
+use leptos::*;
+
+/// Every translated string. Placeholders in braces are filled in by the caller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    Session,
+    NewExpense,
+    AllExpenses,
+    Reports,
+    Settings,
+    Refetch,
+    ReviewDuplicates,
+    RecentlyDeleted,
+    BackToExpenses,
+    EnterAmount,
+    EnterDate,
+    SelectCategory,
+    SuggestedCategory,
+    EnterDescription,
+    AddExpense,
+    AlreadySaved,
+    SaveAnyway,
+    /// `{amount}`, `{category}`.
+    Saved,
+    EnterEmail,
+    EnterPassword,
+    LogIn,
+    MissingCredentials,
+    SignedIn,
+    User,
+    UnknownUser,
+    Server,
+    Token,
+    Checking,
+    TokenValid,
+    TokenExpired,
+    TokenUnknown,
+    LogOut,
+    LoggedOut,
+    CreateAccount,
+    ForgotPassword,
+    BackToLogin,
+    Register,
+    Registered,
+    NewPassword,
+    ConfirmPassword,
+    PasswordStrength,
+    StrengthWeak,
+    StrengthFair,
+    StrengthStrong,
+    /// `{min}`.
+    PasswordTooShort,
+    PasswordTooWeak,
+    PasswordsDiffer,
+    SendResetCode,
+    ResetCodeSent,
+    EnterResetCode,
+    SetNewPassword,
+    PasswordChanged,
+    EnterPin,
+    Unlock,
+    WrongPin,
+    /// `{seconds}`.
+    PinBackoff,
+    Language,
+    SystemLanguage,
+    PaidBy,
+    /// `{member}`.
+    PaidByMember,
+    AnyonePaid,
+    SearchDescriptions,
+    FromDate,
+    ToDate,
+    MinAmount,
+    MaxAmount,
+    ClearFilters,
+    /// `{from}`, `{to}`.
+    Between,
+    /// `{from}`.
+    Since,
+    /// `{to}`.
+    Until,
+    /// `{amount}`.
+    AtLeast,
+    /// `{amount}`.
+    UpTo,
+    /// `{shown}`, `{count}`, `{total}`.
+    ShowingExpenses,
+    Category,
+    Total,
+    Count,
+    Average,
+    Share,
+    Month,
+    NoExpensesThisMonth,
+    Household,
+    HouseholdName,
+    CreateHousehold,
+    InvitationCode,
+    JoinHousehold,
+    JoinedHousehold,
+    InviteSomeone,
+    LeaveHousehold,
+    LeftHousehold,
+    Owner,
+    You,
+    /// `{date}`.
+    InviteWorksOnce,
+    Date,
+    Amount,
+    Description,
+    Account,
+    Delete,
+    ExpenseNotFound,
+    RuleName,
+    DescriptionContains,
+    DescriptionRegex,
+    SuggestCategory,
+    AssignCategory,
+    TestRule,
+    AddRule,
+    /// `{text}`.
+    RuleContains,
+    /// `{pattern}`.
+    RuleMatches,
+    /// `{min}`, `{max}`.
+    RuleAmountBetween,
+    /// `{min}`.
+    RuleAmountAtLeast,
+    /// `{max}`.
+    RuleAmountUpTo,
+    /// `{account}`.
+    RuleAccount,
+    /// Joins a rule's conditions.
+    RuleAnd,
+    RuleAssigns,
+    RuleSuggests,
+    /// `{count}`.
+    RuleMatchesExpenses,
+    ChooseImportFile,
+    /// `{imported}`, `{categorized}`, `{skipped}`, `{credits}`.
+    ImportSummary,
+    /// `{count}`.
+    ImportLeftOut,
+    /// `{count}`.
+    ExpensesNeedCategory,
+    FallbackCategory,
+    QifDayFirst,
+    QifMonthFirst,
+    ImportFile,
+    Restore,
+    DeleteForever,
+    TrashExplained,
+    TrashEmpty,
+    KeepOnlyThis,
+    /// `{count}`.
+    DuplicateGroups,
+    Loading,
+    LoadMore,
+    /// `{amount}`. A group subtotal while more pages remain to load.
+    SubtotalSoFar,
+}
+
+impl Key {
+    fn texts(self) -> (&'static str, &'static str) {
+        match self {
+            Key::Session => ("Session", "Sesión"),
+            Key::NewExpense => ("New Expense", "Nuevo gasto"),
+            Key::AllExpenses => ("All Expenses", "Todos los gastos"),
+            Key::Reports => ("Reports", "Reportes"),
+            Key::Settings => ("Settings", "Ajustes"),
+            Key::Refetch => ("Refetch", "Recargar"),
+            Key::ReviewDuplicates => ("Review possible duplicates", "Revisar posibles duplicados"),
+            Key::RecentlyDeleted => ("Recently deleted", "Eliminados recientemente"),
+            Key::BackToExpenses => ("Back to all expenses", "Volver a todos los gastos"),
+            Key::EnterAmount => ("Enter an amount", "Ingresa un monto"),
+            Key::EnterDate => ("Enter a date", "Ingresa una fecha"),
+            Key::SelectCategory => ("Select category", "Selecciona una categoría"),
+            Key::SuggestedCategory => ("Suggested category: ", "Categoría sugerida: "),
+            Key::EnterDescription => ("Enter a description", "Ingresa una descripción"),
+            Key::AddExpense => ("Add Expense", "Agregar gasto"),
+            Key::AlreadySaved => (
+                "This looks like an expense that is already saved:",
+                "Este gasto parece estar guardado ya:",
+            ),
+            Key::SaveAnyway => ("Save anyway", "Guardar de todos modos"),
+            Key::Saved => (
+                "Saved {amount} in {category}",
+                "Guardado {amount} en {category}",
+            ),
+            Key::EnterEmail => ("Enter your email", "Ingresa tu correo"),
+            Key::EnterPassword => ("Enter your password.", "Ingresa tu contraseña."),
+            Key::LogIn => ("Log in", "Iniciar sesión"),
+            Key::MissingCredentials => (
+                "Enter your email and password.",
+                "Ingresa tu correo y contraseña.",
+            ),
+            Key::SignedIn => ("Signed in.", "Sesión iniciada."),
+            Key::User => ("User", "Usuario"),
+            Key::UnknownUser => ("Unknown", "Desconocido"),
+            Key::Server => ("Server", "Servidor"),
+            Key::Token => ("Token", "Token"),
+            Key::Checking => ("Checking…", "Comprobando…"),
+            Key::TokenValid => ("Valid", "Válido"),
+            Key::TokenExpired => ("Expired, log in again", "Vencido, inicia sesión de nuevo"),
+            Key::TokenUnknown => ("Could not be checked", "No se pudo comprobar"),
+            Key::LogOut => ("Log out", "Cerrar sesión"),
+            Key::LoggedOut => ("Logged out.", "Sesión cerrada."),
+            Key::CreateAccount => ("Create an account", "Crear una cuenta"),
+            Key::ForgotPassword => ("Forgot your password?", "¿Olvidaste tu contraseña?"),
+            Key::BackToLogin => ("Back to log in", "Volver a iniciar sesión"),
+            Key::Register => ("Sign up", "Registrarme"),
+            Key::Registered => ("Account created.", "Cuenta creada."),
+            Key::NewPassword => ("New password", "Nueva contraseña"),
+            Key::ConfirmPassword => ("Repeat the password", "Repite la contraseña"),
+            Key::PasswordStrength => ("Strength: ", "Seguridad: "),
+            Key::StrengthWeak => ("weak", "débil"),
+            Key::StrengthFair => ("fair", "aceptable"),
+            Key::StrengthStrong => ("strong", "fuerte"),
+            Key::PasswordTooShort => (
+                "The password needs at least {min} characters.",
+                "La contraseña necesita al menos {min} caracteres.",
+            ),
+            Key::PasswordTooWeak => (
+                "Mix upper and lower case letters, digits or symbols.",
+                "Combina mayúsculas, minúsculas, números o símbolos.",
+            ),
+            Key::PasswordsDiffer => (
+                "The passwords do not match.",
+                "Las contraseñas no coinciden.",
+            ),
+            Key::SendResetCode => ("Send a reset code", "Enviar un código"),
+            Key::ResetCodeSent => (
+                "If the email has an account, a reset code is on its way.",
+                "Si el correo tiene una cuenta, recibirás un código.",
+            ),
+            Key::EnterResetCode => ("Enter the reset code", "Ingresa el código"),
+            Key::SetNewPassword => ("Set the new password", "Guardar la nueva contraseña"),
+            Key::PasswordChanged => (
+                "Password changed, you are logged in.",
+                "Contraseña cambiada, sesión iniciada.",
+            ),
+            Key::EnterPin => ("Enter your PIN", "Ingresa tu PIN"),
+            Key::Unlock => ("Unlock", "Desbloquear"),
+            Key::WrongPin => ("Wrong PIN.", "PIN incorrecto."),
+            Key::PinBackoff => (
+                "Too many wrong PINs, try again in {seconds} s.",
+                "Demasiados intentos, vuelve a probar en {seconds} s.",
+            ),
+            Key::Language => ("Language", "Idioma"),
+            Key::SystemLanguage => ("System language", "Idioma del sistema"),
+            Key::PaidBy => ("Paid by", "Pagado por"),
+            Key::PaidByMember => ("Paid by {member}", "Pagado por {member}"),
+            Key::AnyonePaid => ("Anyone paid", "Pagado por cualquiera"),
+            Key::SearchDescriptions => ("Search descriptions", "Buscar en las descripciones"),
+            Key::FromDate => ("From", "Desde"),
+            Key::ToDate => ("To", "Hasta"),
+            Key::MinAmount => ("Min amount", "Monto mínimo"),
+            Key::MaxAmount => ("Max amount", "Monto máximo"),
+            Key::ClearFilters => ("Clear filters", "Quitar filtros"),
+            Key::Between => ("{from} to {to}", "{from} a {to}"),
+            Key::Since => ("From {from}", "Desde {from}"),
+            Key::Until => ("Until {to}", "Hasta {to}"),
+            Key::AtLeast => ("At least {amount}", "Al menos {amount}"),
+            Key::UpTo => ("Up to {amount}", "Hasta {amount}"),
+            Key::ShowingExpenses => (
+                "Showing {shown} of {count} expenses, {total} in total.",
+                "Mostrando {shown} de {count} gastos, {total} en total.",
+            ),
+            Key::Category => ("Category", "Categoría"),
+            Key::Total => ("Total", "Total"),
+            Key::Count => ("Count", "Cantidad"),
+            Key::Average => ("Average", "Promedio"),
+            Key::Share => ("Share", "Porcentaje"),
+            Key::Month => ("Month", "Mes"),
+            Key::NoExpensesThisMonth => ("No expenses this month.", "Sin gastos este mes."),
+            Key::Household => ("Household", "Hogar"),
+            Key::HouseholdName => ("Household name", "Nombre del hogar"),
+            Key::CreateHousehold => ("Create household", "Crear hogar"),
+            Key::InvitationCode => ("Invitation code", "Código de invitación"),
+            Key::JoinHousehold => ("Join household", "Unirme al hogar"),
+            Key::JoinedHousehold => ("Joined the household.", "Te uniste al hogar."),
+            Key::InviteSomeone => ("Invite someone", "Invitar a alguien"),
+            Key::LeaveHousehold => ("Leave household", "Salir del hogar"),
+            Key::LeftHousehold => ("You left the household.", "Saliste del hogar."),
+            Key::Owner => ("owner", "dueño"),
+            Key::You => ("you", "tú"),
+            Key::Date => ("Date", "Fecha"),
+            Key::Amount => ("Amount", "Monto"),
+            Key::Description => ("Description", "Descripción"),
+            Key::Account => ("Account", "Cuenta"),
+            Key::Delete => ("Delete", "Eliminar"),
+            Key::ExpenseNotFound => ("Expense not found.", "No se encontró el gasto."),
+            Key::RuleName => ("Rule name", "Nombre de la regla"),
+            Key::DescriptionContains => ("Description contains", "La descripción contiene"),
+            Key::DescriptionRegex => ("Description regex", "Expresión regular de la descripción"),
+            Key::SuggestCategory => ("Suggest the category", "Sugerir la categoría"),
+            Key::AssignCategory => ("Assign the category", "Asignar la categoría"),
+            Key::TestRule => ("Test rule", "Probar la regla"),
+            Key::AddRule => ("Add rule", "Agregar la regla"),
+            Key::RuleContains => ("contains \"{text}\"", "contiene \"{text}\""),
+            Key::RuleMatches => ("matches /{pattern}/", "coincide con /{pattern}/"),
+            Key::RuleAmountBetween => ("amount {min} to {max}", "monto de {min} a {max}"),
+            Key::RuleAmountAtLeast => ("amount at least {min}", "monto de al menos {min}"),
+            Key::RuleAmountUpTo => ("amount up to {max}", "monto de hasta {max}"),
+            Key::RuleAccount => ("account {account}", "cuenta {account}"),
+            Key::RuleAnd => ("and", "y"),
+            Key::RuleAssigns => ("assign", "asignar"),
+            Key::RuleSuggests => ("suggest", "sugerir"),
+            Key::RuleMatchesExpenses => (
+                "{count} existing expenses match.",
+                "{count} gastos existentes coinciden.",
+            ),
+            Key::ChooseImportFile => (
+                "Choose an OFX or QIF file to import.",
+                "Elige un archivo OFX o QIF para importar.",
+            ),
+            Key::ImportSummary => (
+                "Imported {imported} expenses ({categorized} categorized by rules), skipped {skipped} already imported, ignored {credits} credits.",
+                "Se importaron {imported} gastos ({categorized} categorizados por reglas), se omitieron {skipped} ya importados y {credits} abonos.",
+            ),
+            Key::ImportLeftOut => (
+                "{count} matched no rule and were left out; choose a category for them and import the file again.",
+                "{count} no coincidieron con ninguna regla y quedaron fuera; elige una categoría para ellos e importa el archivo de nuevo.",
+            ),
+            Key::ExpensesNeedCategory => (
+                "{count} expenses need a category.",
+                "{count} gastos necesitan una categoría.",
+            ),
+            Key::FallbackCategory => (
+                "Category when no rule matches",
+                "Categoría si ninguna regla coincide",
+            ),
+            Key::QifDayFirst => ("QIF dates: day first", "Fechas QIF: día primero"),
+            Key::QifMonthFirst => ("QIF dates: month first", "Fechas QIF: mes primero"),
+            Key::ImportFile => ("Import OFX / QIF", "Importar OFX / QIF"),
+            Key::Restore => ("Restore", "Restaurar"),
+            Key::DeleteForever => ("Delete forever", "Eliminar para siempre"),
+            Key::TrashExplained => (
+                "Expenses deleted on this device in the last 30 days.",
+                "Gastos eliminados en este dispositivo en los últimos 30 días.",
+            ),
+            Key::TrashEmpty => ("The trash is empty.", "La papelera está vacía."),
+            Key::KeepOnlyThis => ("Keep only this", "Conservar solo este"),
+            Key::DuplicateGroups => (
+                "{count} groups of possible duplicates.",
+                "{count} grupos de posibles duplicados.",
+            ),
+            Key::Loading => ("Loading…", "Cargando…"),
+            Key::LoadMore => ("Load more", "Cargar más"),
+            Key::SubtotalSoFar => ("{amount} so far", "{amount} hasta ahora"),
+            Key::InviteWorksOnce => (
+                "Works once, until {date}.",
+                "Sirve una vez, hasta el {date}.",
+            ),
+        }
+    }
+
+    pub fn text(self, language: Language) -> &'static str {
+        let (english, spanish) = self.texts();
+        match language {
+            Language::English => english,
+            Language::Spanish => spanish,
+        }
+    }
+}
+
+pub fn language_code(language: Language) -> &'static str {
+    match language {
+        Language::English => "en",
+        Language::Spanish => "es",
+    }
+}
+
+pub fn language_from_code(code: &str) -> Option<Language> {
+    match code.get(..2)?.to_ascii_lowercase().as_str() {
+        "en" => Some(Language::English),
+        "es" => Some(Language::Spanish),
+        _ => None,
+    }
+}
+
+/// Names each language in itself, so it can be found whatever is selected.
+pub fn language_name(language: Language) -> &'static str {
+    match language {
+        Language::English => "English",
+        Language::Spanish => "Español",
+    }
+}
+
+fn system_language() -> Language {
+    window()
+        .navigator()
+        .language()
+        .and_then(|code| language_from_code(&code))
+        .unwrap_or(Language::English)
+}
+
+/// Thousands separated, as in `12,345` or `12.345`.
+pub fn format_number(language: Language, number: i64) -> String {
+    let separator = match language {
+        Language::English => ',',
+        Language::Spanish => '.',
+    };
+    let digits = number.unsigned_abs().to_string();
+    let mut grouped = String::new();
+    for (at, digit) in digits.chars().enumerate() {
+        if at > 0 && (digits.len() - at).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    if number < 0 {
+        grouped.insert(0, '-');
+    }
+    grouped
+}
+
+/// Amounts are whole pesos, so there are no decimals.
+pub fn format_amount(language: Language, amount: i64) -> String {
+    let number = format_number(language, amount.abs());
+    if amount < 0 {
+        format!("-${number}")
+    } else {
+        format!("${number}")
+    }
+}
+
+const MONTHS: [(&str, &str); 12] = [
+    ("Jan", "ene"),
+    ("Feb", "feb"),
+    ("Mar", "mar"),
+    ("Apr", "abr"),
+    ("May", "may"),
+    ("Jun", "jun"),
+    ("Jul", "jul"),
+    ("Aug", "ago"),
+    ("Sep", "sept"),
+    ("Oct", "oct"),
+    ("Nov", "nov"),
+    ("Dec", "dic"),
+];
+
+/// `Oct 19, 2026` or `19 oct 2026`. Dates that do not parse are shown as they are.
+pub fn format_date(language: Language, date: &str) -> String {
+    let Some(parsed) = parse_date(date) else {
+        return date.to_owned();
+    };
+    let (english, spanish) = MONTHS[usize::from(u8::from(parsed.month())) - 1];
+    match language {
+        Language::English => format!("{english} {}, {}", parsed.day(), parsed.year()),
+        Language::Spanish => format!("{} {spanish} {}", parsed.day(), parsed.year()),
+    }
+}
+
+/// The selected language, shared through context.
+#[derive(Clone, Copy)]
+pub struct I18n {
+    language: Signal<Language>,
+}
+
+impl I18n {
+    pub fn language(&self) -> Language {
+        self.language.get()
+    }
+
+    pub fn t(&self, key: Key) -> &'static str {
+        key.text(self.language())
+    }
+
+    pub fn amount(&self, amount: impl Into<i64>) -> String {
+        format_amount(self.language(), amount.into())
+    }
+
+    pub fn date(&self, date: &str) -> String {
+        format_date(self.language(), date)
+    }
+}
+
+/// Follows the language in the settings, or the system's until one is picked. Call
+/// once, in `App`.
+pub fn provide_i18n(settings: ReadSignal<Settings>) -> I18n {
+    let language = Signal::derive(move || {
+        settings
+            .with(|settings| settings.language)
+            .unwrap_or_else(system_language)
+    });
+    create_effect(move |_| {
+        if let Some(root) = document().document_element() {
+            _ = root.set_attribute("lang", language_code(language.get()));
+        }
+    });
+    let i18n = I18n { language };
+    provide_context(i18n);
+    i18n
+}
+
+pub fn use_i18n() -> I18n {
+    expect_context::<I18n>()
+}
@@ -0,0 +1,112 @@
+use core_api::{lock::LockStatus, models::Settings};
+use gloo::timers::callback::Interval;
+use leptos::*;
+
+use crate::utils::{call, error_toast};
+
+/// How often idle time is counted.
+const TICK_SECONDS: u32 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockState {
+    /// Waiting for the backend to say whether a PIN is set; nothing is shown yet.
+    Checking,
+    Locked,
+    Unlocked,
+}
+
+/// The PIN lock over the whole app. The backend keeps and checks the PIN; this only
+/// decides when to ask for it.
+#[derive(Clone, Copy)]
+pub struct AppLock {
+    state: RwSignal<LockState>,
+    enabled: RwSignal<bool>,
+    idle_seconds: StoredValue<u32>,
+}
+
+impl AppLock {
+    pub fn state(&self) -> ReadSignal<LockState> {
+        self.state.read_only()
+    }
+
+    /// Whether a PIN is set.
+    pub fn enabled(&self) -> ReadSignal<bool> {
+        self.enabled.read_only()
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+    }
+
+    /// Does nothing without a PIN. The backend is told too, so that it refuses the
+    /// session, settings and data until the PIN is entered.
+    pub fn lock(&self) {
+        if self.enabled.get_untracked() {
+            self.state.set(LockState::Locked);
+            spawn_local(async {
+                if let Err(e) = call::<()>("lock_app", ()).await {
+                    error_toast(e);
+                }
+            });
+        }
+    }
+
+    /// Call once the backend accepted the PIN.
+    pub fn unlocked(&self) {
+        self.idle_seconds.set_value(0);
+        self.state.set(LockState::Unlocked);
+    }
+
+    fn tick(&self, settings: ReadSignal<Settings>) {
+        if self.state.get_untracked() != LockState::Unlocked {
+            return;
+        }
+        let Some(minutes) = settings.with_untracked(|settings| settings.idle_lock_minutes) else {
+            return;
+        };
+        let idle = self.idle_seconds.get_value() + TICK_SECONDS;
+        self.idle_seconds.set_value(idle);
+        if idle >= minutes * 60 {
+            self.lock();
+        }
+    }
+}
+
+/// Locks on launch when a PIN is set, and again after the idle time in the settings.
+/// Call once, in `App`.
+pub fn provide_lock(settings: ReadSignal<Settings>) -> AppLock {
+    let lock = AppLock {
+        state: create_rw_signal(LockState::Checking),
+        enabled: create_rw_signal(false),
+        idle_seconds: store_value(0),
+    };
+    provide_context(lock);
+    spawn_local(async move {
+        match call::<LockStatus>("lock_status", ()).await {
+            Ok(status) => {
+                lock.enabled.set(status.enabled);
+                lock.state.set(if status.enabled {
+                    LockState::Locked
+                } else {
+                    LockState::Unlocked
+                });
+            }
+            // A broken lock file must not keep the data out of reach.
+            Err(e) => {
+                error_toast(e);
+                lock.state.set(LockState::Unlocked);
+            }
+        }
+    });
+
+    let active = move || lock.idle_seconds.set_value(0);
+    window_event_listener(ev::pointerdown, move |_| active());
+    window_event_listener(ev::keydown, move |_| active());
+    window_event_listener(ev::wheel, move |_| active());
+    Interval::new(TICK_SECONDS * 1000, move || lock.tick(settings)).forget();
+    lock
+}
+
+pub fn use_lock() -> AppLock {
+    expect_context::<AppLock>()
+}
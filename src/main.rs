@@ -1,5 +1,9 @@
 mod app;
 mod components;
+mod i18n;
+mod lock;
+mod store;
+mod toasts;
 mod utils;
 
 use app::*;
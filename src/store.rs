@@ -0,0 +1,487 @@
+use core_api::{
+    alerts::Alert,
+    filters::ExpenseFilter,
+    household::{Household, Member},
+    models::{ApiResponse, Category, Expense, Settings},
+    paging::{ExpenseQuery, MAX_PAGE_SIZE},
+    reports::{monthly_summaries, MonthSummary},
+    session::Session,
+    sorting::ListOptions,
+};
+use gloo::timers::future::TimeoutFuture;
+use leptos::{error::Result, *};
+use serde::{de::DeserializeOwned, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    components::{
+        expense_card::remove_post,
+        expense_form::{post_expense, put_expense},
+        expense_list::fetch_page,
+        household_panel::fetch_household,
+        session_panel::post_logout,
+        trash::{delete_expense, PendingDelete, UNDO_SECONDS},
+    },
+    toasts::{toaster, ToastLevel},
+    utils::{api_url, call, error_toast, get_headers, save_settings, utc_offset_minutes},
+};
+
+/// Failed loads stay up long enough to notice and retry.
+const RETRY_SECONDS: u32 = 15;
+
+async fn fetch_all<T: Serialize + Clone + DeserializeOwned>(section: &str) -> Result<T> {
+    Ok(
+        reqwasm::http::Request::get(&format!("{}/{section}?all_listed=true", api_url().await))
+            .headers(get_headers().await)
+            .send()
+            .await?
+            .json::<ApiResponse<T>>()
+            .await?
+            .data(),
+    )
+}
+
+/// The data shared by every screen. `App` provides it once, and components read it
+/// through `use_store` and change it only through its actions.
+#[derive(Clone, Copy)]
+pub struct Store {
+    session: RwSignal<Session>,
+    household: RwSignal<Option<Household>>,
+    expenses: RwSignal<Vec<Expense>>,
+    /// Whether a screen asked for the full copy, so refreshes keep it loaded.
+    expenses_wanted: StoredValue<bool>,
+    /// Whether the full copy matches the server, as far as this device knows.
+    expenses_loaded: StoredValue<bool>,
+    revision: RwSignal<u64>,
+    categories: RwSignal<Vec<Category>>,
+    settings: RwSignal<Settings>,
+    pending_deletes: RwSignal<Vec<PendingDelete>>,
+    monthly: Memo<Vec<MonthSummary>>,
+}
+
+impl Store {
+    /// The saved session; its token is empty when nobody is logged in.
+    pub fn session(&self) -> ReadSignal<Session> {
+        self.session.read_only()
+    }
+
+    /// `None` outside a household, or with a server that has none.
+    pub fn household(&self) -> ReadSignal<Option<Household>> {
+        self.household.read_only()
+    }
+
+    pub fn member(&self, id: Uuid) -> Option<Member> {
+        self.household.with(|household| {
+            household
+                .as_ref()
+                .and_then(|household| household.member(id).cloned())
+        })
+    }
+
+    /// The email of whoever paid, blank outside a household.
+    pub fn payer_name(&self, paid_by: Option<Uuid>) -> String {
+        self.household.with(|household| {
+            household
+                .as_ref()
+                .and_then(|household| household.member(household.payer(paid_by)))
+                .map(|member| member.email.clone())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Every expense, for the screens that work on all of them. Loaded the first time
+    /// one asks; the list pages from the server instead.
+    pub fn expenses(&self) -> ReadSignal<Vec<Expense>> {
+        if !self.expenses_wanted.get_value() {
+            self.expenses_wanted.set_value(true);
+            self.load_expenses();
+        }
+        self.expenses.read_only()
+    }
+
+    /// The full copy once it is loaded, for actions that need every expense right away.
+    pub async fn all_expenses(&self) -> Result<Vec<Expense>> {
+        self.expenses_wanted.set_value(true);
+        if !self.expenses_loaded.get_value() {
+            self.expenses
+                .set(fetch_all::<Vec<Expense>>("expenses").await?);
+            self.expenses_loaded.set_value(true);
+        }
+        Ok(self.expenses.get_untracked())
+    }
+
+    /// Every expense matching `filter`, from the server when it pages, otherwise from the
+    /// full copy.
+    pub async fn matching(&self, filter: ExpenseFilter) -> Result<Vec<Expense>> {
+        let mut query = ExpenseQuery::new(filter, ListOptions::default());
+        query.limit = MAX_PAGE_SIZE;
+        let mut matching = vec![];
+        loop {
+            let Some(page) = fetch_page(&query).await? else {
+                let all = self.all_expenses().await?;
+                return Ok(query
+                    .filter
+                    .apply(&all, self.household.get_untracked().as_ref()));
+            };
+            matching.extend(page.items);
+            match page.next_cursor {
+                Some(cursor) => query = query.cursor(Some(cursor)),
+                None => return Ok(matching),
+            }
+        }
+    }
+
+    /// Changes with every add, edit, delete and refresh, so that views paging from the
+    /// server know when to load again.
+    pub fn revision(&self) -> ReadSignal<u64> {
+        self.revision.read_only()
+    }
+
+    pub fn categories(&self) -> ReadSignal<Vec<Category>> {
+        self.categories.read_only()
+    }
+
+    pub fn settings(&self) -> ReadSignal<Settings> {
+        self.settings.read_only()
+    }
+
+    /// Expenses removed but still in their undo window.
+    pub fn pending_deletes(&self) -> ReadSignal<Vec<PendingDelete>> {
+        self.pending_deletes.read_only()
+    }
+
+    pub fn category(&self, id: Uuid) -> Option<Category> {
+        self.categories.with(|categories| {
+            categories
+                .iter()
+                .find(|category| category.id == id)
+                .cloned()
+        })
+    }
+
+    /// Totals per month, oldest first, each split by category.
+    pub fn monthly(&self) -> Memo<Vec<MonthSummary>> {
+        self.monthly
+    }
+
+    pub fn month_summary(&self, month: &str) -> MonthSummary {
+        self.monthly.with(|monthly| {
+            monthly
+                .iter()
+                .find(|summary| summary.month == month)
+                .cloned()
+                .unwrap_or_else(|| MonthSummary {
+                    month: month.to_owned(),
+                    ..MonthSummary::default()
+                })
+        })
+    }
+
+    pub fn load_session(&self) {
+        let session = self.session;
+        spawn_local(async move {
+            match call::<Session>("get_session", ()).await {
+                Ok(loaded) => session.set(loaded),
+                Err(e) => error_toast(e),
+            }
+        });
+    }
+
+    /// Only the language and theme, which are all the backend hands out before the PIN is
+    /// entered.
+    pub fn load_lock_screen_settings(&self) {
+        let settings = self.settings;
+        spawn_local(async move {
+            match call::<Settings>("lock_screen_settings", ()).await {
+                Ok(loaded) => settings.set(loaded),
+                Err(e) => error_toast(e),
+            }
+        });
+    }
+
+    pub fn load_settings(&self) {
+        let settings = self.settings;
+        spawn_local(async move {
+            match call::<Settings>("get_settings", ()).await {
+                Ok(loaded) => settings.set(loaded),
+                Err(e) => error_toast(e),
+            }
+        });
+    }
+
+    pub fn load_categories(&self) {
+        let store = *self;
+        spawn_local(async move {
+            match fetch_all::<Vec<Category>>("categories").await {
+                Ok(categories) => store.categories.set(categories),
+                Err(e) => {
+                    toaster().show_with_action(
+                        ToastLevel::Error,
+                        format!("Could not load categories: {e}"),
+                        "Retry",
+                        RETRY_SECONDS,
+                        move || store.load_categories(),
+                    );
+                }
+            }
+        });
+    }
+
+    fn load_expenses(&self) {
+        let store = *self;
+        spawn_local(async move {
+            match fetch_all::<Vec<Expense>>("expenses").await {
+                Ok(expenses) => {
+                    store.expenses.set(expenses);
+                    store.expenses_loaded.set_value(true);
+                }
+                Err(e) => {
+                    toaster().show_with_action(
+                        ToastLevel::Error,
+                        format!("Could not load expenses: {e}"),
+                        "Retry",
+                        RETRY_SECONDS,
+                        move || store.load_expenses(),
+                    );
+                }
+            }
+        });
+    }
+
+    // Servers without households, like the mock one, answer with an error; that only
+    // means there is no household.
+    pub fn load_household(&self) {
+        let household = self.household;
+        spawn_local(async move {
+            household.set(fetch_household().await.ok().flatten());
+        });
+    }
+
+    /// After joining or leaving a household everything else changes too.
+    pub fn set_household(&self, household: Option<Household>) {
+        self.household.set(household);
+        self.load_categories();
+        self.reload_expenses();
+    }
+
+    /// Everything the backend keeps until the PIN is entered, and what the server has.
+    pub fn load(&self) {
+        self.load_session();
+        self.refresh();
+    }
+
+    pub fn refresh(&self) {
+        self.load_settings();
+        self.load_household();
+        self.load_categories();
+        self.reload_expenses();
+    }
+
+    /// For changes made without the store's actions. The full copy is only loaded again
+    /// if a screen uses it.
+    pub fn reload_expenses(&self) {
+        self.expenses_loaded.set_value(false);
+        if self.expenses_wanted.get_value() {
+            self.load_expenses();
+        }
+        self.revision.update(|revision| *revision += 1);
+    }
+
+    /// Applies a change made on the server to the full copy, if there is one.
+    fn expenses_changed(&self, change: impl FnOnce(&mut Vec<Expense>)) {
+        if self.expenses_loaded.get_value() {
+            self.expenses.update(change);
+        }
+        self.revision.update(|revision| *revision += 1);
+    }
+
+    /// Ends the session on the server when it can, then forgets the token and every cached
+    /// expense and category on this device. Settings are kept.
+    pub async fn sign_out(&self) -> Result<()> {
+        if let Err(e) = post_logout().await {
+            error_toast(e);
+        }
+        call::<()>("logout", ()).await?;
+        self.pending_deletes.set(vec![]);
+        self.expenses_loaded.set_value(false);
+        self.expenses.set(vec![]);
+        self.categories.set(vec![]);
+        self.household.set(None);
+        self.session.set(Session::default());
+        Ok(())
+    }
+
+    /// Changes the settings here only; saving them is up to the caller.
+    pub fn update_settings(&self, change: impl FnOnce(&mut Settings)) {
+        self.settings.update(change);
+    }
+
+    pub async fn save_settings(&self) -> Result<()> {
+        save_settings(self.settings.get_untracked()).await
+    }
+
+    pub async fn add_expense(&self, expense: Expense) -> Result<Expense> {
+        let created = post_expense(expense, false).await?;
+        self.expenses_changed(|expenses| expenses.push(created.clone()));
+        Ok(created)
+    }
+
+    /// Adds an expense that existed before, such as one from a backup or the trash, under
+    /// its old id when the server allows it.
+    pub async fn restore_expense(&self, expense: Expense) -> Result<Expense> {
+        let restored = post_expense(expense, true).await?;
+        self.expenses_changed(|expenses| {
+            expenses.retain(|listed| listed.id != restored.id);
+            expenses.push(restored.clone());
+        });
+        Ok(restored)
+    }
+
+    /// Uses the update endpoint when the server has one. Otherwise, as with the hosted
+    /// API, the changed expense is added under a new id and the old one deleted after it,
+    /// so a failure leaves the old one in place; the saved expense is returned either way.
+    pub async fn update_expense(&self, expense: Expense) -> Result<Expense> {
+        if let Some(updated) = put_expense(expense.clone()).await? {
+            self.expenses_changed(|expenses| {
+                if let Some(listed) = expenses.iter_mut().find(|listed| listed.id == updated.id) {
+                    *listed = updated.clone();
+                }
+            });
+            return Ok(updated);
+        }
+        let added = post_expense(expense.clone(), false).await?;
+        if let Err(e) = remove_post(expense.clone()).await {
+            // Two copies are worse than an edit that did not go through.
+            if let Err(undo) = remove_post(added).await {
+                error_toast(undo);
+            }
+            return Err(e);
+        }
+        self.expenses_changed(|expenses| {
+            expenses.retain(|listed| listed.id != expense.id);
+            expenses.push(added.clone());
+        });
+        Ok(added)
+    }
+
+    /// Hides the expense and only deletes it once the undo window has passed.
+    pub fn remove_expense(&self, expense: Expense) {
+        let store = *self;
+        let token = Uuid::new_v4();
+        self.pending_deletes.update(|pending| {
+            pending.push(PendingDelete {
+                token,
+                expense: expense.clone(),
+            })
+        });
+        let description = expense
+            .description
+            .as_ref()
+            .map(|description| format!(" / {description}"))
+            .unwrap_or_default();
+        toaster().show_with_action(
+            ToastLevel::Info,
+            format!(
+                "Deleted ${} {}{description}",
+                expense.amount, expense.category_name
+            ),
+            "Undo",
+            UNDO_SECONDS,
+            move || {
+                store
+                    .pending_deletes
+                    .update(|pending| pending.retain(|deleting| deleting.token != token))
+            },
+        );
+        spawn_local(async move {
+            TimeoutFuture::new(UNDO_SECONDS * 1000).await;
+            let undone = !store
+                .pending_deletes
+                .get_untracked()
+                .iter()
+                .any(|deleting| deleting.token == token);
+            if undone {
+                return;
+            }
+            let id = expense.id;
+            let deleted = delete_expense(expense).await;
+            store
+                .pending_deletes
+                .update(|pending| pending.retain(|deleting| deleting.token != token));
+            match deleted {
+                Ok(_) => {
+                    store.expenses_changed(|expenses| expenses.retain(|listed| listed.id != id))
+                }
+                Err(e) => error_toast(e),
+            }
+        });
+    }
+}
+
+/// Creates the store and keeps the device copy used for backups and alerts in sync with
+/// the full copy of the expenses. Only the look of the lock screen is loaded; `App` calls
+/// `load` once the app is unlocked.
+/// Call once, in `App`.
+pub fn provide_store() -> Store {
+    let expenses = create_rw_signal(Vec::<Expense>::new());
+    let store = Store {
+        session: create_rw_signal(Session::default()),
+        household: create_rw_signal(None),
+        expenses,
+        expenses_wanted: store_value(false),
+        expenses_loaded: store_value(false),
+        revision: create_rw_signal(0),
+        categories: create_rw_signal(vec![]),
+        settings: create_rw_signal(Settings::default()),
+        pending_deletes: create_rw_signal(vec![]),
+        monthly: create_memo(move |_| expenses.with(|expenses| monthly_summaries(expenses))),
+    };
+    provide_context(store);
+    store.load_lock_screen_settings();
+
+    // The background check reads the device copy, so alerts need every expense.
+    create_effect(move |_| {
+        let alerting = store
+            .settings
+            .with(|settings| settings.notification_rules.iter().any(|rule| rule.enabled));
+        if alerting {
+            store.expenses();
+        }
+    });
+    // Skips the initial empty values.
+    create_effect(move |initialized: Option<()>| {
+        let expenses = store.expenses.get();
+        if initialized.is_some() {
+            spawn_local(async move {
+                let args = serde_json::json!({ "expenses": expenses });
+                if let Err(e) = call::<()>("cache_expenses", args).await {
+                    return error_toast(e);
+                }
+                if let Err(e) = call::<Vec<Alert>>(
+                    "check_notifications",
+                    serde_json::json!({ "offset_minutes": utc_offset_minutes() }),
+                )
+                .await
+                {
+                    error_toast(e);
+                }
+            });
+        }
+    });
+    create_effect(move |initialized: Option<()>| {
+        let categories = store.categories.get();
+        if initialized.is_some() {
+            spawn_local(async move {
+                let args = serde_json::json!({ "categories": categories });
+                if let Err(e) = call::<()>("cache_categories", args).await {
+                    error_toast(e);
+                }
+            });
+        }
+    });
+    store
+}
+
+pub fn use_store() -> Store {
+    expect_context::<Store>()
+}
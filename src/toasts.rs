@@ -0,0 +1,162 @@
+use std::{cell::Cell, rc::Rc};
+
+use gloo::timers::future::TimeoutFuture;
+use leptos::{component, *};
+
+use crate::lock::{use_lock, LockState};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+impl ToastLevel {
+    fn class(self) -> &'static str {
+        match self {
+            ToastLevel::Info => "toast toast-info",
+            ToastLevel::Success => "toast toast-success",
+            ToastLevel::Warning => "toast toast-warning",
+            ToastLevel::Error => "toast toast-error",
+        }
+    }
+    // Problems stay up longer, so they can be read.
+    fn seconds(self) -> u32 {
+        match self {
+            ToastLevel::Info | ToastLevel::Success => 4,
+            ToastLevel::Warning => 6,
+            ToastLevel::Error => 8,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ToastAction {
+    pub label: String,
+    pub run: Rc<dyn Fn()>,
+}
+
+#[derive(Clone)]
+pub struct Toast {
+    pub id: u64,
+    pub level: ToastLevel,
+    pub message: String,
+    pub action: Option<ToastAction>,
+}
+
+/// Handle to the toast stack. Shared through context, and also kept for code that runs
+/// outside a component, like the `error_toast` calls at the end of async tasks.
+#[derive(Clone, Copy)]
+pub struct Toaster {
+    toasts: RwSignal<Vec<Toast>>,
+    next_id: StoredValue<u64>,
+}
+
+thread_local! {
+    static TOASTER: Cell<Option<Toaster>> = const { Cell::new(None) };
+}
+
+impl Toaster {
+    fn push(
+        &self,
+        level: ToastLevel,
+        message: String,
+        action: Option<ToastAction>,
+        seconds: u32,
+    ) -> u64 {
+        let id = self.next_id.get_value();
+        self.next_id.set_value(id + 1);
+        self.toasts.update(|toasts| {
+            toasts.push(Toast {
+                id,
+                level,
+                message,
+                action,
+            })
+        });
+        let toaster = *self;
+        spawn_local(async move {
+            TimeoutFuture::new(seconds * 1000).await;
+            toaster.dismiss(id);
+        });
+        id
+    }
+
+    pub fn show(&self, level: ToastLevel, message: impl Into<String>) -> u64 {
+        self.push(level, message.into(), None, level.seconds())
+    }
+
+    /// A toast with a button, like "Retry" or "Undo". It stays up for `seconds`, and
+    /// pressing the button runs `run` and dismisses it.
+    pub fn show_with_action(
+        &self,
+        level: ToastLevel,
+        message: impl Into<String>,
+        label: impl Into<String>,
+        seconds: u32,
+        run: impl Fn() + 'static,
+    ) -> u64 {
+        let action = ToastAction {
+            label: label.into(),
+            run: Rc::new(run),
+        };
+        self.push(level, message.into(), Some(action), seconds)
+    }
+
+    pub fn dismiss(&self, id: u64) {
+        self.toasts
+            .update(|toasts| toasts.retain(|toast| toast.id != id));
+    }
+}
+
+/// Creates the toaster for the app; call once, in `App`.
+pub fn provide_toaster() -> Toaster {
+    let toaster = Toaster {
+        toasts: create_rw_signal(vec![]),
+        next_id: store_value(0),
+    };
+    provide_context(toaster);
+    TOASTER.with(|cell| cell.set(Some(toaster)));
+    toaster
+}
+
+pub fn toaster() -> Toaster {
+    use_context::<Toaster>()
+        .or_else(|| TOASTER.with(Cell::get))
+        .expect("provide_toaster was not called")
+}
+
+#[component]
+pub fn toast_stack() -> impl IntoView {
+    let toaster = toaster();
+    let lock = use_lock();
+    let toasts = move || {
+        // Actions such as Undo wait until the app is unlocked.
+        let unlocked = lock.state().get() == LockState::Unlocked;
+        toaster
+            .toasts
+            .get()
+            .into_iter()
+            .map(|toast| {
+                let id = toast.id;
+                let action = toast.action.filter(|_| unlocked).map(|action| {
+                    view! {
+                        <button on:click=move|_|{ (action.run)(); toaster.dismiss(id); }>
+                            {action.label.clone()}
+                        </button>
+                    }
+                });
+                view! {
+                    <div class={toast.level.class()} role="status">
+                        <p>{toast.message}</p>
+                        {action}
+                        <button class="toast-close" title="Dismiss" on:click=move|_|toaster.dismiss(id)>"×"</button>
+                    </div>
+                }
+            })
+            .collect_view()
+    };
+
+    view! { <div class="toasts">{toasts}</div> }
+}
@@ -1,23 +1,70 @@
-use gloo::dialogs::alert;
-use leptos::error::Error;
-use reqwasm::http::Headers;
+use core_api::{
+    error::AppError,
+    models::{ApiResponse, Settings},
+    session::API_URL,
+};
+use leptos::error::{Error, Result};
+use reqwasm::http::{Headers, Response};
+use serde::{de::DeserializeOwned, Serialize};
 use wasm_bindgen::JsValue;
 
-use crate::app::invoke;
+use crate::{
+    app::try_invoke,
+    toasts::{toaster, ToastLevel},
+};
 
-pub fn error_alert(e: Error) {
-    alert(format!("Error: {}", e).as_str());
+/// This device's UTC offset in minutes, east positive.
+pub fn utc_offset_minutes() -> i32 {
+    -(js_sys::Date::new_0().get_timezone_offset() as i32)
 }
 
-pub const API_URL: &str = "https://arrebolit.com/apiFinance/api";
+pub fn error_toast(e: Error) {
+    toaster().show(ToastLevel::Error, format!("Error: {e}"));
+}
+
+pub async fn call<T: DeserializeOwned>(cmd: &str, args: impl Serialize) -> Result<T> {
+    let args = args
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| AppError::HardError(e.to_string()))?;
+    let value = try_invoke(cmd, args)
+        .await
+        .map_err(|e| AppError::HardError(e.as_string().unwrap_or(format!("{e:?}"))))?;
+    Ok(serde_wasm_bindgen::from_value(value).map_err(|e| AppError::HardError(e.to_string()))?)
+}
+
+pub async fn save_settings(settings: Settings) -> Result<()> {
+    call("set_settings", serde_json::json!({ "settings": settings })).await
+}
 
+pub async fn api_url() -> String {
+    call::<Settings>("get_settings", ())
+        .await
+        .ok()
+        .and_then(|settings| settings.server_url)
+        .unwrap_or(API_URL.to_owned())
+}
 pub async fn get_headers() -> Headers {
-    let token = invoke("get_token", JsValue::default())
+    // Refused while the app is locked.
+    let token = try_invoke("get_token", JsValue::default())
         .await
-        .as_string()
+        .ok()
+        .and_then(|token| token.as_string())
         .unwrap_or("no-token".to_owned());
     let headers = Headers::new();
     headers.append("Authorization", format!("Bearer {}", token).as_str());
     headers.append("content-type", "application/json");
     headers
 }
+
+/// The messages of a failed API reply, for showing next to the form that sent it.
+pub async fn response_errors(resp: Response) -> Vec<String> {
+    let text = resp.text().await.unwrap_or_default();
+    let errors = serde_json::from_str::<ApiResponse<()>>(&text)
+        .map(|response| response.errors())
+        .unwrap_or_default();
+    if errors.is_empty() {
+        vec![text]
+    } else {
+        errors
+    }
+}